@@ -60,6 +60,14 @@ fn main() -> anyhow::Result<ExitCode> {
             let exit_code = cmd.run()?;
             return Ok(exit_code);
         }
+        Some(AptosAnalyzerCmd::MemoryUsage(cmd)) => {
+            let exit_code = cmd.run()?;
+            return Ok(exit_code);
+        }
+        Some(AptosAnalyzerCmd::Scip(cmd)) => {
+            let exit_code = cmd.run()?;
+            return Ok(exit_code);
+        }
     }
 
     Ok(ExitCode::SUCCESS)
@@ -6,12 +6,16 @@
 
 mod bench;
 mod diagnostics;
+mod memory_usage;
 mod organize_imports;
+mod scip;
 mod utils;
 
 use crate::cli::bench::Bench;
 use crate::cli::diagnostics::Diagnostics;
+use crate::cli::memory_usage::MemoryUsage;
 use crate::cli::organize_imports::OrganizeImports;
+use crate::cli::scip::Scip;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -34,4 +38,6 @@ pub enum AptosAnalyzerCmd {
     Diagnostics(Diagnostics),
     Bench(Bench),
     OrganizeImports(OrganizeImports),
+    MemoryUsage(MemoryUsage),
+    Scip(Scip),
 }
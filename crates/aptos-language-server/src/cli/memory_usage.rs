@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli::utils;
+use crate::cli::utils::{CmdPath, CmdPathKind};
+use clap::Args;
+use ide::Analysis;
+use ide_db::assists::AssistResolveStrategy;
+use ide_diagnostics::config::DiagnosticsConfig;
+use project_model::DiscoveredManifest;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use vfs::FileId;
+
+#[derive(Debug, Args)]
+pub struct MemoryUsage {
+    /// Path to a directory with Aptos packages.
+    pub path: PathBuf,
+}
+
+impl MemoryUsage {
+    pub fn run(self) -> anyhow::Result<ExitCode> {
+        const STACK_SIZE: usize = 1024 * 1024 * 8;
+
+        let handle =
+            stdx::thread::Builder::new(stdx::thread::ThreadIntent::LatencySensitive, "BIG_STACK_THREAD")
+                .stack_size(STACK_SIZE)
+                .spawn(|| self.run_())
+                .unwrap();
+
+        handle.join()
+    }
+
+    fn run_(self) -> anyhow::Result<ExitCode> {
+        let cmd_path = CmdPath::new(&self.path)?;
+        let CmdPathKind::Workspace(ws_root) = cmd_path.kind() else {
+            eprintln!("Provide a directory.");
+            return Ok(ExitCode::FAILURE);
+        };
+        let ws_manifests = DiscoveredManifest::discover_all(&[ws_root.clone()]);
+        if ws_manifests.is_empty() {
+            eprintln!("Could not find any Aptos packages.");
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let (db, vfs) = utils::init_db(ws_manifests);
+        let ws_file_ids = utils::ws_package_roots(&db, &vfs, ws_root)
+            .iter()
+            .flat_map(|it| it.file_ids())
+            .filter(|file_id| {
+                vfs.file_path(*file_id)
+                    .name_and_extension()
+                    .is_some_and(|(_, ext)| ext == Some("move"))
+            })
+            .collect::<Vec<_>>();
+
+        let analysis = Analysis::new(db);
+
+        // fill the caches the same way the language server does
+        analysis.parallel_prime_caches(num_cpus::get_physical(), |_| ())?;
+        self.compute_diagnostics(&analysis, ws_file_ids)?;
+
+        println!("{}", analysis.memory_usage()?);
+
+        Ok(ExitCode::SUCCESS)
+    }
+
+    /// Infers types of all items in the workspace files.
+    fn compute_diagnostics(&self, analysis: &Analysis, file_ids: Vec<FileId>) -> anyhow::Result<()> {
+        let diagnostics_config = DiagnosticsConfig::test_sample();
+        for file_id in file_ids {
+            let frange = analysis.full_file_range(file_id)?;
+            analysis.semantic_diagnostics(&diagnostics_config, AssistResolveStrategy::None, frange)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+use crate::cli::utils;
+use crate::cli::utils::{CmdPath, CmdPathKind};
+use clap::Args;
+use ide::Analysis;
+use ide::moniker::ItemPath;
+use ide::static_index::{StaticIndex, TokenId, TokenStaticData};
+use ide_db::SymbolKind;
+use line_index::LineIndex;
+use paths::AbsPathBuf;
+use project_model::DiscoveredManifest;
+use scip::types as scip_types;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use syntax::TextRange;
+
+#[derive(Debug, Args)]
+pub struct Scip {
+    /// Path to a directory with Aptos packages.
+    pub path: PathBuf,
+
+    /// File to write the index to.
+    #[clap(long, default_value = "index.scip")]
+    pub output: PathBuf,
+}
+
+impl Scip {
+    pub fn run(self) -> anyhow::Result<ExitCode> {
+        const STACK_SIZE: usize = 1024 * 1024 * 8;
+
+        let handle =
+            stdx::thread::Builder::new(stdx::thread::ThreadIntent::LatencySensitive, "BIG_STACK_THREAD")
+                .stack_size(STACK_SIZE)
+                .spawn(|| self.run_())
+                .unwrap();
+
+        handle.join()
+    }
+
+    fn run_(self) -> anyhow::Result<ExitCode> {
+        let cmd_path = CmdPath::new(&self.path)?;
+        let CmdPathKind::Workspace(ws_root) = cmd_path.kind() else {
+            eprintln!("Provide a directory.");
+            return Ok(ExitCode::FAILURE);
+        };
+        let ws_manifests = DiscoveredManifest::discover_all(&[ws_root.clone()]);
+        if ws_manifests.is_empty() {
+            eprintln!("Could not find any Aptos packages.");
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let (db, vfs) = utils::init_db(ws_manifests);
+        let analysis = Analysis::new(db);
+        let static_index = analysis.static_index()?;
+
+        // vfs paths are canonical
+        let project_root = AbsPathBuf::assert_utf8(std::fs::canonicalize(&ws_root)?);
+
+        let mut symbols = SymbolGenerator::default();
+        let mut documents = vec![];
+        for file in &static_index.files {
+            let Some(file_path) = vfs.file_path(file.file_id).as_path().map(|it| it.to_path_buf())
+            else {
+                continue;
+            };
+            // local dependencies outside of the project
+            let Some(relative_path) = file_path.strip_prefix(&project_root) else {
+                continue;
+            };
+            let line_index = analysis.file_line_index(file.file_id)?;
+
+            symbols.start_document();
+            let mut occurrences = vec![];
+            let mut symbol_infos = vec![];
+            for (range, token_id) in file.tokens.iter().copied() {
+                let token = &static_index.tokens[token_id.0];
+                let symbol = symbols.symbol(token_id, token);
+                let is_definition =
+                    token.definition.file_id == file.file_id && token.definition.range == range;
+                if is_definition {
+                    symbol_infos.push(symbol_information(&symbol, token));
+                }
+                occurrences.push(scip_types::Occurrence {
+                    range: text_range_to_scip_range(&line_index, range),
+                    symbol,
+                    symbol_roles: if is_definition {
+                        scip_types::SymbolRole::Definition as i32
+                    } else {
+                        0
+                    },
+                    ..Default::default()
+                });
+            }
+            if occurrences.is_empty() {
+                continue;
+            }
+            documents.push(scip_types::Document {
+                relative_path: relative_path.as_str().to_string(),
+                language: "move".to_string(),
+                occurrences,
+                symbols: symbol_infos,
+                position_encoding: scip_types::PositionEncoding::UTF8CodeUnitOffsetFromLineStart.into(),
+                ..Default::default()
+            });
+        }
+
+        let index = scip_types::Index {
+            metadata: Some(scip_types::Metadata {
+                version: scip_types::ProtocolVersion::UnspecifiedProtocolVersion.into(),
+                tool_info: Some(scip_types::ToolInfo {
+                    name: "aptos-language-server".to_string(),
+                    version: crate::version().to_string(),
+                    arguments: vec![],
+                    ..Default::default()
+                })
+                .into(),
+                project_root: format!("file://{project_root}"),
+                text_document_encoding: scip_types::TextEncoding::UTF8.into(),
+                ..Default::default()
+            })
+            .into(),
+            documents,
+            external_symbols: vec![],
+            ..Default::default()
+        };
+
+        scip::write_message_to_file(&self.output, index)
+            .map_err(|err| anyhow::format_err!("cannot write {}: {err}", self.output.display()))?;
+        eprintln!("Generated SCIP index into {}", self.output.display());
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Assigns SCIP symbols to the definitions of the [`StaticIndex`].
+///
+/// Local symbols are scoped to the document, so only the definitions which are never referenced
+/// outside of their own document get the `local N` symbols, numbered anew in every document.
+#[derive(Default)]
+struct SymbolGenerator {
+    global_symbols: HashMap<TokenId, String>,
+    local_symbols: HashMap<TokenId, String>,
+}
+
+impl SymbolGenerator {
+    /// Forgets the local symbols of the previous document.
+    fn start_document(&mut self) {
+        self.local_symbols.clear();
+    }
+
+    fn symbol(&mut self, token_id: TokenId, token: &TokenStaticData) -> String {
+        if let Some(symbol) = self
+            .global_symbols
+            .get(&token_id)
+            .or_else(|| self.local_symbols.get(&token_id))
+        {
+            return symbol.clone();
+        }
+        if let Some(symbol) = token_global_symbol(token) {
+            self.global_symbols.insert(token_id, symbol.clone());
+            return symbol;
+        }
+        let symbol = format!("local {}", self.local_symbols.len() + 1);
+        self.local_symbols.insert(token_id, symbol.clone());
+        symbol
+    }
+}
+
+fn token_global_symbol(token: &TokenStaticData) -> Option<String> {
+    if let Some(item_path) = &token.item_path {
+        return Some(global_symbol(item_path, token.kind, None));
+    }
+    let is_document_local = token
+        .references
+        .iter()
+        .all(|it| it.range.file_id == token.definition.file_id);
+    if is_document_local {
+        return None;
+    }
+    // fields, parameters and locals used from the other documents, like `.spec.move` files,
+    // are named after the item which contains them
+    let (container_path, container_kind) = token.container.as_ref()?;
+    Some(global_symbol(
+        container_path,
+        Some(*container_kind),
+        Some((&token.name, token.kind)),
+    ))
+}
+
+/// `scip-move aptos <package> . <address>/<module>/<item><suffix>[<member><suffix>]`
+fn global_symbol(
+    item_path: &ItemPath,
+    kind: Option<SymbolKind>,
+    member: Option<(&str, Option<SymbolKind>)>,
+) -> String {
+    use scip_types::descriptor::Suffix;
+
+    let descriptor = |name: &str, suffix: Suffix| scip_types::Descriptor {
+        name: name.to_string(),
+        suffix: suffix.into(),
+        ..Default::default()
+    };
+    let mut descriptors = vec![
+        descriptor(&item_path.address, Suffix::Namespace),
+        descriptor(&item_path.module_name, Suffix::Namespace),
+    ];
+    if let Some(item_name) = &item_path.item_name {
+        let suffix = match kind {
+            Some(SymbolKind::Function | SymbolKind::Method) => Suffix::Method,
+            Some(SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Schema) => Suffix::Type,
+            _ => Suffix::Term,
+        };
+        descriptors.push(descriptor(item_name, suffix));
+    }
+    if let Some((member_name, member_kind)) = member {
+        let suffix = match member_kind {
+            Some(SymbolKind::ValueParam) => Suffix::Parameter,
+            Some(SymbolKind::TypeParam) => Suffix::TypeParameter,
+            _ => Suffix::Term,
+        };
+        descriptors.push(descriptor(member_name, suffix));
+    }
+
+    scip::symbol::format_symbol(scip_types::Symbol {
+        scheme: "scip-move".to_string(),
+        package: Some(scip_types::Package {
+            manager: "aptos".to_string(),
+            name: item_path.package_name.clone(),
+            version: String::new(),
+            ..Default::default()
+        })
+        .into(),
+        descriptors,
+        ..Default::default()
+    })
+}
+
+fn symbol_information(symbol: &str, token: &TokenStaticData) -> scip_types::SymbolInformation {
+    use scip_types::symbol_information::Kind;
+
+    let kind = match token.kind {
+        Some(SymbolKind::Module) => Kind::Module,
+        Some(SymbolKind::Const) => Kind::Constant,
+        Some(SymbolKind::Function) => Kind::Function,
+        Some(SymbolKind::Method) => Kind::Method,
+        Some(SymbolKind::Struct) => Kind::Struct,
+        Some(SymbolKind::Field) => Kind::Field,
+        Some(SymbolKind::Enum) => Kind::Enum,
+        Some(SymbolKind::EnumVariant) => Kind::EnumMember,
+        Some(SymbolKind::TypeParam) => Kind::TypeParameter,
+        Some(SymbolKind::ValueParam) => Kind::Parameter,
+        Some(SymbolKind::Local | SymbolKind::GlobalVariableDecl) => Kind::Variable,
+        _ => Kind::UnspecifiedKind,
+    };
+    scip_types::SymbolInformation {
+        symbol: symbol.to_string(),
+        documentation: token.hover.clone().into_iter().collect(),
+        display_name: token.name.clone(),
+        kind: kind.into(),
+        ..Default::default()
+    }
+}
+
+/// `[start_line, start_col, end_col]` for the single line ranges,
+/// `[start_line, start_col, end_line, end_col]` otherwise.
+fn text_range_to_scip_range(line_index: &LineIndex, range: TextRange) -> Vec<i32> {
+    let start = line_index.line_col(range.start());
+    let end = line_index.line_col(range.end());
+    if start.line == end.line {
+        vec![start.line as i32, start.col as i32, end.col as i32]
+    } else {
+        vec![
+            start.line as i32,
+            start.col as i32,
+            end.line as i32,
+            end.col as i32,
+        ]
+    }
+}
@@ -24,6 +24,7 @@ use crate::config::options::{DefaultConfigData, FullConfigInput};
 use crate::config::utils::find_movefmt_path;
 use crate::config::validation::ConfigErrors;
 use crate::lsp_ext;
+use ide::document_links::DocumentLinksConfig;
 use ide::inlay_hints::{InlayFieldsToResolve, InlayHintsConfig};
 use ide_db::assist_config::AssistConfig;
 use ide_diagnostics::config::DiagnosticsConfig;
@@ -35,6 +36,7 @@ use stdx::itertools::Itertools;
 pub struct ClientCommandsConfig {
     pub run_test: bool,
     pub debug_test: bool,
+    pub run_prover: bool,
     pub debug_transaction: bool,
     pub show_references: bool,
     pub goto_location: bool,
@@ -220,6 +222,7 @@ impl Config {
             allow_snippets: AllowSnippets::new(self.completion_snippet()),
             enable_imports_on_the_fly: self.completion_autoimport_enable().to_owned()
                 && self.caps.has_completion_item_resolve_additionalTextEdits(),
+            enable_postfix_completions: self.completion_postfix_enable().to_owned(),
         }
     }
 
@@ -229,6 +232,7 @@ impl Config {
             disabled: self.diagnostics_disabled().to_owned(),
             enable_only: self.diagnostics_enableOnly().to_owned(),
             needs_type_annotation: self.diagnostics_needsTypeAnnotation().to_owned(),
+            unused_items: self.diagnostics_unusedItems().to_owned(),
             assists_only: false,
         }
     }
@@ -241,11 +245,18 @@ impl Config {
             tuple_type_hints: self.inlayHints_typeHints_showForTuples().to_owned(),
             parameter_hints: self.inlayHints_parameterHints_enable().to_owned(),
             range_exclusive_hints: self.inlayHints_rangeExclusiveHints_enable().to_owned(),
+            const_value_hints: self.inlayHints_constValueHints_enable().to_owned(),
             hide_closure_parameter_hints: self.inlayHints_typeHints_hideClosureParameter().to_owned(),
             fields_to_resolve: InlayFieldsToResolve::from_client_capabilities(&client_capability_fields),
         }
     }
 
+    pub fn document_links_config(&self) -> DocumentLinksConfig {
+        DocumentLinksConfig {
+            address_explorer_url: self.documentLinks_addressExplorerUrl().to_owned(),
+        }
+    }
+
     pub fn inlay_hint_resolve_support_properties(&self) -> HashSet<&str> {
         self.0
             .text_document
@@ -327,6 +338,7 @@ impl Config {
         ClientCommandsConfig {
             run_test: get("move-on-aptos.runTest"),
             debug_test: get("move-on-aptos.debugTest"),
+            run_prover: get("move-on-aptos.runProver"),
             debug_transaction: get("move-on-aptos.debugTransaction"),
             show_references: get("move-on-aptos.showReferences"),
             goto_location: get("move-on-aptos.gotoLocation"),
@@ -337,6 +349,31 @@ impl Config {
         num_cpus::get_physical()
     }
 
+    pub fn library_cache_dir(&self) -> Option<AbsPathBuf> {
+        if !*self.libraryCache_enable() {
+            return None;
+        }
+        let cache_dir = match self.libraryCache_path().clone() {
+            Some(path) => path,
+            None => Utf8PathBuf::from_path_buf(dirs::cache_dir()?)
+                .ok()?
+                .join("aptos-language-server"),
+        };
+        AbsPathBuf::try_from(cache_dir).ok()
+    }
+
+    /// LRU capacities of the syntax trees and the inference results.
+    pub fn lru_capacities(&self) -> (Option<usize>, Option<usize>) {
+        (*self.lru_parseCapacity(), *self.lru_inferenceCapacity())
+    }
+
+    pub fn prime_caches_num_threads(&self) -> usize {
+        match *self.cachePriming_numThreads() {
+            0 => num_cpus::get_physical(),
+            n => n,
+        }
+    }
+
     pub fn json_schema() -> serde_json::Value {
         let mut s = FullConfigInput::json_schema();
 
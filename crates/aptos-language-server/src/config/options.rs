@@ -30,6 +30,10 @@ config_data! {
         /// Path to the `aptos-cli` executable.
         aptosPath: Option<Utf8PathBuf>                         = None,
 
+        /// How many worker threads are used to index the packages after the workspace is loaded.
+        /// The default `0` means to use the number of physical CPU cores.
+        cachePriming_numThreads: usize = 0,
+
         /// Extra command-line arguments passed to the `aptos-dap` binary.
         dap_extraArgs: Vec<String> = @verbatim: r#"["--skip-fetch-latest-git-deps"]"#,
         /// Path to aptos-dap executable for transaction debugging.
@@ -40,6 +44,8 @@ config_data! {
         /// Note that your client must specify the `additionalTextEdits` LSP client capability to
         /// truly have this feature enabled.
         completion_autoimport_enable: bool = false,
+        /// Show postfix completions like `expr.if` or `expr.let`.
+        completion_postfix_enable: bool = true,
 
         /// List of aptos-language-server diagnostics to disable.
         diagnostics_disabled: HashSet<String> = @verbatim: r#"["missing-const-doc-comment"]"#,
@@ -51,12 +57,23 @@ config_data! {
         /// Enable "needs type annotation" diagnostic, which is known to produce
         /// a lot of false-positives.
         diagnostics_needsTypeAnnotation: bool   = false,
+        /// Report private functions, constants and structs, `#[test_only]` items and `friend`
+        /// declarations, which are never used in the package.
+        diagnostics_unusedItems: bool           = true,
+
+        /// Block explorer URL for the address literals like `@0x1` and `@std`, where `{address}`
+        /// is replaced with the address value, for example
+        /// `https://explorer.aptoslabs.com/account/{address}?network=mainnet`.
+        /// Address links are disabled if not set.
+        documentLinks_addressExplorerUrl: Option<String> = None,
 
         // /// These paths (file/directories) will be ignored by aptos-language-server. They are
         // /// relative to the workspace root, and globs are not supported. You may
         // /// also need to add the folders to Code's `files.watcherExclude`.
         // files_excludeDirs: Vec<Utf8PathBuf> = vec![],
 
+        /// Whether to show the evaluated values of the constants as inlay hints.
+        inlayHints_constValueHints_enable: bool                    = false,
         /// Whether to show function parameter name inlay hints at the call
         /// site.
         inlayHints_parameterHints_enable: bool                     = true,
@@ -82,6 +99,20 @@ config_data! {
         /// Where to render annotations.
         lens_location: AnnotationLocation = AnnotationLocation::AboveName,
 
+        /// Whether to store the symbol indices of the dependency packages on disk and reuse them
+        /// across sessions.
+        libraryCache_enable: bool = true,
+        /// Directory of the on-disk cache of the dependency packages. Defaults to
+        /// `aptos-language-server` in the user cache directory.
+        libraryCache_path: Option<Utf8PathBuf> = None,
+
+        /// Number of the function and spec inference results kept in memory after the file changes,
+        /// least recently used ones are dropped. Defaults to 1024.
+        lru_inferenceCapacity: Option<usize> = None,
+        /// Number of the syntax trees kept in memory after the file changes, least recently used
+        /// ones are dropped. Defaults to 128.
+        lru_parseCapacity: Option<usize> = None,
+
         /// Path to the `movefmt` executable.
         movefmt_path: Option<Utf8PathBuf>                         = None,
         /// Additional arguments to `movefmt`.
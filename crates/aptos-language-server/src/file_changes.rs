@@ -6,9 +6,11 @@
 
 use crate::global_state::{GlobalState, LoadPackagesRequest};
 use crate::reload;
+use base_db::SourceDatabase;
 use base_db::change::FileChanges;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 use paths::AbsPathBuf;
+use std::sync::Arc;
 use stdext::line_endings::LineEndings;
 
 impl GlobalState {
@@ -27,8 +29,23 @@ impl GlobalState {
         }
 
         let _p = tracing::info_span!("GlobalState::process_changes/apply_change").entered();
+        let changed_file_ids = changes
+            .files_changed
+            .iter()
+            .map(|(it, _)| *it)
+            .collect::<Vec<_>>();
         self.analysis_host.apply_change(changes);
 
+        let diagnostics_revisions = Arc::make_mut(&mut self.diagnostics_revisions);
+        if needs_to_refresh_packages {
+            diagnostics_revisions.bump_all();
+        } else {
+            let db = self.analysis_host.raw_database();
+            for file_id in changed_file_ids {
+                diagnostics_revisions.bump_file(file_id, Some(db.file_package_id(file_id)));
+            }
+        }
+
         if needs_to_refresh_packages {
             let _p = tracing::info_span!("GlobalState::process_changes/ws_structure_change").entered();
             self.load_aptos_packages_queue.request_op(
@@ -76,6 +93,11 @@ impl GlobalState {
                 }
             }
 
+            // verification errors of the prover point into the old text
+            if self.prover_diagnostics.contains_key(&changed_file.file_id) {
+                Arc::make_mut(&mut self.prover_diagnostics).remove(&changed_file.file_id);
+            }
+
             let file_text = match changed_file.change {
                 vfs::Change::Create(bytes, _) => String::from_utf8(bytes).ok(),
                 vfs::Change::Modify(bytes, _) => String::from_utf8(bytes).ok(),
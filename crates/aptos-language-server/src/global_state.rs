@@ -14,10 +14,14 @@ use crate::lsp_ext::{MovefmtVersionError, MovefmtVersionErrorParams};
 use crate::main_loop::Task;
 use crate::op_queue::{Cause, OpQueue};
 use crate::opened_files::OpenedFiles;
+use crate::prover::ProverRun;
 use crate::task_pool::TaskPool;
+use crate::version::version;
+use base_db::package_root::PackageId;
 use camino::Utf8PathBuf;
 use crossbeam_channel::{Receiver, Sender, unbounded};
-use ide::{Analysis, AnalysisHost, Cancellable};
+use ide::{Analysis, AnalysisHost, Cancellable, ParallelPrimeCachesProgress};
+use ide_db::library_cache::LibraryCache;
 use lang::builtins_file;
 use lsp_types::{Notification, Uri};
 use parking_lot::{MappedRwLockReadGuard, RwLock, RwLockReadGuard};
@@ -38,7 +42,7 @@ pub(crate) struct LoadPackagesRequest {
 }
 
 pub(crate) struct LoadPackagesResponse {
-    pub(crate) packages_from_fs: LoadedPackages,
+    pub(crate) packages_from_fs: Arc<LoadedPackages>,
     pub(crate) force_reload_package_deps: bool,
 }
 
@@ -70,6 +74,12 @@ pub(crate) struct GlobalState {
     pub(crate) analysis_host: AnalysisHost,
     pub(crate) opened_files: OpenedFiles,
     pub(crate) package_root_config: PackageRootConfig,
+    /// Revisions of the inputs of the file diagnostics, the pull diagnostics reports with the same
+    /// `resultId` are unchanged.
+    pub(crate) diagnostics_revisions: Arc<DiagnosticsRevisions>,
+    /// Verification errors of the last prover runs, reported along with the analysis diagnostics.
+    pub(crate) prover_diagnostics: Arc<HashMap<FileId, Vec<lsp_types::Diagnostic>>>,
+    pub(crate) prover_run: Option<ProverRun>,
 
     // status
     pub(crate) shutdown_requested: bool,
@@ -84,10 +94,15 @@ pub(crate) struct GlobalState {
     pub(crate) vfs_synced_once: bool,
 
     pub(crate) scheduled_switch: Option<Cause>,
+    /// Cause of the last rebuild of the package graph.
+    pub(crate) last_reload_cause: Option<Cause>,
 
     pub(crate) all_packages: Arc<Vec<AptosPackage>>,
     // op queues
     pub(crate) load_aptos_packages_queue: OpQueue<LoadPackagesRequest, LoadPackagesResponse>,
+    pub(crate) prime_caches_queue: OpQueue,
+    /// Last reported progress of the running cache priming.
+    pub(crate) prime_caches_progress: Option<ParallelPrimeCachesProgress>,
 }
 
 /// An immutable snapshot of the world's state at a point in time.
@@ -97,9 +112,66 @@ pub(crate) struct GlobalStateSnapshot {
     opened_files: OpenedFiles,
     vfs: Arc<RwLock<(vfs::Vfs, HashMap<FileId, LineEndings>)>>,
     pub(crate) all_packages: Arc<Vec<AptosPackage>>,
+    pub(crate) workspace_status: WorkspaceStatus,
+    diagnostics_revisions: Arc<DiagnosticsRevisions>,
+    pub(crate) prover_diagnostics: Arc<HashMap<FileId, Vec<lsp_types::Diagnostic>>>,
     sender: Sender<lsp_server::Message>,
 }
 
+/// Revisions of the inputs of the file diagnostics: the text of the file, the files of its dependency
+/// packages, its prover diagnostics and the inputs of all files, like the diagnostics config
+/// and the package graph.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DiagnosticsRevisions {
+    generation: u64,
+    files: HashMap<FileId, u64>,
+    packages: HashMap<PackageId, u64>,
+}
+
+impl DiagnosticsRevisions {
+    /// Invalidates the diagnostics of all files.
+    pub(crate) fn bump_all(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Invalidates the diagnostics of the file, and of all files of the packages depending on it
+    /// if the `package_id` is passed.
+    pub(crate) fn bump_file(&mut self, file_id: FileId, package_id: Option<PackageId>) {
+        *self.files.entry(file_id).or_default() += 1;
+        if let Some(package_id) = package_id {
+            *self.packages.entry(package_id).or_default() += 1;
+        }
+    }
+
+    fn result_id(&self, file_id: FileId, dep_package_ids: &[PackageId]) -> String {
+        let file_revision = self.files.get(&file_id).copied().unwrap_or_default();
+        // revisions only grow, so the sum changes with every change of the dependencies
+        let deps_revision = dep_package_ids
+            .iter()
+            .map(|it| self.packages.get(it).copied().unwrap_or_default())
+            .sum::<u64>();
+        format!("{}-{file_revision}-{deps_revision}", self.generation)
+    }
+}
+
+/// State of the workspace loading at the moment of the snapshot, reported by the analyzer status.
+#[derive(Clone)]
+pub(crate) struct WorkspaceStatus {
+    pub(crate) vfs_synced_once: bool,
+    pub(crate) vfs_sync_in_progress: bool,
+    pub(crate) vfs_config_version: u32,
+    pub(crate) vfs_progress_config_version: u32,
+    pub(crate) last_reload_cause: Option<Cause>,
+    last_loaded_packages: Option<Arc<LoadedPackages>>,
+}
+
+impl WorkspaceStatus {
+    /// Formatted on demand, as the snapshot is taken for every request.
+    pub(crate) fn load_packages_error(&self) -> Option<String> {
+        self.last_loaded_packages.as_ref()?.display_error_for_tracing()
+    }
+}
+
 impl std::panic::UnwindSafe for GlobalStateSnapshot {}
 
 impl GlobalState {
@@ -126,6 +198,11 @@ impl GlobalState {
         };
 
         let mut analysis_host = AnalysisHost::new();
+        analysis_host
+            .raw_database_mut()
+            .set_library_cache(library_cache(&config));
+        let (parse_lru_cap, inference_lru_cap) = config.lru_capacities();
+        analysis_host.update_lru_capacities(parse_lru_cap, inference_lru_cap);
 
         let vfs = Arc::new(RwLock::new((vfs::Vfs::default(), HashMap::default())));
         {
@@ -142,6 +219,9 @@ impl GlobalState {
             config: Arc::new(config.clone()),
             analysis_host,
             opened_files: OpenedFiles::default(),
+            diagnostics_revisions: Arc::default(),
+            prover_diagnostics: Arc::default(),
+            prover_run: None,
             shutdown_requested: false,
             last_reported_status: lsp_ext::ServerStatusParams {
                 health: lsp_ext::Health::Ok,
@@ -158,9 +238,12 @@ impl GlobalState {
             vfs_sync_in_progress: false,
             vfs_synced_once: false,
             scheduled_switch: None,
+            last_reload_cause: None,
 
             all_packages: Arc::from(Vec::new()),
             load_aptos_packages_queue: OpQueue::default(),
+            prime_caches_queue: OpQueue::default(),
+            prime_caches_progress: None,
         }
     }
 
@@ -175,6 +258,19 @@ impl GlobalState {
             analysis: self.analysis_host.analysis(),
             vfs: Arc::clone(&self.vfs),
             opened_files: self.opened_files.clone(),
+            workspace_status: WorkspaceStatus {
+                vfs_synced_once: self.vfs_synced_once,
+                vfs_sync_in_progress: self.vfs_sync_in_progress,
+                vfs_config_version: self.vfs_config_version,
+                vfs_progress_config_version: self.vfs_progress_config_version,
+                last_reload_cause: self.last_reload_cause.clone(),
+                last_loaded_packages: self
+                    .load_aptos_packages_queue
+                    .last_op_result()
+                    .map(|it| Arc::clone(&it.packages_from_fs)),
+            },
+            diagnostics_revisions: Arc::clone(&self.diagnostics_revisions),
+            prover_diagnostics: Arc::clone(&self.prover_diagnostics),
             // semantic_tokens_cache: Arc::clone(&self.semantic_tokens_cache),
             sender: self.sender.clone(),
         }
@@ -236,6 +332,7 @@ impl GlobalState {
     }
 
     pub(crate) fn cancel(&mut self, request_id: lsp_server::RequestId) {
+        self.cancel_prover_run(&request_id);
         if let Some(response) = self.req_queue.incoming.cancel(request_id) {
             self.send(response.into());
         }
@@ -298,6 +395,12 @@ impl GlobalStateSnapshot {
         Some(self.opened_files.get(self.vfs_read().file_path(file_id))?.version)
     }
 
+    pub(crate) fn diagnostics_result_id(&self, file_id: FileId) -> Cancellable<String> {
+        let package_id = self.analysis.package_id(file_id)?;
+        let dep_package_ids = self.analysis.dependencies(package_id)?;
+        Ok(self.diagnostics_revisions.result_id(file_id, &dep_package_ids))
+    }
+
     pub(crate) fn url_file_version(&self, url: &Uri) -> Option<i32> {
         let path = from_proto::vfs_path(url).ok()?;
         Some(self.opened_files.get(&path)?.version)
@@ -327,13 +430,18 @@ impl GlobalStateSnapshot {
         self.send_notification(notif);
     }
 
-    pub(crate) fn ask_client_for_movefmt_update(&self, message: String) {
-        let aptos_cli = match self.config.aptos_path() {
+    /// Explicitly configured `aptos` executable, or the one found in `PATH`.
+    pub(crate) fn aptos_cli_path(&self) -> Option<Utf8PathBuf> {
+        match self.config.aptos_path() {
             Some(p) => Some(p),
             None => which::which("aptos")
                 .ok()
                 .and_then(|it| Utf8PathBuf::from_path_buf(it).ok()),
-        };
+        }
+    }
+
+    pub(crate) fn ask_client_for_movefmt_update(&self, message: String) {
+        let aptos_cli = self.aptos_cli_path();
         tracing::info!(cli = ?aptos_cli, "Ask to fetch movefmt");
         let notif = lsp_server::Notification::new(
             MovefmtVersionError::METHOD.into(),
@@ -379,3 +487,8 @@ pub(crate) fn vfs_path_to_file_id(vfs: &vfs::Vfs, vfs_path: &VfsPath) -> anyhow:
         .ok_or_else(|| anyhow::format_err!("file not found: {vfs_path}"))?;
     Ok(res)
 }
+
+pub(crate) fn library_cache(config: &Config) -> Option<LibraryCache> {
+    let cache_dir = config.library_cache_dir()?;
+    Some(LibraryCache::new(cache_dir, version().to_string()))
+}
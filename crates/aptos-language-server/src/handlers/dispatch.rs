@@ -72,6 +72,37 @@ impl RequestDispatcher<'_> {
         self
     }
 
+    /// Dispatches the request onto the current thread like `on_sync_mut`, but the handler only
+    /// starts the background work and responds to the request later, using its id.
+    pub(crate) fn on_sync_mut_deferred<R>(
+        &mut self,
+        f: fn(&mut GlobalState, lsp_server::RequestId, R::Params) -> anyhow::Result<()>,
+    ) -> &mut Self
+    where
+        R: lsp_types::Request,
+        R::Params: DeserializeOwned + panic::UnwindSafe + fmt::Debug,
+        R::Result: Serialize,
+    {
+        let (req, params, panic_context) = match self.parse::<R>() {
+            Some(it) => it,
+            None => return self,
+        };
+        let _guard =
+            tracing::info_span!("request", method = ?req.method, "request_id" = ?req.id).entered();
+        tracing::debug!(?params);
+        let result = {
+            let _pctx = DbPanicContext::enter(panic_context);
+            f(self.global_state, req.id.clone(), params)
+        };
+        if let Err(err) = result
+            && let Ok(response) = result_to_response::<R>(req.id, Err(err))
+        {
+            self.global_state.respond(response);
+        }
+
+        self
+    }
+
     /// Dispatches the request onto the current thread.
     pub(crate) fn on_sync<R>(
         &mut self,
@@ -4,7 +4,7 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
-use crate::global_state::GlobalStateSnapshot;
+use crate::global_state::{GlobalState, GlobalStateSnapshot};
 use crate::lsp::utils::{all_edits_are_disjoint, invalid_params_error};
 use crate::lsp::{LspError, from_proto, to_proto};
 use crate::movefmt::run_movefmt;
@@ -13,15 +13,17 @@ use ide::Cancellable;
 use ide::annotations::AnnotationConfig;
 use ide::inlay_hints::InlayFieldsToResolve;
 use ide_db::assists::{AssistKind, AssistResolveStrategy, SingleResolve};
+use ide_db::source_change::{FileSystemEdit, SourceChange};
 use ide_db::symbol_index::Query;
 use line_index::TextRange;
 use lsp_server::ErrorCode;
 use lsp_types::{
     CodeActionResponse, CodeLens, DocumentChange, DocumentHighlightKind, InlayHint, InlayHintParams,
-    Location, PrepareRenameResult, RenameParams, ResourceOperationKind, SemanticTokensParams,
-    SemanticTokensRangeParams, SymbolInformation, TextDocumentIdentifier, Uri, WorkspaceEdit,
-    WorkspaceSymbolParams,
+    Location, Notification, PrepareRenameResult, RenameParams, ResourceOperationKind,
+    SemanticTokensParams, SemanticTokensRangeParams, SymbolInformation, TextDocumentIdentifier, Uri,
+    WorkspaceEdit, WorkspaceSymbolParams,
 };
+use std::collections::HashMap;
 use std::env;
 use std::hash::DefaultHasher;
 use stdx::format_to;
@@ -389,6 +391,10 @@ pub(crate) fn empty_diagnostic_report() -> lsp_types::DocumentDiagnosticReport {
     )
 }
 
+pub(crate) fn empty_workspace_diagnostic_report() -> lsp_types::WorkspaceDiagnosticReport {
+    lsp_types::WorkspaceDiagnosticReport { items: vec![] }
+}
+
 pub(crate) fn handle_document_diagnostics(
     snap: GlobalStateSnapshot,
     params: lsp_types::DocumentDiagnosticParams,
@@ -397,13 +403,145 @@ pub(crate) fn handle_document_diagnostics(
 
     let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
 
+    let result_id = snap.diagnostics_result_id(file_id)?;
+    if params.previous_result_id.as_ref() == Some(&result_id) {
+        return Ok(
+            lsp_types::DocumentDiagnosticReport::RelatedUnchangedDocumentDiagnosticReport(
+                lsp_types::RelatedUnchangedDocumentDiagnosticReport {
+                    unchanged_document_diagnostic_report: lsp_types::UnchangedDocumentDiagnosticReport {
+                        result_id,
+                    },
+                    related_documents: None,
+                },
+            ),
+        );
+    }
+
+    let diagnostics = file_diagnostics(&snap, file_id)?;
+    Ok(
+        lsp_types::DocumentDiagnosticReport::RelatedFullDocumentDiagnosticReport(
+            lsp_types::RelatedFullDocumentDiagnosticReport {
+                full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                    result_id: Some(result_id),
+                    items: diagnostics,
+                },
+                related_documents: None,
+            },
+        ),
+    )
+}
+
+/// Reports for all files of the local packages. Files with the `previousResultId` of the current
+/// revisions of their inputs are reported as unchanged. If the client passes the partial result token,
+/// every report is sent with a separate `$/progress` notification as soon as it's computed.
+pub(crate) fn handle_workspace_diagnostics(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::WorkspaceDiagnosticParams,
+) -> anyhow::Result<lsp_types::WorkspaceDiagnosticReport> {
+    let _p = tracing::info_span!("handle_workspace_diagnostics").entered();
+
+    let previous_result_ids = params
+        .previous_result_ids
+        .into_iter()
+        .map(|it| (it.uri, it.value))
+        .collect::<HashMap<_, _>>();
+
+    let mut file_ids = vec![];
+    for (file_id, path) in snap.vfs_read().iter() {
+        if path
+            .as_path()
+            .is_some_and(|it| it.as_utf8_path().extension() == Some("move"))
+        {
+            file_ids.push(file_id);
+        }
+    }
+    let mut local_file_ids = vec![];
+    for file_id in file_ids {
+        if snap.analysis.is_local_package(file_id)? {
+            local_file_ids.push(file_id);
+        }
+    }
+
+    let work_done_token = params.work_done_progress_params.work_done_token;
+    let partial_result_token = params.partial_result_params.partial_result_token;
+    send_progress(
+        &snap,
+        work_done_token.as_ref(),
+        lsp_types::WorkDoneProgressBegin {
+            title: "Checking workspace".to_owned(),
+            cancellable: Some(false),
+            message: None,
+            percentage: Some(0),
+        },
+    );
+
+    let n_files = local_file_ids.len();
+    let mut items = vec![];
+    for (i, file_id) in local_file_ids.into_iter().enumerate() {
+        let uri = to_proto::url(&snap, file_id);
+        let version = snap.file_version(file_id);
+        let result_id = snap.diagnostics_result_id(file_id)?;
+        let report = if previous_result_ids.get(&uri) == Some(&result_id) {
+            lsp_types::WorkspaceDocumentDiagnosticReport::WorkspaceUnchangedDocumentDiagnosticReport(
+                lsp_types::WorkspaceUnchangedDocumentDiagnosticReport {
+                    uri,
+                    version,
+                    unchanged_document_diagnostic_report: lsp_types::UnchangedDocumentDiagnosticReport {
+                        result_id,
+                    },
+                },
+            )
+        } else {
+            lsp_types::WorkspaceDocumentDiagnosticReport::WorkspaceFullDocumentDiagnosticReport(
+                lsp_types::WorkspaceFullDocumentDiagnosticReport {
+                    uri,
+                    version,
+                    full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
+                        result_id: Some(result_id),
+                        items: file_diagnostics(&snap, file_id)?,
+                    },
+                },
+            )
+        };
+        match &partial_result_token {
+            Some(token) => send_progress(
+                &snap,
+                Some(token),
+                lsp_types::WorkspaceDiagnosticReportPartialResult { items: vec![report] },
+            ),
+            None => items.push(report),
+        }
+        send_progress(
+            &snap,
+            work_done_token.as_ref(),
+            lsp_types::WorkDoneProgressReport {
+                cancellable: Some(false),
+                message: Some(format!("{}/{n_files}", i + 1)),
+                percentage: Some(((i + 1) * 100 / n_files) as u32),
+            },
+        );
+    }
+
+    send_progress(
+        &snap,
+        work_done_token.as_ref(),
+        lsp_types::WorkDoneProgressEnd { message: None },
+    );
+
+    Ok(lsp_types::WorkspaceDiagnosticReport { items })
+}
+
+fn file_diagnostics(
+    snap: &GlobalStateSnapshot,
+    file_id: FileId,
+) -> anyhow::Result<Vec<lsp_types::Diagnostic>> {
     let mut config = snap.config.diagnostics_config();
     if !config.enabled {
-        return Ok(empty_diagnostic_report());
+        return Ok(vec![]);
     }
 
     if !snap.analysis.is_local_package(file_id)? {
-        return Ok(empty_diagnostic_report());
+        return Ok(vec![]);
     }
     let package_metadata = snap.analysis.package_metadata(file_id)?;
     if package_metadata.is_none_or(|it| !it.resolve_deps) {
@@ -412,7 +550,7 @@ pub(crate) fn handle_document_diagnostics(
     }
 
     let line_index = snap.file_line_index(file_id)?;
-    let diagnostics = snap
+    let mut diagnostics: Vec<_> = snap
         .analysis
         .full_diagnostics(&config, AssistResolveStrategy::None, file_id)?
         .into_iter()
@@ -423,18 +561,29 @@ pub(crate) fn handle_document_diagnostics(
                 return Some(diagnostic);
             }
             None
-        });
-    Ok(
-        lsp_types::DocumentDiagnosticReport::RelatedFullDocumentDiagnosticReport(
-            lsp_types::RelatedFullDocumentDiagnosticReport {
-                full_document_diagnostic_report: lsp_types::FullDocumentDiagnosticReport {
-                    result_id: Some("aptos-language-server".to_owned()),
-                    items: diagnostics.collect(),
-                },
-                related_documents: None,
-            },
-        ),
-    )
+        })
+        .collect();
+    if let Some(prover_diagnostics) = snap.prover_diagnostics.get(&file_id) {
+        diagnostics.extend(prover_diagnostics.iter().cloned());
+    }
+    Ok(diagnostics)
+}
+
+fn send_progress(
+    snap: &GlobalStateSnapshot,
+    token: Option<&lsp_types::ProgressToken>,
+    value: impl serde::Serialize,
+) {
+    let Some(token) = token else {
+        return;
+    };
+    snap.send_notification(lsp_server::Notification::new(
+        lsp_types::ProgressNotification::METHOD.into(),
+        lsp_types::ProgressParams {
+            token: token.clone(),
+            value: serde_json::to_value(value).unwrap(),
+        },
+    ));
 }
 
 fn lsp_diagnostic(
@@ -564,6 +713,21 @@ pub(crate) fn handle_rename(
         .rename(position, &params.new_name)?
         .map_err(to_proto::rename_error)?;
 
+    // module files can only be renamed along with the module if the client supports it,
+    // rename just the module otherwise
+    let supports_file_renames = snap
+        .config
+        .workspace_edit_resource_operations()
+        .is_some_and(|it| it.contains(&ResourceOperationKind::Rename));
+    if !supports_file_renames {
+        change.file_system_edits.retain(|it| {
+            !matches!(
+                it,
+                FileSystemEdit::MoveFile { .. } | FileSystemEdit::MoveDir { .. }
+            )
+        });
+    }
+
     // this is kind of a hack to prevent double edits from happening when moving files
     // When a module gets renamed by renaming the mod declaration this causes the file to move
     // which in turn will trigger a WillRenameFiles request to the server for which we reply with a
@@ -585,6 +749,48 @@ pub(crate) fn handle_rename(
     Ok(Some(workspace_edit))
 }
 
+pub(crate) fn handle_will_rename_files(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::RenameFilesParams,
+) -> anyhow::Result<Option<WorkspaceEdit>> {
+    let _p = tracing::info_span!("handle_will_rename_files").entered();
+
+    let mut source_change = SourceChange::default();
+    for file_rename in params.files {
+        let Some((file_id, new_name_stem)) = renamed_move_file(&snap, &file_rename) else {
+            continue;
+        };
+        if let Some(change) = snap.analysis.will_rename_file(file_id, &new_name_stem)? {
+            // no collect here because we want to merge text edits on same file ids
+            source_change.extend(change.source_file_edits);
+        }
+    }
+    if source_change.source_file_edits.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(to_proto::workspace_edit(&snap, source_change)?))
+}
+
+/// Only renames of the `.move` files within the same directory are supported.
+fn renamed_move_file(
+    snap: &GlobalStateSnapshot,
+    file_rename: &lsp_types::FileRename,
+) -> Option<(FileId, String)> {
+    let old_uri = file_rename.old_uri.parse::<Uri>().ok()?;
+    let new_uri = file_rename.new_uri.parse::<Uri>().ok()?;
+    let old_path = from_proto::abs_path(&old_uri).ok()?;
+    let new_path = from_proto::abs_path(&new_uri).ok()?;
+    if old_path.parent() != new_path.parent() {
+        return None;
+    }
+    if new_path.extension() != Some("move") {
+        return None;
+    }
+    let new_name_stem = new_path.file_stem()?.to_string();
+    let file_id = from_proto::file_id(snap, &old_uri).ok()?;
+    Some((file_id, new_name_stem))
+}
+
 pub(crate) fn handle_view_syntax_tree(
     snap: GlobalStateSnapshot,
     params: lsp_ext::ViewSyntaxTreeParams,
@@ -633,6 +839,33 @@ pub(crate) fn handle_references(
     Ok(Some(locations))
 }
 
+pub(crate) fn handle_moniker(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::MonikerParams,
+) -> anyhow::Result<Option<Vec<lsp_types::Moniker>>> {
+    let _p = tracing::info_span!("handle_moniker").entered();
+
+    let position = from_proto::file_position(&snap, params.text_document_position_params)?;
+    let Some(monikers) = snap.analysis.moniker(position)? else {
+        return Ok(None);
+    };
+    Ok(Some(monikers.info.into_iter().map(to_proto::moniker).collect()))
+}
+
+pub(crate) fn handle_symbol_id(
+    snap: GlobalStateSnapshot,
+    params: lsp_ext::SymbolIdParams,
+) -> anyhow::Result<Vec<Location>> {
+    let _p = tracing::info_span!("handle_symbol_id").entered();
+
+    let nav_targets = snap.analysis.resolve_moniker_identifier(&params.identifier)?;
+    let locations = nav_targets
+        .iter()
+        .map(|nav| to_proto::location_from_nav(&snap, nav))
+        .collect::<Cancellable<Vec<_>>>()?;
+    Ok(locations)
+}
+
 pub(crate) fn handle_formatting(
     snap: GlobalStateSnapshot,
     params: lsp_types::DocumentFormattingParams,
@@ -644,42 +877,55 @@ pub(crate) fn handle_formatting(
 
 pub(crate) fn handle_analyzer_status(
     snap: GlobalStateSnapshot,
-    _params: lsp_ext::AnalyzerStatusParams,
+    params: lsp_ext::AnalyzerStatusParams,
 ) -> anyhow::Result<String> {
     let _p = tracing::info_span!("handle_analyzer_status").entered();
 
     let mut buf = String::new();
 
-    // let mut file_id = None;
-    // if let Some(tdi) = params.text_document {
-    //     match from_proto::file_id(&snap, &tdi.uri) {
-    //         Ok(it) => file_id = Some(it),
-    //         Err(_) => format_to!(buf, "file {} not found in vfs", tdi.uri),
-    //     }
-    // }
+    let mut file_id = None;
+    if let Some(tdi) = params.text_document {
+        match from_proto::file_id(&snap, &tdi.uri) {
+            Ok(it) => file_id = Some(it),
+            Err(err) => format_to!(buf, "{err}\n\n"),
+        }
+    }
 
+    // packages and named addresses are listed by the analysis status below
     if snap.all_packages.is_empty() {
         buf.push_str("No packages\n")
     } else {
-        buf.push_str("Packages:\n");
-        format_to!(buf, "Loaded {:?} packages.\n", snap.all_packages.len(),);
-
-        format_to!(
-            buf,
-            "Package root folders: {:?}",
-            snap.all_packages
-                .iter()
-                .map(|ws| ws.content_root())
-                .collect::<Vec<_>>()
-        );
+        format_to!(buf, "Loaded {} packages\n", snap.all_packages.len());
     }
-    // buf.push_str("\nAnalysis:\n");
-    // buf.push_str(
-    //     &snap
-    //         .analysis
-    //         .status(file_id)
-    //         .unwrap_or_else(|_| "Analysis retrieval was cancelled".to_owned()),
-    // );
+
+    let status = &snap.workspace_status;
+    buf.push_str("\nVFS:\n");
+    format_to!(buf, "  files: {}\n", snap.vfs_read().iter().count());
+    format_to!(
+        buf,
+        "  synced: {}, sync in progress: {}, config version: {}/{}\n",
+        status.vfs_synced_once,
+        status.vfs_sync_in_progress,
+        status.vfs_progress_config_version,
+        status.vfs_config_version
+    );
+    format_to!(buf, "  file watcher: {:?}\n", snap.config.files().watcher);
+    format_to!(
+        buf,
+        "  last reload cause: {}\n",
+        status.last_reload_cause.as_deref().unwrap_or("<none>")
+    );
+    if let Some(load_packages_error) = status.load_packages_error() {
+        format_to!(buf, "  packages loading error: {load_packages_error}\n");
+    }
+
+    buf.push_str("\nAnalysis:\n");
+    buf.push_str(
+        &snap
+            .analysis
+            .status(file_id)
+            .unwrap_or_else(|_| "Analysis retrieval was cancelled".to_owned()),
+    );
 
     buf.push_str("\nVersion: \n");
     format_to!(buf, "{}", crate::version());
@@ -690,6 +936,31 @@ pub(crate) fn handle_analyzer_status(
     Ok(buf)
 }
 
+pub(crate) fn handle_memory_usage(snap: GlobalStateSnapshot, _params: ()) -> anyhow::Result<String> {
+    let _p = tracing::info_span!("handle_memory_usage").entered();
+
+    let memory_usage = snap.analysis.memory_usage()?;
+    Ok(memory_usage.to_string())
+}
+
+pub(crate) fn handle_document_link(
+    snap: GlobalStateSnapshot,
+    params: lsp_types::DocumentLinkParams,
+) -> anyhow::Result<Option<Vec<lsp_types::DocumentLink>>> {
+    let _p = tracing::info_span!("handle_document_link").entered();
+
+    let file_id = from_proto::file_id(&snap, &params.text_document.uri)?;
+    let line_index = snap.file_line_index(file_id)?;
+    let links = snap
+        .analysis
+        .document_links(&snap.config.document_links_config(), file_id)?;
+    let res = links
+        .into_iter()
+        .map(|link| to_proto::document_link(&snap, &line_index, link))
+        .collect::<Cancellable<Vec<_>>>()?;
+    Ok(Some(res))
+}
+
 pub(crate) fn handle_document_highlight(
     snap: GlobalStateSnapshot,
     params: lsp_types::DocumentHighlightParams,
@@ -803,6 +1074,15 @@ pub(crate) fn handle_inlay_hints_resolve(
         .unwrap_or(original_hint))
 }
 
+pub(crate) fn handle_run_prover(
+    state: &mut GlobalState,
+    request_id: lsp_server::RequestId,
+    params: lsp_ext::AptosRunnableArgs,
+) -> anyhow::Result<()> {
+    let _p = tracing::info_span!("handle_run_prover").entered();
+    state.start_prover_run(request_id, params)
+}
+
 pub(crate) fn handle_organize_imports(
     snap: GlobalStateSnapshot,
     params: lsp_ext::OrganizeImportsParams,
@@ -21,6 +21,7 @@ pub mod cli;
 mod file_changes;
 pub mod lsp;
 mod movefmt;
+mod prover;
 pub mod toolchain;
 pub mod tracing;
 
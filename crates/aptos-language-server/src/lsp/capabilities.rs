@@ -11,11 +11,12 @@ use ide::inlay_hints::InlayFieldsToResolve;
 use line_index::WideEncoding;
 use lsp_types::{
     CodeActionKind, CodeActionOptions, CodeActionProvider, CodeLensOptions, CompletionOptions,
-    DefinitionProvider, DocumentFormattingProvider, DocumentHighlightProvider, DocumentSymbolProvider,
-    HoverProvider, InlayHintOptions, InlayHintProvider, PositionEncodingKind, ReferencesProvider,
-    RenameOptions, RenameProvider, SelectionRangeProvider, SemanticTokensLegend, SemanticTokensOptions,
-    ServerCapabilities, ServerCompletionItemOptions, SignatureHelpOptions, TextDocumentSync,
-    TextDocumentSyncKind, WorkDoneProgressOptions, WorkspaceSymbolProvider,
+    DefinitionProvider, DocumentFormattingProvider, DocumentHighlightProvider, DocumentLinkOptions,
+    DocumentSymbolProvider, HoverProvider, InlayHintOptions, InlayHintProvider, MonikerProvider,
+    PositionEncodingKind, ReferencesProvider, RenameOptions, RenameProvider, SelectionRangeProvider,
+    SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities, ServerCompletionItemOptions,
+    SignatureHelpOptions, TextDocumentSync, TextDocumentSyncKind, WorkDoneProgressOptions,
+    WorkspaceSymbolProvider,
 };
 use std::collections::HashSet;
 
@@ -74,42 +75,28 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             work_done_progress_options: WorkDoneProgressOptions { work_done_progress: None },
         })),
         // linked_editing_range_provider: None,
-        // document_link_provider: None,
+        document_link_provider: Some(DocumentLinkOptions {
+            resolve_provider: Some(false),
+            work_done_progress_options: WorkDoneProgressOptions { work_done_progress: None },
+        }),
         // color_provider: None,
         // execute_command_provider: None,
-        // workspace: Some(WorkspaceServerCapabilities {
-        //     workspace_folders: Some(WorkspaceFoldersServerCapabilities {
-        //         supported: Some(true),
-        //         change_notifications: Some(OneOf::Left(true)),
-        //     }),
-        //     file_operations: Some(WorkspaceFileOperationsServerCapabilities {
-        //         did_create: None,
-        //         will_create: None,
-        //         did_rename: None,
-        //         will_rename: Some(FileOperationRegistrationOptions {
-        //             filters: vec![
-        //                 FileOperationFilter {
-        //                     scheme: Some(String::from("file")),
-        //                     pattern: FileOperationPattern {
-        //                         glob: String::from("**/*.rs"),
-        //                         matches: Some(FileOperationPatternKind::File),
-        //                         options: None,
-        //                     },
-        //                 },
-        //                 FileOperationFilter {
-        //                     scheme: Some(String::from("file")),
-        //                     pattern: FileOperationPattern {
-        //                         glob: String::from("**"),
-        //                         matches: Some(FileOperationPatternKind::Folder),
-        //                         options: None,
-        //                     },
-        //                 },
-        //             ],
-        //         }),
-        //         did_delete: None,
-        //         will_delete: None,
-        //     }),
-        // }),
+        workspace: Some(lsp_types::WorkspaceOptions {
+            file_operations: Some(lsp_types::FileOperationOptions {
+                will_rename: Some(lsp_types::FileOperationRegistrationOptions {
+                    filters: vec![lsp_types::FileOperationFilter {
+                        scheme: Some(String::from("file")),
+                        pattern: lsp_types::FileOperationPattern {
+                            glob: String::from("**/*.move"),
+                            matches: Some(lsp_types::FileOperationPatternKind::File),
+                            options: None,
+                        },
+                    }],
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
         // call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
         semantic_tokens_provider: Some(
             SemanticTokensOptions {
@@ -126,7 +113,7 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             }
             .into(),
         ),
-        // moniker_provider: None,
+        moniker_provider: Some(MonikerProvider::Bool(true)),
         inlay_hint_provider: Some(InlayHintProvider::InlayHintOptions(InlayHintOptions {
             resolve_provider: Some(config.caps().inlay_hints_resolve_provider()),
             work_done_progress_options: Default::default(),
@@ -135,8 +122,7 @@ pub fn server_capabilities(config: &Config) -> ServerCapabilities {
             lsp_types::DiagnosticOptions {
                 identifier: Some("aptos-language-server".to_owned()),
                 inter_file_dependencies: true,
-                // FIXME
-                workspace_diagnostics: false,
+                workspace_diagnostics: true,
                 work_done_progress_options: WorkDoneProgressOptions { work_done_progress: None },
             },
         )),
@@ -255,9 +241,9 @@ impl ClientCapabilities {
                     // Ideally we would base this off of the client capabilities
                     // but the client is supposed to fall back gracefully for unknown values.
                     code_action_kinds: Some(vec![
-                        // CodeActionKind::EMPTY,
+                        CodeActionKind::Empty,
                         CodeActionKind::QuickFix,
-                        // CodeActionKind::REFACTOR,
+                        CodeActionKind::Refactor,
                         // CodeActionKind::REFACTOR_EXTRACT,
                         // CodeActionKind::REFACTOR_INLINE,
                         // CodeActionKind::REFACTOR_REWRITE,
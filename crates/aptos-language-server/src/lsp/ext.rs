@@ -28,6 +28,31 @@ pub struct AnalyzerStatusParams {
     pub text_document: Option<TextDocumentIdentifier>,
 }
 
+pub enum MemoryUsage {}
+
+impl Request for MemoryUsage {
+    type Params = ();
+    type Result = String;
+    const METHOD: LspRequestMethod = LspRequestMethod::new("aptos-language-server/memoryUsage");
+    const MESSAGE_DIRECTION: MessageDirection = MessageDirection::ClientToServer;
+}
+
+pub enum SymbolId {}
+
+impl Request for SymbolId {
+    type Params = SymbolIdParams;
+    type Result = Vec<lsp_types::Location>;
+    const METHOD: LspRequestMethod = LspRequestMethod::new("aptos-language-server/symbolId");
+    const MESSAGE_DIRECTION: MessageDirection = MessageDirection::ClientToServer;
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolIdParams {
+    /// Moniker identifier of the item, like `AptosFramework/0x1::coin::transfer`.
+    pub identifier: String,
+}
+
 pub enum ReloadWorkspace {}
 
 impl Request for ReloadWorkspace {
@@ -169,6 +194,23 @@ pub struct AptosRunnableArgs {
     pub dep_roots: Vec<Utf8PathBuf>,
 }
 
+pub enum RunProver {}
+
+impl Request for RunProver {
+    type Params = AptosRunnableArgs;
+    type Result = RunProverResult;
+    const METHOD: LspRequestMethod = LspRequestMethod::new("aptos-language-server/runProver");
+    const MESSAGE_DIRECTION: MessageDirection = MessageDirection::ClientToServer;
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunProverResult {
+    pub success: bool,
+    pub n_errors: usize,
+    pub output: String,
+}
+
 pub enum OrganizeImports {}
 
 impl Request for OrganizeImports {
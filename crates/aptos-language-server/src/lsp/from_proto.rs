@@ -106,6 +106,7 @@ pub(crate) fn file_range_uri(
 
 pub(crate) fn assist_kind(kind: lsp_types::CodeActionKind) -> Option<AssistKind> {
     let assist_kind = match &kind {
+        k if k == &lsp_types::CodeActionKind::Empty => AssistKind::Generate,
         k if k == &lsp_types::CodeActionKind::QuickFix => AssistKind::QuickFix,
         k if k == &lsp_types::CodeActionKind::Refactor => AssistKind::Refactor,
         k if k == &lsp_types::CodeActionKind::RefactorExtract => AssistKind::RefactorExtract,
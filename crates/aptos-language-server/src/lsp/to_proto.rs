@@ -12,10 +12,12 @@ use crate::lsp::{LspError, semantic_tokens};
 use crate::{Config, lsp_ext};
 use camino::{Utf8Component, Utf8PathBuf, Utf8Prefix};
 use ide::annotations::{Annotation, AnnotationKind};
+use ide::document_links::{DocumentLink, DocumentLinkTarget};
 use ide::inlay_hints::{
     InlayFieldsToResolve, InlayHint, InlayHintLabel, InlayHintLabelPart, InlayHintPosition, InlayKind,
     InlayTooltip, LazyProperty,
 };
+use ide::moniker::{MonikerKind, MonikerResult, MonikerUniqueness};
 use ide::runnables::{Runnable, RunnableKind};
 use ide::syntax_highlighting::tags::{Highlight, HlOperator, HlPunct, HlTag};
 use ide::{Cancellable, HlRange, NavigationTarget, SignatureHelp};
@@ -279,6 +281,51 @@ pub(crate) fn location(
     Ok(loc)
 }
 
+pub(crate) fn document_link(
+    snap: &GlobalStateSnapshot,
+    line_index: &LineIndex,
+    link: DocumentLink,
+) -> Cancellable<lsp_types::DocumentLink> {
+    let target = match link.target {
+        DocumentLinkTarget::Url(url) => lsp_types::Uri::parse(&url).ok(),
+        DocumentLinkTarget::File(path) => path.as_path().map(url_from_abs_path),
+        DocumentLinkTarget::Item(nav) => {
+            // VS Code opens the file at the `#L<line>,<column>` position
+            let target_line_index = snap.file_line_index(nav.file_id)?;
+            let position = lsp_position(&target_line_index, nav.focus_or_full_range().start());
+            let file_url = url(snap, nav.file_id);
+            lsp_types::Uri::parse(&format!(
+                "{}#L{},{}",
+                file_url.as_str(),
+                position.line + 1,
+                position.character + 1
+            ))
+            .ok()
+        }
+    };
+    Ok(lsp_types::DocumentLink {
+        range: lsp_range(line_index, link.range),
+        target,
+        tooltip: link.tooltip,
+        data: None,
+    })
+}
+
+pub(crate) fn moniker(moniker: MonikerResult) -> lsp_types::Moniker {
+    lsp_types::Moniker {
+        scheme: ide::moniker::MONIKER_SCHEME.to_owned(),
+        identifier: moniker.identifier(),
+        unique: match moniker.uniqueness {
+            MonikerUniqueness::Global => lsp_types::UniquenessLevel::Scheme,
+            MonikerUniqueness::Package => lsp_types::UniquenessLevel::Project,
+        },
+        kind: Some(match moniker.kind {
+            MonikerKind::Import => lsp_types::MonikerKind::Import,
+            MonikerKind::Export => lsp_types::MonikerKind::Export,
+        }),
+    }
+}
+
 /// Prefer using `location_link`, if the client has the cap.
 pub(crate) fn location_from_nav(
     snap: &GlobalStateSnapshot,
@@ -524,9 +571,23 @@ pub(crate) fn text_document_edit(
 ) -> Cancellable<lsp_types::TextDocumentEdit> {
     let text_document = optional_versioned_text_document_identifier(snap, file_id);
     let line_index = snap.file_line_index(file_id)?;
+    let annotation_id = edit
+        .change_annotation()
+        .filter(|_| snap.config.change_annotation_support())
+        .map(|it| it.to_string());
     let edits = {
         edit.into_iter()
-            .map(|it| Edit::TextEdit(lsp_text_edit(&line_index, it)))
+            .map(|it| {
+                let text_edit = lsp_text_edit(&line_index, it);
+                match &annotation_id {
+                    Some(annotation_id) => Edit::AnnotatedTextEdit(lsp_types::AnnotatedTextEdit {
+                        range: text_edit.range,
+                        new_text: text_edit.new_text,
+                        annotation_id: annotation_id.clone(),
+                    }),
+                    None => Edit::TextEdit(text_edit),
+                }
+            })
             .collect::<Vec<_>>()
     };
     Ok(lsp_types::TextDocumentEdit { text_document, edits })
@@ -562,6 +623,16 @@ pub(crate) fn text_document_ops(
                 ));
             }
         }
+        FileSystemEdit::MoveFile { src, dst } => {
+            let old_uri = snap.file_id_to_url(src);
+            let new_uri = snap.anchored_path(&dst);
+            ops.push(lsp_types::DocumentChange::RenameFile(lsp_types::RenameFile {
+                old_uri,
+                new_uri,
+                options: None,
+                annotation_id: None,
+            }));
+        }
         _ => (),
     }
     Ok(ops)
@@ -588,11 +659,33 @@ pub(crate) fn workspace_edit(
         let edit = text_document_edit(snap, file_id, edit)?;
         document_changes.push(lsp_types::DocumentChange::TextDocumentEdit(edit));
     }
+    // files are renamed after the edits, which refer to them by the old paths
+    for op in source_change.file_system_edits {
+        if !matches!(op, FileSystemEdit::CreateFile { .. }) {
+            let ops = text_document_ops(snap, op)?;
+            document_changes.extend_from_slice(&ops);
+        }
+    }
+
+    let change_annotations = snap.config.change_annotation_support().then(|| {
+        source_change
+            .annotations
+            .into_iter()
+            .map(|(id, annotation)| {
+                let annotation = lsp_types::ChangeAnnotation {
+                    label: annotation.label,
+                    needs_confirmation: Some(annotation.needs_confirmation),
+                    description: annotation.description,
+                };
+                (id.to_string(), annotation)
+            })
+            .collect()
+    });
 
     Ok(lsp_types::WorkspaceEdit {
         changes: None,
         document_changes: Some(document_changes),
-        change_annotations: None,
+        change_annotations,
     })
 }
 
@@ -981,6 +1074,16 @@ pub(crate) fn code_lens(
                             });
                         }
                     }
+                    RunnableKind::ProveFun { .. } | RunnableKind::ProveModule { .. } => {
+                        if client_commands_config.run_prover {
+                            let command = command::run_prover(&r, &title);
+                            acc.push(lsp_types::CodeLens {
+                                range: annotation_range,
+                                command: Some(command),
+                                data: None,
+                            });
+                        }
+                    }
                     RunnableKind::Transaction { .. } => {
                         if client_commands_config.debug_transaction && snap.config.dap().is_available() {
                             let command = command::debug_transaction(&r);
@@ -991,7 +1094,6 @@ pub(crate) fn code_lens(
                             });
                         }
                     }
-                    _ => (),
                 }
             }
         }
@@ -1089,6 +1191,15 @@ pub(crate) mod command {
         }
     }
 
+    pub(crate) fn run_prover(runnable: &lsp_ext::Runnable, title: &str) -> lsp_types::Command {
+        lsp_types::Command {
+            title: title.to_owned(),
+            command: "move-on-aptos.runProver".into(),
+            arguments: Some(vec![serde_json::to_value(runnable).unwrap()]),
+            tooltip: None,
+        }
+    }
+
     pub(crate) fn debug_transaction(runnable: &lsp_ext::Runnable) -> lsp_types::Command {
         lsp_types::Command {
             title: "Debug Transaction".to_owned(),
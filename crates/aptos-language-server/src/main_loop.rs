@@ -8,17 +8,22 @@ use crate::config::Config;
 use crate::global_state::{GlobalState, LoadPackagesRequest, LoadPackagesResponse};
 use crate::handlers::dispatch::{NotificationDispatcher, RequestDispatcher};
 use crate::handlers::request;
-use crate::handlers::request::empty_diagnostic_report;
+use crate::handlers::request::{empty_diagnostic_report, empty_workspace_diagnostic_report};
 use crate::lsp::utils::{Progress, notification_is};
 use crate::lsp_ext;
+use crate::op_queue::Cause;
+use crate::prover::ProverTask;
 use crate::reload::FetchPackagesProgress;
 use crossbeam_channel::Receiver;
+use ide::ParallelPrimeCachesProgress;
 use lsp_server::Connection;
 use lsp_types::Notification;
 use paths::AbsPathBuf;
 use std::fmt;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use stdx::always;
+use stdx::thread::ThreadIntent;
 use tracing::{Level, span};
 use vfs::VfsPath;
 use vfs::loader::LoadingProgress;
@@ -102,6 +107,15 @@ pub(crate) enum Task {
     Response(lsp_server::Response),
     Retry(lsp_server::Request),
     FetchPackagesProgress(FetchPackagesProgress),
+    PrimeCaches(PrimeCachesProgress),
+    Prover(ProverTask),
+}
+
+#[derive(Debug)]
+pub(crate) enum PrimeCachesProgress {
+    Begin,
+    Report(ParallelPrimeCachesProgress),
+    End { cancelled: bool },
 }
 
 impl fmt::Display for Task {
@@ -112,6 +126,8 @@ impl fmt::Display for Task {
             Task::FetchPackagesProgress(progress) => {
                 write!(f, "Task::FetchPackagesProgress({progress})")
             }
+            Task::PrimeCaches(progress) => write!(f, "Task::PrimeCaches({progress:?})"),
+            Task::Prover(_) => write!(f, "Task::Prover"),
         }
     }
 }
@@ -243,6 +259,11 @@ impl GlobalState {
         if self.is_project_fully_loaded() {
             let became_fully_loaded = !was_fully_loaded;
 
+            if became_fully_loaded {
+                self.prime_caches_queue
+                    .request_op("became fully loaded".to_owned(), ());
+            }
+
             let ask_for_client_refresh = became_fully_loaded || any_file_changed;
             if ask_for_client_refresh {
                 self.ask_for_semantic_tokens_refresh();
@@ -258,9 +279,39 @@ impl GlobalState {
             self.load_aptos_packages_from_fs(cause, force_reload_package_deps);
         }
 
+        if self.is_project_fully_loaded()
+            && let Some((cause, ())) = self.prime_caches_queue.should_start_op()
+        {
+            self.prime_caches(cause);
+        }
+
         self.update_status_or_notify();
     }
 
+    fn prime_caches(&mut self, cause: Cause) {
+        tracing::debug!(%cause, "will prime caches");
+        let num_worker_threads = self.config.prime_caches_num_threads();
+        let analysis = self.analysis_host.analysis();
+        self.task_pool
+            .handle
+            .spawn_with_sender(ThreadIntent::Worker, move |sender| {
+                let _p = tracing::info_span!("worker thread: prime_caches()").entered();
+                sender
+                    .send(Task::PrimeCaches(PrimeCachesProgress::Begin))
+                    .unwrap();
+                let res = analysis.parallel_prime_caches(num_worker_threads, |progress| {
+                    sender
+                        .send(Task::PrimeCaches(PrimeCachesProgress::Report(progress)))
+                        .unwrap();
+                });
+                sender
+                    .send(Task::PrimeCaches(PrimeCachesProgress::End {
+                        cancelled: res.is_err(),
+                    }))
+                    .unwrap();
+            });
+    }
+
     fn update_status_or_notify(&mut self) {
         let status = self.current_status();
         if self.last_reported_status != status {
@@ -328,7 +379,7 @@ impl GlobalState {
                     FetchPackagesProgress::Report(msg) => (Progress::Report, Some(msg)),
                     FetchPackagesProgress::End(packages_from_fs, force_reload_package_deps) => {
                         self.load_aptos_packages_queue.op_completed(LoadPackagesResponse {
-                            packages_from_fs,
+                            packages_from_fs: Arc::new(packages_from_fs),
                             force_reload_package_deps,
                         });
                         if let Some(loading_error) = self.load_packages_error() {
@@ -341,6 +392,20 @@ impl GlobalState {
 
                 self.report_progress("Fetching", state, msg, None, None);
             }
+            Task::PrimeCaches(progress) => match progress {
+                PrimeCachesProgress::Begin => self.prime_caches_progress = None,
+                PrimeCachesProgress::Report(report) => self.prime_caches_progress = Some(report),
+                PrimeCachesProgress::End { cancelled } => {
+                    self.prime_caches_queue.op_completed(());
+                    self.prime_caches_progress = None;
+                    // the database was changed by an edit, start again with the new inputs
+                    if cancelled {
+                        self.prime_caches_queue
+                            .request_op("restart after cancellation".to_owned(), ());
+                    }
+                }
+            },
+            Task::Prover(task) => self.handle_prover_task(task),
         }
     }
 
@@ -460,8 +525,8 @@ impl GlobalState {
             // Request handlers that must run on the main thread
             // because they mutate GlobalState:
             // .on_sync_mut::<lsp_ext::ReloadWorkspace>(handlers::handle_workspace_reload)
-            // .on_sync_mut::<lsp_ext::MemoryUsage>(handlers::handle_memory_usage)
             // .on_sync_mut::<lsp_ext::RunTest>(handlers::handle_run_test)
+            .on_sync_mut_deferred::<lsp_ext::RunProver>(handlers::handle_run_prover)
             // Request handlers which are related to the user typing
             // are run on the main thread to reduce latency:
             .on_sync::<lsp_types::SelectionRangeRequest>(handlers::handle_selection_range)
@@ -492,6 +557,14 @@ impl GlobalState {
                         retrigger_request: true
                     }).ok(),
                 })
+            .on_with_vfs_default::<lsp_types::WorkspaceDiagnosticRequest>(
+                handlers::handle_workspace_diagnostics, empty_workspace_diagnostic_report, || lsp_server::ResponseError {
+                    code: lsp_server::ErrorCode::ServerCancelled as i32,
+                    message: "server cancelled the request".to_owned(),
+                    data: serde_json::to_value(lsp_types::DiagnosticServerCancellationData {
+                        retrigger_request: true
+                    }).ok(),
+                })
             .on::<RETRY, lsp_types::DocumentSymbolRequest>(handlers::handle_document_symbol)
             .on::<NO_RETRY, lsp_types::SignatureHelpRequest>(handlers::handle_signature_help)
             .on::<NO_RETRY, lsp_types::HoverRequest>(handlers::handle_hover)
@@ -502,13 +575,18 @@ impl GlobalState {
             .on_identity::<NO_RETRY, lsp_types::CodeLensResolveRequest, _>(handlers::handle_code_lens_resolve)
             .on::<NO_RETRY, lsp_types::PrepareRenameRequest>(handlers::handle_prepare_rename)
             .on::<NO_RETRY, lsp_types::RenameRequest>(handlers::handle_rename)
+            .on::<NO_RETRY, lsp_types::WillRenameFilesRequest>(handlers::handle_will_rename_files)
             .on::<NO_RETRY, lsp_types::ReferencesRequest>(handlers::handle_references)
+            .on::<NO_RETRY, lsp_types::MonikerRequest>(handlers::handle_moniker)
             .on::<NO_RETRY, lsp_types::DocumentHighlightRequest>(handlers::handle_document_highlight)
+            .on::<NO_RETRY, lsp_types::DocumentLinkRequest>(handlers::handle_document_link)
             .on::<RETRY, lsp_types::WorkspaceSymbolRequest>(handlers::handle_workspace_symbol)
             .on::<NO_RETRY, lsp_types::CodeActionRequest>(request::handle_code_action)
             .on_identity::<RETRY, lsp_types::CodeActionResolveRequest, _>(request::handle_code_action_resolve)
             // All other request handlers (lsp extension)
             .on::<RETRY, lsp_ext::AnalyzerStatus>(handlers::handle_analyzer_status)
+            .on::<NO_RETRY, lsp_ext::MemoryUsage>(handlers::handle_memory_usage)
+            .on::<NO_RETRY, lsp_ext::SymbolId>(handlers::handle_symbol_id)
             .on::<NO_RETRY, lsp_ext::ViewSyntaxTree>(request::handle_view_syntax_tree)
             .on::<NO_RETRY, lsp_ext::OrganizeImports>(handlers::handle_organize_imports)
             .finish();
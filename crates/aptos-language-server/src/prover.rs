@@ -0,0 +1,818 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::global_state::{GlobalState, GlobalStateSnapshot};
+use crate::lsp::{LspError, to_proto};
+use crate::lsp_ext;
+use crate::main_loop::Task;
+use crate::toolchain;
+use anyhow::Context;
+use camino::{Utf8Path, Utf8PathBuf};
+use lsp_server::ErrorCode;
+use paths::AbsPathBuf;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{self, Read};
+use std::process::{Child, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::thread;
+use std::time::Duration;
+use stdx::thread::ThreadIntent;
+use syntax::{TextRange, TextSize};
+use vfs::{FileId, VfsPath};
+
+const PROVER_DIAGNOSTICS_SOURCE: &str = "aptos-prover";
+const PROVER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Running `aptos move prove` process, killed when the request is cancelled by the client
+/// or superseded by the next run.
+pub(crate) struct ProverRun {
+    request_id: lsp_server::RequestId,
+    cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+pub(crate) enum ProverTask {
+    /// The prover process exited, its output is not yet mapped to the file locations.
+    Exited {
+        request_id: lsp_server::RequestId,
+        package_root: Utf8PathBuf,
+        /// Texts of the package files at the prover start, the prover output refers to them.
+        file_texts: HashMap<FileId, Arc<str>>,
+        success: bool,
+        output: String,
+    },
+    /// Prover diagnostics for all files of the package, empty for the files without errors.
+    Finished {
+        request_id: lsp_server::RequestId,
+        file_diagnostics: HashMap<FileId, Vec<lsp_types::Diagnostic>>,
+        result: lsp_ext::RunProverResult,
+    },
+}
+
+impl GlobalState {
+    /// Spawns `aptos move prove` with the runnable arguments onto the task pool. The request is
+    /// responded once the verification errors are merged into the pull diagnostics.
+    pub(crate) fn start_prover_run(
+        &mut self,
+        request_id: lsp_server::RequestId,
+        params: lsp_ext::AptosRunnableArgs,
+    ) -> anyhow::Result<()> {
+        if params.args.get(1).map(|it| it.as_str()) != Some("prove") {
+            return Err(LspError::new(
+                ErrorCode::InvalidParams as i32,
+                format!("not a prover command: {:?}", params.args),
+            )
+            .into());
+        }
+        let Some(aptos_path) = self.snapshot().aptos_cli_path() else {
+            return Err(LspError::new(
+                ErrorCode::RequestFailed as i32,
+                "aptos CLI path is not provided".to_owned(),
+            )
+            .into());
+        };
+
+        let mut command = toolchain::command(&aptos_path, &params.package_root);
+        command.args(&params.args);
+        command.envs(&params.environment);
+        tracing::info!(?command);
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(format!("Failed to spawn {command:?}"))?;
+
+        if let Some(previous_run) = self.prover_run.take() {
+            previous_run.cancelled.store(true, Ordering::Relaxed);
+            self.respond(lsp_server::Response::new_err(
+                previous_run.request_id,
+                ErrorCode::RequestCanceled as i32,
+                "superseded by the next prover run".to_owned(),
+            ));
+        }
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.prover_run = Some(ProverRun {
+            request_id: request_id.clone(),
+            cancelled: Arc::clone(&cancelled),
+        });
+
+        let package_root = params.package_root;
+        let file_texts = package_file_texts(&self.snapshot(), &package_root);
+        self.task_pool
+            .handle
+            .spawn_with_sender(ThreadIntent::Worker, move |sender| {
+                let _p = tracing::info_span!("aptos move prove").entered();
+                let task = match wait_for_prover(child, &cancelled) {
+                    Ok(Some((success, output))) => Task::Prover(ProverTask::Exited {
+                        request_id,
+                        package_root,
+                        file_texts,
+                        success,
+                        output,
+                    }),
+                    // the request is already responded to
+                    Ok(None) => return,
+                    Err(err) => Task::Response(lsp_server::Response::new_err(
+                        request_id,
+                        ErrorCode::RequestFailed as i32,
+                        format!("aptos move prove failed: {err}"),
+                    )),
+                };
+                sender.send(task).unwrap();
+            });
+        Ok(())
+    }
+
+    /// Kills the running prover process, if it belongs to the cancelled request.
+    pub(crate) fn cancel_prover_run(&mut self, request_id: &lsp_server::RequestId) {
+        if let Some(run) = self.prover_run.take_if(|it| it.request_id == *request_id) {
+            run.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn handle_prover_task(&mut self, task: ProverTask) {
+        match task {
+            ProverTask::Exited {
+                request_id,
+                package_root,
+                file_texts,
+                success,
+                output,
+            } => {
+                if !self.is_current_prover_run(&request_id) {
+                    return;
+                }
+                // map the locations with a fresh snapshot, the old one would block the changes
+                // applied during the prover run, locations in the files edited since the start
+                // are dropped
+                let snap = self.snapshot();
+                self.task_pool.handle.spawn(ThreadIntent::Worker, move || {
+                    let prover_diagnostics = parse_prover_output(&output);
+                    let n_errors = prover_diagnostics.len();
+                    let file_diagnostics =
+                        prover_file_diagnostics(&snap, &package_root, &file_texts, prover_diagnostics);
+                    Task::Prover(ProverTask::Finished {
+                        request_id,
+                        file_diagnostics,
+                        result: lsp_ext::RunProverResult { success, n_errors, output },
+                    })
+                });
+            }
+            ProverTask::Finished {
+                request_id,
+                file_diagnostics,
+                result,
+            } => {
+                if !self.is_current_prover_run(&request_id) {
+                    return;
+                }
+                self.prover_run = None;
+
+                let prover_diagnostics = Arc::make_mut(&mut self.prover_diagnostics);
+                let diagnostics_revisions = Arc::make_mut(&mut self.diagnostics_revisions);
+                for (file_id, diagnostics) in file_diagnostics {
+                    if diagnostics.is_empty() {
+                        prover_diagnostics.remove(&file_id);
+                    } else {
+                        prover_diagnostics.insert(file_id, diagnostics);
+                    }
+                    diagnostics_revisions.bump_file(file_id, None);
+                }
+                self.ask_client_for_diagnostics_refresh("prover run finished");
+
+                self.respond(lsp_server::Response::new_ok(request_id, result));
+            }
+        }
+    }
+
+    fn is_current_prover_run(&self, request_id: &lsp_server::RequestId) -> bool {
+        self.prover_run
+            .as_ref()
+            .is_some_and(|it| it.request_id == *request_id)
+    }
+}
+
+/// Waits for the prover process to exit and collects its output, returns `None` if the run
+/// was cancelled.
+fn wait_for_prover(mut child: Child, cancelled: &AtomicBool) -> io::Result<Option<(bool, String)>> {
+    // drain the pipes while waiting, otherwise the prover blocks on a full pipe buffer
+    let stdout = child.stdout.take().map(read_to_string_in_background);
+    let stderr = child.stderr.take().map(read_to_string_in_background);
+
+    let status = loop {
+        if cancelled.load(Ordering::Relaxed) {
+            tracing::info!("killing cancelled prover run");
+            child.kill()?;
+            child.wait()?;
+            // the pipes are closed now, so the readers finish
+            for reader in [stdout, stderr].into_iter().flatten() {
+                let _ = reader.join();
+            }
+            return Ok(None);
+        }
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        thread::sleep(PROVER_POLL_INTERVAL);
+    };
+
+    let join = |reader: Option<thread::JoinHandle<String>>| {
+        reader.and_then(|it| it.join().ok()).unwrap_or_default()
+    };
+    let stdout = join(stdout);
+    let stderr = join(stderr);
+    Ok(Some((status.success(), format!("{stdout}\n{stderr}"))))
+}
+
+fn read_to_string_in_background(mut stream: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        if let Err(err) = stream.read_to_end(&mut bytes) {
+            tracing::warn!(?err, "cannot read prover output");
+        }
+        strip_ansi_escapes::strip_str(String::from_utf8_lossy(&bytes))
+    })
+}
+
+fn package_file_texts(snap: &GlobalStateSnapshot, package_root: &Utf8Path) -> HashMap<FileId, Arc<str>> {
+    let mut file_texts = HashMap::new();
+    for (file_id, path) in snap.vfs_read().iter() {
+        if let Some(path) = path.as_path().map(|it| it.as_utf8_path())
+            && path.starts_with(package_root)
+            && path.extension() == Some("move")
+            && let Ok(text) = snap.analysis.file_text(file_id)
+        {
+            file_texts.insert(file_id, text);
+        }
+    }
+    file_texts
+}
+
+fn prover_file_diagnostics(
+    snap: &GlobalStateSnapshot,
+    package_root: &Utf8Path,
+    file_texts: &HashMap<FileId, Arc<str>>,
+    prover_diagnostics: Vec<ProverDiagnostic>,
+) -> HashMap<FileId, Vec<lsp_types::Diagnostic>> {
+    // clear results of the previous run for all files of the package
+    let mut file_diagnostics: HashMap<FileId, Vec<lsp_types::Diagnostic>> =
+        file_texts.keys().map(|file_id| (*file_id, vec![])).collect();
+    let location = |span: &ProverSpan| prover_location(snap, package_root, file_texts, span);
+
+    for prover_diagnostic in prover_diagnostics {
+        let Some(primary_location) = prover_diagnostic.primary.as_ref().and_then(location) else {
+            tracing::warn!(?prover_diagnostic, "cannot find location of the prover error");
+            continue;
+        };
+        let severity = match prover_diagnostic.severity {
+            ProverSeverity::Error => lsp_types::DiagnosticSeverity::Error,
+            ProverSeverity::Warning => lsp_types::DiagnosticSeverity::Warning,
+        };
+
+        let mut related_information = vec![];
+        let mut label_diagnostics = vec![];
+        for (label_span, label_message) in &prover_diagnostic.labels {
+            let Some(label_location) = location(label_span) else {
+                continue;
+            };
+            related_information.push(lsp_types::DiagnosticRelatedInformation {
+                location: label_location.1.clone(),
+                message: label_message.clone(),
+            });
+            label_diagnostics.push((
+                label_location.0,
+                prover_lsp_diagnostic(
+                    label_location.1.range,
+                    severity,
+                    format!("{label_message} ({})", prover_diagnostic.message),
+                    vec![lsp_types::DiagnosticRelatedInformation {
+                        location: primary_location.1.clone(),
+                        message: prover_diagnostic.message.clone(),
+                    }],
+                ),
+            ));
+        }
+        for trace_step in &prover_diagnostic.trace {
+            let Some(step_location) = location(&trace_step.span) else {
+                continue;
+            };
+            related_information.push(lsp_types::DiagnosticRelatedInformation {
+                location: step_location.1,
+                message: trace_step.to_string(),
+            });
+        }
+
+        let (primary_file_id, primary_location) = primary_location;
+        file_diagnostics
+            .entry(primary_file_id)
+            .or_default()
+            .push(prover_lsp_diagnostic(
+                primary_location.range,
+                severity,
+                prover_diagnostic.message.clone(),
+                related_information,
+            ));
+        for (file_id, label_diagnostic) in label_diagnostics {
+            file_diagnostics
+                .entry(file_id)
+                .or_default()
+                .push(label_diagnostic);
+        }
+    }
+
+    file_diagnostics
+}
+
+fn prover_lsp_diagnostic(
+    range: lsp_types::Range,
+    severity: lsp_types::DiagnosticSeverity,
+    message: String,
+    related_information: Vec<lsp_types::DiagnosticRelatedInformation>,
+) -> lsp_types::Diagnostic {
+    lsp_types::Diagnostic {
+        range,
+        severity: Some(severity),
+        code: None,
+        code_description: None,
+        source: Some(PROVER_DIAGNOSTICS_SOURCE.to_owned()),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        tags: None,
+        data: None,
+    }
+}
+
+/// Location of the span in the current file text, `None` if the file was changed
+/// since the prover start.
+fn prover_location(
+    snap: &GlobalStateSnapshot,
+    package_root: &Utf8Path,
+    file_texts: &HashMap<FileId, Arc<str>>,
+    span: &ProverSpan,
+) -> Option<(FileId, lsp_types::Location)> {
+    let abs_path = AbsPathBuf::try_from(package_root.join(&span.path))
+        .ok()?
+        .normalize();
+    let file_id = snap.vfs_path_to_file_id(&VfsPath::from(abs_path)).ok()?;
+
+    let file_text = snap.analysis.file_text(file_id).ok()?;
+    if file_texts.get(&file_id) != Some(&file_text) {
+        tracing::info!(?file_id, "file is changed since the prover start");
+        return None;
+    }
+    let line_index = snap.file_line_index(file_id).ok()?;
+    let range = span_text_range(&file_text, span)?;
+
+    let location = lsp_types::Location::new(
+        snap.file_id_to_url(file_id),
+        to_proto::lsp_range(&line_index, range),
+    );
+    Some((file_id, location))
+}
+
+/// Converts 1-based prover line / column into the text range, if no column is provided,
+/// the whole line without indentation is used.
+fn span_text_range(text: &str, span: &ProverSpan) -> Option<TextRange> {
+    let start = match span.col {
+        Some(col) => line_col_offset(text, span.line, col)?,
+        None => {
+            let line_start = line_col_offset(text, span.line, 1)?;
+            let line_text = &text[usize::from(line_start)..];
+            let indent = line_text.len() - line_text.trim_start_matches([' ', '\t']).len();
+            line_start + TextSize::of(&line_text[..indent])
+        }
+    };
+    let end = match span.end {
+        Some((end_line, end_col)) => line_col_offset(text, end_line, end_col)?,
+        None => line_col_offset(text, span.line, u32::MAX)?,
+    };
+    Some(TextRange::new(start, end.max(start)))
+}
+
+/// Offset of the 1-based `col` character of the 1-based `line`, clamped to the end of the line.
+fn line_col_offset(text: &str, line: u32, col: u32) -> Option<TextSize> {
+    let mut line_start = 0;
+    for _ in 1..line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    let line_text = text[line_start..].split('\n').next()?.trim_end_matches('\r');
+    let col_offset = line_text
+        .char_indices()
+        .nth(col.saturating_sub(1) as usize)
+        .map(|(offset, _)| offset)
+        .unwrap_or(line_text.len());
+    TextSize::try_from(line_start + col_offset).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProverSeverity {
+    Error,
+    Warning,
+}
+
+/// Location in the prover output, lines and columns are 1-based, `end` column is exclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProverSpan {
+    pub(crate) path: Utf8PathBuf,
+    pub(crate) line: u32,
+    pub(crate) col: Option<u32>,
+    pub(crate) end: Option<(u32, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProverTraceStep {
+    pub(crate) span: ProverSpan,
+    pub(crate) description: String,
+    /// Counterexample values, like `x = 1`.
+    pub(crate) values: Vec<String>,
+}
+
+impl std::fmt::Display for ProverTraceStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at {}", self.description)?;
+        if !self.values.is_empty() {
+            write!(f, ": {}", self.values.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProverDiagnostic {
+    pub(crate) severity: ProverSeverity,
+    pub(crate) message: String,
+    pub(crate) primary: Option<ProverSpan>,
+    /// Secondary labels, like `abort happened here with execution failure`.
+    pub(crate) labels: Vec<(ProverSpan, String)>,
+    pub(crate) trace: Vec<ProverTraceStep>,
+}
+
+/// Parses codespan-formatted verification errors of the Move Prover:
+/// ```text
+/// error: post-condition does not hold
+///    ┌─ ./sources/m.move:8:9
+///    │
+///  8 │         ensures result == a + 1;
+///    │         ^^^^^^^^^^^^^^^^^^^^^^^^
+///    │
+///    =     at ./sources/m.move:2: add
+///    =         a = 0
+///    =     at ./sources/m.move:8: add (spec)
+/// ```
+pub(crate) fn parse_prover_output(output: &str) -> Vec<ProverDiagnostic> {
+    let mut res = vec![];
+
+    let mut lines = output.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Some(header) = HEADER_REGEX.captures(line) else {
+            continue;
+        };
+        let severity = match &header[1] {
+            "warning" => ProverSeverity::Warning,
+            _ => ProverSeverity::Error,
+        };
+        let message = header[2].trim().to_owned();
+
+        let mut block = vec![];
+        while let Some(line) = lines.peek() {
+            if line.trim().is_empty() || HEADER_REGEX.is_match(line) {
+                break;
+            }
+            block.push(*line);
+            lines.next();
+        }
+        res.push(parse_prover_diagnostic(severity, message, &block));
+    }
+
+    res
+}
+
+fn parse_prover_diagnostic(
+    severity: ProverSeverity,
+    message: String,
+    block: &[&str],
+) -> ProverDiagnostic {
+    let mut primary: Option<ProverSpan> = None;
+    let mut labels = vec![];
+    let mut trace: Vec<ProverTraceStep> = vec![];
+
+    // snippets with multiline labels have an additional gutter for the `╭`/`│`/`╰` markers
+    let gutter = if block.iter().any(|it| it.contains('╭')) {
+        2
+    } else {
+        0
+    };
+    let mut last_source_line: Option<u32> = None;
+
+    for line in block {
+        if let Some(location) = LOCATION_REGEX.captures(line) {
+            if primary.is_none() {
+                primary = Some(ProverSpan {
+                    path: Utf8PathBuf::from(location[1].trim()),
+                    line: location[2].parse().unwrap_or(1),
+                    col: location[3].parse().ok(),
+                    end: None,
+                });
+            }
+            continue;
+        }
+        if let Some(source_line) = SOURCE_LINE_REGEX.captures(line) {
+            last_source_line = source_line[1].parse().ok();
+            continue;
+        }
+        if let Some(marker_line) = MARKER_LINE_REGEX.captures(line) {
+            let (Some(primary), Some(source_line)) = (primary.as_mut(), last_source_line) else {
+                continue;
+            };
+            let markers = marker_line[1].chars().collect::<Vec<_>>();
+            if let Some((start, len)) = marker_run(&markers, '^') {
+                let end_col = (start + len).saturating_sub(gutter) as u32 + 1;
+                if markers.contains(&'╰') || primary.line != source_line {
+                    primary.end = Some((source_line, end_col));
+                } else {
+                    primary.col = Some(start.saturating_sub(gutter) as u32 + 1);
+                    primary.end = Some((source_line, end_col));
+                }
+                continue;
+            }
+            if let Some((start, len)) = marker_run(&markers, '-') {
+                let label = markers[start + len..]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_owned();
+                if !label.is_empty() {
+                    let col = start.saturating_sub(gutter) as u32 + 1;
+                    let span = ProverSpan {
+                        path: primary.path.clone(),
+                        line: source_line,
+                        col: Some(col),
+                        end: Some((source_line, col + len as u32)),
+                    };
+                    labels.push((span, label));
+                }
+            }
+            continue;
+        }
+        if let Some(note_line) = NOTE_LINE_REGEX.captures(line) {
+            let note = note_line[1].trim();
+            if let Some(step) = TRACE_STEP_REGEX.captures(note) {
+                let col = step.get(3).and_then(|it| it.as_str().parse().ok());
+                let line = step[2].parse().unwrap_or(1);
+                let end = col
+                    .zip(step.get(4).and_then(|it| it.as_str().parse::<u32>().ok()))
+                    .map(|(col, len)| (line, col + len));
+                trace.push(ProverTraceStep {
+                    span: ProverSpan {
+                        path: Utf8PathBuf::from(&step[1]),
+                        line,
+                        col,
+                        end,
+                    },
+                    description: step[5].trim().to_owned(),
+                    values: vec![],
+                });
+            } else if let Some(last_step) = trace.last_mut() {
+                last_step.values.push(note.to_owned());
+            }
+        }
+    }
+
+    ProverDiagnostic {
+        severity,
+        message,
+        primary,
+        labels,
+        trace,
+    }
+}
+
+/// Start and length of the first run of `marker` characters.
+fn marker_run(markers: &[char], marker: char) -> Option<(usize, usize)> {
+    let start = markers.iter().position(|it| *it == marker)?;
+    let len = markers[start..].iter().take_while(|it| **it == marker).count();
+    Some((start, len))
+}
+
+static HEADER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(error|warning|bug)(?:\[\w+\])?: (.+)$").unwrap());
+static LOCATION_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:┌─|-->)\s*(.+):(\d+):(\d+)\s*$").unwrap());
+static SOURCE_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*(\d+)\s+[│|]").unwrap());
+static MARKER_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*[│|] ?(.*)$").unwrap());
+static NOTE_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*= (.*)$").unwrap());
+static TRACE_STEP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^at (.+?):(\d+)(?::(\d+))?(?:\+(\d+))?: (.+)$").unwrap());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::expect;
+
+    #[test]
+    fn test_parse_failed_post_condition() {
+        let output = r#"
+[INFO] preparing module 0x1::m
+[INFO] running solver
+error: post-condition does not hold
+   ┌─ ./sources/m.move:8:9
+   │
+ 8 │         ensures result == a + 1;
+   │         ^^^^^^^^^^^^^^^^^^^^^^^^
+   │
+   =     at ./sources/m.move:2: add
+   =         a = 0
+   =         b = 7
+   =     at ./sources/m.move:3: add
+   =         result = 7
+   =     at ./sources/m.move:8: add (spec)
+
+Error: Move Prover failed: exiting with verification errors
+"#;
+        let diagnostics = parse_prover_output(output);
+        expect![[r#"
+            [
+                ProverDiagnostic {
+                    severity: Error,
+                    message: "post-condition does not hold",
+                    primary: Some(
+                        ProverSpan {
+                            path: "./sources/m.move",
+                            line: 8,
+                            col: Some(
+                                9,
+                            ),
+                            end: Some(
+                                (
+                                    8,
+                                    33,
+                                ),
+                            ),
+                        },
+                    ),
+                    labels: [],
+                    trace: [
+                        ProverTraceStep {
+                            span: ProverSpan {
+                                path: "./sources/m.move",
+                                line: 2,
+                                col: None,
+                                end: None,
+                            },
+                            description: "add",
+                            values: [
+                                "a = 0",
+                                "b = 7",
+                            ],
+                        },
+                        ProverTraceStep {
+                            span: ProverSpan {
+                                path: "./sources/m.move",
+                                line: 3,
+                                col: None,
+                                end: None,
+                            },
+                            description: "add",
+                            values: [
+                                "result = 7",
+                            ],
+                        },
+                        ProverTraceStep {
+                            span: ProverSpan {
+                                path: "./sources/m.move",
+                                line: 8,
+                                col: None,
+                                end: None,
+                            },
+                            description: "add (spec)",
+                            values: [],
+                        },
+                    ],
+                },
+            ]
+        "#]]
+        .assert_debug_eq(&diagnostics);
+        expect!["at add: a = 0, b = 7"].assert_eq(&diagnostics[0].trace[0].to_string());
+    }
+
+    #[test]
+    fn test_parse_abort_not_covered_with_multiline_spec() {
+        let output = r#"
+error: abort not covered by any of the `aborts_if` clauses
+   ┌─ /home/user/pkg/sources/m.move:10:5
+   │
+ 6 │           a + b
+   │           ----- abort happened here with execution failure
+   ·
+10 │ ╭     spec add {
+11 │ │         aborts_if false;
+12 │ │     }
+   │ ╰─────^
+   │
+   =     at /home/user/pkg/sources/m.move:5: add
+   =         a = 18446744073709551615
+   =     at /home/user/pkg/sources/m.move:6:9+5: add
+   =         ABORTED
+"#;
+        let diagnostics = parse_prover_output(output);
+        expect![[r#"
+            [
+                ProverDiagnostic {
+                    severity: Error,
+                    message: "abort not covered by any of the `aborts_if` clauses",
+                    primary: Some(
+                        ProverSpan {
+                            path: "/home/user/pkg/sources/m.move",
+                            line: 10,
+                            col: Some(
+                                5,
+                            ),
+                            end: Some(
+                                (
+                                    12,
+                                    6,
+                                ),
+                            ),
+                        },
+                    ),
+                    labels: [
+                        (
+                            ProverSpan {
+                                path: "/home/user/pkg/sources/m.move",
+                                line: 6,
+                                col: Some(
+                                    9,
+                                ),
+                                end: Some(
+                                    (
+                                        6,
+                                        14,
+                                    ),
+                                ),
+                            },
+                            "abort happened here with execution failure",
+                        ),
+                    ],
+                    trace: [
+                        ProverTraceStep {
+                            span: ProverSpan {
+                                path: "/home/user/pkg/sources/m.move",
+                                line: 5,
+                                col: None,
+                                end: None,
+                            },
+                            description: "add",
+                            values: [
+                                "a = 18446744073709551615",
+                            ],
+                        },
+                        ProverTraceStep {
+                            span: ProverSpan {
+                                path: "/home/user/pkg/sources/m.move",
+                                line: 6,
+                                col: Some(
+                                    9,
+                                ),
+                                end: Some(
+                                    (
+                                        6,
+                                        14,
+                                    ),
+                                ),
+                            },
+                            description: "add",
+                            values: [
+                                "ABORTED",
+                            ],
+                        },
+                    ],
+                },
+            ]
+        "#]]
+        .assert_debug_eq(&diagnostics);
+    }
+
+    #[test]
+    fn test_span_text_range() {
+        let text = "module 0x1::m {\n    fun main() {\n        1 + 1;\n    }\n}\n";
+        let span = |line, col, end| ProverSpan {
+            path: Utf8PathBuf::from("m.move"),
+            line,
+            col,
+            end,
+        };
+        let range_text = |span: ProverSpan| {
+            let range = span_text_range(text, &span).unwrap();
+            text[range].to_owned()
+        };
+        assert_eq!(range_text(span(3, Some(9), Some((3, 14)))), "1 + 1");
+        assert_eq!(range_text(span(3, None, None)), "1 + 1;");
+        assert_eq!(
+            range_text(span(2, Some(5), Some((4, 6)))),
+            "fun main() {\n        1 + 1;\n    }"
+        );
+    }
+}
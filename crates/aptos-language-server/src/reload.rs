@@ -5,7 +5,7 @@
 // Modifications have been made to the original code.
 
 use crate::config::FilesWatcher;
-use crate::global_state::{GlobalState, LoadPackagesRequest, LoadPackagesResponse};
+use crate::global_state::{GlobalState, LoadPackagesRequest, LoadPackagesResponse, library_cache};
 use crate::lsp::utils::Progress;
 use crate::main_loop::Task;
 use crate::op_queue::Cause;
@@ -74,9 +74,22 @@ impl GlobalState {
         }
 
         if self.config.diagnostics_config() != old_config.diagnostics_config() {
+            Arc::make_mut(&mut self.diagnostics_revisions).bump_all();
             self.ask_client_for_diagnostics_refresh("update configuration");
         }
 
+        if self.config.library_cache_dir() != old_config.library_cache_dir() {
+            self.analysis_host
+                .raw_database_mut()
+                .set_library_cache(library_cache(&self.config));
+        }
+
+        if self.config.lru_capacities() != old_config.lru_capacities() {
+            let (parse_lru_cap, inference_lru_cap) = self.config.lru_capacities();
+            self.analysis_host
+                .update_lru_capacities(parse_lru_cap, inference_lru_cap);
+        }
+
         if self.config.inlay_hints_config() != old_config.inlay_hints_config() {
             self.ask_for_inlay_hints_refresh("update configuration");
         }
@@ -85,11 +98,20 @@ impl GlobalState {
     pub(crate) fn current_status(&self) -> lsp_ext::ServerStatusParams {
         let mut status = lsp_ext::ServerStatusParams {
             health: lsp_ext::Health::Ok,
-            quiescent: self.is_project_fully_loaded(),
+            quiescent: self.is_project_fully_loaded() && !self.prime_caches_queue.op_in_progress(),
             message: None,
         };
         let mut message = String::new();
 
+        if let Some(progress) = &self.prime_caches_progress {
+            stdx::format_to!(
+                message,
+                "Indexing {}/{} packages\n\n",
+                progress.packages_done,
+                progress.packages_total
+            );
+        }
+
         if let Some(err) = &self.config_errors {
             status.health |= lsp_ext::Health::Warning;
             stdx::format_to!(message, "{err}\n");
@@ -352,6 +374,10 @@ impl GlobalState {
         let mut changes = FileChanges::new();
         changes.set_package_graph(package_graph);
         self.analysis_host.apply_change(changes);
+        Arc::make_mut(&mut self.diagnostics_revisions).bump_all();
+        self.prime_caches_queue
+            .request_op("package graph changed".to_owned(), ());
+        self.last_reload_cause = Some(cause);
     }
 
     pub(crate) fn is_package_loading_error(&self) -> bool {
@@ -366,7 +392,7 @@ impl GlobalState {
         false
     }
 
-    pub(super) fn load_packages_error(&self) -> Option<String> {
+    pub(crate) fn load_packages_error(&self) -> Option<String> {
         let LoadPackagesResponse { packages_from_fs, .. } =
             self.load_aptos_packages_queue.last_op_result()?;
         packages_from_fs.display_error_for_tracing()
@@ -6,8 +6,10 @@
 
 use lsp_types::{
     DidOpenTextDocumentNotification, DidOpenTextDocumentParams, DocumentDiagnosticParams,
-    DocumentDiagnosticRequest, LanguageKind, TextDocumentItem,
+    DocumentDiagnosticRequest, LanguageKind, PreviousResultId, TextDocumentItem,
+    WorkspaceDiagnosticParams, WorkspaceDiagnosticRequest,
 };
+use serde_json::json;
 use std::fs;
 use test_utils::fixtures::test_state::{named, named_with_deps};
 
@@ -70,17 +72,17 @@ module std::table {
 
     let server = server.wait_until_workspace_is_loaded();
 
-    let resp = server.send_request::<DocumentDiagnosticRequest>(DocumentDiagnosticParams {
-        text_document: main_document,
+    let mut resp = server.send_request::<DocumentDiagnosticRequest>(DocumentDiagnosticParams {
+        text_document: main_document.clone(),
         identifier: None,
         previous_result_id: None,
         work_done_progress_params: Default::default(),
         partial_result_params: Default::default(),
     });
+    let result_id = resp.as_object_mut().unwrap().remove("resultId").unwrap();
     let s = serde_json::to_string_pretty(&resp).unwrap();
     let expected_resp = expect_test::expect![[r#"
         {
-          "resultId": "aptos-language-server",
           "items": [
             {
               "range": {
@@ -98,6 +100,25 @@ module std::table {
               "source": "aptos-language-server",
               "message": "Unused use item"
             },
+            {
+              "range": {
+                "start": {
+                  "line": 3,
+                  "character": 8
+                },
+                "end": {
+                  "line": 3,
+                  "character": 12
+                }
+              },
+              "severity": 2,
+              "code": "unused-item",
+              "source": "aptos-language-server",
+              "message": "Function `main` is never used",
+              "tags": [
+                1
+              ]
+            },
             {
               "range": {
                 "start": {
@@ -118,4 +139,139 @@ module std::table {
           "kind": "full"
         }"#]];
     expected_resp.assert_eq(&s);
+
+    let resp = server.send_request::<DocumentDiagnosticRequest>(DocumentDiagnosticParams {
+        text_document: main_document,
+        identifier: None,
+        previous_result_id: Some(result_id.as_str().unwrap().to_owned()),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+    assert_eq!(resp, json!({ "resultId": result_id, "kind": "unchanged" }));
+}
+
+#[test]
+fn test_workspace_diagnostics_for_not_opened_file() {
+    let server = support::project(vec![named(
+        "MyPackage",
+        // language=Move
+        r#"
+//- /main.move
+module std::m {
+    entry fun main() {
+        1 + true;
+    }
+}
+    "#,
+    )])
+    .wait_until_workspace_is_loaded();
+    let main_uri = server.doc_id("MyPackage/sources/main.move").uri;
+
+    let resp = server.send_request::<WorkspaceDiagnosticRequest>(WorkspaceDiagnosticParams {
+        identifier: None,
+        previous_result_ids: vec![],
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+    let main_report = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|it| it["uri"] == json!(main_uri))
+        .cloned()
+        .expect("no report for main.move");
+    assert_eq!(main_report["kind"], "full");
+    assert_eq!(main_report["items"][0]["code"], "type-error");
+
+    let result_id = main_report["resultId"].as_str().unwrap().to_owned();
+    let resp = server.send_request::<WorkspaceDiagnosticRequest>(WorkspaceDiagnosticParams {
+        identifier: None,
+        previous_result_ids: vec![PreviousResultId {
+            uri: main_uri.clone(),
+            value: result_id,
+        }],
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+    let main_report = resp["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|it| it["uri"] == json!(main_uri))
+        .cloned()
+        .expect("no report for main.move");
+    assert_eq!(main_report["kind"], "unchanged");
+}
+
+#[test]
+fn test_workspace_diagnostics_unchanged_for_files_not_depending_on_changed_file() {
+    let server = support::project(vec![
+        named(
+            "MyPackage",
+            // language=Move
+            r#"
+//- /main.move
+module std::m {
+    entry fun main() {
+        1 + true;
+    }
+}
+    "#,
+        ),
+        named(
+            "Other",
+            // language=Move
+            r#"
+//- /other.move
+module 0x1::other {}
+    "#,
+        ),
+    ])
+    .wait_until_workspace_is_loaded();
+    let main_uri = server.doc_id("MyPackage/sources/main.move").uri;
+    let other_uri = server.doc_id("Other/sources/other.move").uri;
+    let workspace_reports = |previous_result_ids: Vec<PreviousResultId>| {
+        let resp = server.send_request::<WorkspaceDiagnosticRequest>(WorkspaceDiagnosticParams {
+            identifier: None,
+            previous_result_ids,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        let report = |uri: &lsp_types::Uri| {
+            resp["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|it| it["uri"] == json!(uri))
+                .cloned()
+                .expect("no report for the file")
+        };
+        (report(&main_uri), report(&other_uri))
+    };
+
+    let (main_report, other_report) = workspace_reports(vec![]);
+    let previous_result_ids = vec![
+        PreviousResultId {
+            uri: main_uri.clone(),
+            value: main_report["resultId"].as_str().unwrap().to_owned(),
+        },
+        PreviousResultId {
+            uri: other_uri.clone(),
+            value: other_report["resultId"].as_str().unwrap().to_owned(),
+        },
+    ];
+
+    server.notification::<DidOpenTextDocumentNotification>(DidOpenTextDocumentParams {
+        text_document: TextDocumentItem::new(
+            other_uri.clone(),
+            LanguageKind::new("move"),
+            0,
+            "module 0x1::other { fun f() { 1 + true; } }".to_owned(),
+        ),
+    });
+
+    let (main_report, other_report) = workspace_reports(previous_result_ids);
+    assert_eq!(main_report["kind"], "unchanged");
+    assert_eq!(other_report["kind"], "full");
+    assert_eq!(other_report["items"][0]["code"], "type-error");
 }
@@ -8,6 +8,7 @@
 
 pub mod change;
 pub mod inputs;
+pub mod memory_counters;
 pub mod package_root;
 pub mod source_db;
 
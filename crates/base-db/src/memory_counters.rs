@@ -0,0 +1,109 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Estimated sizes of the memoized query results, which allow to report the memory usage
+//! without computing anything.
+//!
+//! Every memoized value holds the [`TrackedSize`], which is counted until salsa drops the value,
+//! either evicting it with the LRU or replacing it with the recomputed one.
+
+use crate::package_root::PackageId;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use vfs::FileId;
+
+/// Counters of the database, shared between its snapshots.
+#[derive(Debug, Default)]
+pub struct MemoryCounters {
+    pub syntax_trees: Arc<MemoryCounter<FileId>>,
+    pub inference_results: Arc<MemoryCounter<FileId>>,
+    pub symbol_indices: Arc<MemoryCounter<PackageId>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValuesSize {
+    pub count: usize,
+    pub bytes: usize,
+}
+
+/// Number and size of the alive values, by the file or the package they are computed for.
+#[derive(Debug)]
+pub struct MemoryCounter<K> {
+    sizes: Mutex<HashMap<K, ValuesSize>>,
+}
+
+impl<K> Default for MemoryCounter<K> {
+    fn default() -> Self {
+        MemoryCounter {
+            sizes: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Copy + Eq + Hash> MemoryCounter<K> {
+    /// Counts the `bytes` until the returned [`TrackedSize`] is dropped.
+    pub fn track(self: &Arc<Self>, key: K, bytes: usize) -> TrackedSize<K> {
+        let mut sizes = self.sizes.lock().unwrap();
+        let size = sizes.entry(key).or_default();
+        size.count += 1;
+        size.bytes += bytes;
+        TrackedSize {
+            counter: self.clone(),
+            key,
+            bytes,
+        }
+    }
+
+    pub fn sizes(&self) -> HashMap<K, ValuesSize> {
+        self.sizes.lock().unwrap().clone()
+    }
+
+    fn untrack(&self, key: K, bytes: usize) {
+        let mut sizes = self.sizes.lock().unwrap();
+        if let Some(size) = sizes.get_mut(&key) {
+            size.count -= 1;
+            size.bytes -= bytes;
+            if size.count == 0 {
+                sizes.remove(&key);
+            }
+        }
+    }
+}
+
+/// Estimated size of the value, stored alongside it.
+///
+/// Always equal to any other [`TrackedSize`], so that it doesn't prevent salsa from backdating
+/// the recomputed value which is equal to the old one.
+pub struct TrackedSize<K: Copy + Eq + Hash> {
+    counter: Arc<MemoryCounter<K>>,
+    key: K,
+    bytes: usize,
+}
+
+impl<K: Copy + Eq + Hash> TrackedSize<K> {
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+impl<K: Copy + Eq + Hash> Drop for TrackedSize<K> {
+    fn drop(&mut self) {
+        self.counter.untrack(self.key, self.bytes);
+    }
+}
+
+impl<K: Copy + Eq + Hash> PartialEq for TrackedSize<K> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<K: Copy + Eq + Hash> Eq for TrackedSize<K> {}
+
+impl<K: Copy + Eq + Hash> fmt::Debug for TrackedSize<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedSize").field("bytes", &self.bytes).finish()
+    }
+}
@@ -9,13 +9,14 @@ use crate::inputs::{
     FileIdInput, FileIdSet, FileText, PackageIdSet, PackageMetadata, PackageMetadataInput,
     PackageRootInput,
 };
+use crate::memory_counters::{MemoryCounters, TrackedSize};
 use crate::package_root::{PackageId, PackageRoot};
 use salsa::Durability;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::panic;
 use std::sync::Once;
-use syntax::{Parse, SyntaxError, ast};
+use syntax::{NodeOrToken, Parse, SyntaxError, ast};
 use vfs::FileId;
 
 #[salsa_macros::db]
@@ -51,14 +52,57 @@ pub trait SourceDatabase: salsa::Database {
     fn set_spec_related_files(&mut self, file_id: FileId, file_set: Vec<FileId>);
 
     fn all_package_ids(&self) -> PackageIdSet;
+
+    /// Sizes of the memoized query results, see [`crate::memory_counters`].
+    fn memory_counters(&self) -> &MemoryCounters;
 }
 
 /// Parses the file into the syntax tree.
-#[salsa_macros::tracked(returns(ref))]
-pub fn parse(db: &dyn SourceDatabase, file_id: FileIdInput) -> Parse {
+///
+/// Only the most recently used syntax trees are kept after the revision change,
+/// see [`set_parse_lru_capacity`].
+pub fn parse(db: &dyn SourceDatabase, file_id: FileIdInput) -> &Parse {
+    &parse_tracked(db, file_id).parse
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct MemoizedParse {
+    parse: Parse,
+    size: TrackedSize<FileId>,
+}
+
+#[salsa_macros::tracked(returns(ref), lru = 128)]
+fn parse_tracked(db: &dyn SourceDatabase, file_id: FileIdInput) -> MemoizedParse {
     let _p = tracing::info_span!("source_db::parse", ?file_id).entered();
-    let text = db.file_text(file_id.data(db)).text(db);
-    ast::SourceFile::parse(&text)
+    let file_id = file_id.data(db);
+    let text = db.file_text(file_id).text(db);
+    let parse = ast::SourceFile::parse(&text);
+    let size = db
+        .memory_counters()
+        .syntax_trees
+        .track(file_id, syntax_tree_size(&parse));
+    MemoizedParse { parse, size }
+}
+
+pub const DEFAULT_PARSE_LRU_CAP: usize = 128;
+
+pub fn set_parse_lru_capacity(db: &mut dyn SourceDatabase, capacity: usize) {
+    parse_tracked::set_lru_capacity(db, capacity);
+}
+
+/// Rough estimate of the green tree: node and token headers, children pointers and token texts.
+fn syntax_tree_size(parse: &Parse) -> usize {
+    const NODE_SIZE: usize = 40;
+    const TOKEN_SIZE: usize = 32;
+
+    let mut size = 0;
+    for element in parse.syntax_node().descendants_with_tokens() {
+        size += match element {
+            NodeOrToken::Node(_) => NODE_SIZE,
+            NodeOrToken::Token(token) => TOKEN_SIZE + token.text().len(),
+        };
+    }
+    size
 }
 
 #[salsa_macros::tracked(returns(ref))]
@@ -0,0 +1,56 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+use ide_db::RootDatabase;
+use ide_db::assist_config::AssistConfig;
+use lang::Semantics;
+use syntax::files::FileRange;
+use syntax::{AstNode, SourceFile, TextSize, algo};
+use vfs::FileId;
+
+/// `AssistContext` allows to apply an assist or check if it could be applied.
+pub(crate) struct AssistContext<'a> {
+    pub(crate) config: &'a AssistConfig,
+    pub(crate) sema: Semantics<'a, RootDatabase>,
+    frange: FileRange,
+    source_file: SourceFile,
+}
+
+impl<'a> AssistContext<'a> {
+    pub(crate) fn new(
+        sema: Semantics<'a, RootDatabase>,
+        config: &'a AssistConfig,
+        frange: FileRange,
+    ) -> AssistContext<'a> {
+        let source_file = sema.parse(frange.file_id);
+        AssistContext {
+            config,
+            sema,
+            frange,
+            source_file,
+        }
+    }
+
+    pub(crate) fn db(&self) -> &RootDatabase {
+        self.sema.db
+    }
+
+    pub(crate) fn file_id(&self) -> FileId {
+        self.frange.file_id
+    }
+
+    pub(crate) fn offset(&self) -> TextSize {
+        self.frange.range.start()
+    }
+
+    pub(crate) fn source_file(&self) -> &SourceFile {
+        &self.source_file
+    }
+
+    pub(crate) fn find_node_at_offset<N: AstNode>(&self) -> Option<N> {
+        algo::find_node_at_offset(self.source_file.syntax(), self.offset())
+    }
+}
@@ -0,0 +1,23 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+use crate::assist_context::AssistContext;
+use ide_db::assist_context::LocalAssists;
+
+mod generate_spec;
+mod generate_unit_test;
+mod move_item_to_module;
+
+pub(crate) type Handler = fn(&mut LocalAssists, &AssistContext) -> Option<()>;
+
+pub(crate) fn all() -> &'static [Handler] {
+    &[
+        // keep sorted
+        generate_spec::generate_spec,
+        generate_unit_test::generate_unit_test,
+        move_item_to_module::move_item_to_module,
+    ]
+}
@@ -0,0 +1,467 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::assist_context::AssistContext;
+use ide_db::assist_context::LocalAssists;
+use ide_db::assists::AssistId;
+use ide_db::imports;
+use lang::item_specs;
+use lang::loc::SyntaxLocFileExt;
+use lang::node_ext::ModuleLangExt;
+use lang::types::ty::Ty;
+use syntax::ast::edit::{AstNodeEdit, IndentLevel};
+use syntax::ast::node_ext::assert_macro_expr::AssertKind;
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::node_ext::syntax_node::SyntaxNodeExt;
+use syntax::ast::node_ext::use_speck::UseSpeckKind;
+use syntax::ast::operators::{BinaryOp, CmpOp, Ordering};
+use syntax::ast::syntax_factory::SyntaxFactory;
+use syntax::ast::{HasAttrs, HasStmts, UseStmtsOwner};
+use syntax::files::InFileExt;
+use syntax::syntax_editor::{Element, Position};
+use syntax::{AstNode, SyntaxNode, ast};
+
+// Assist: generate_spec
+//
+// Generates a `spec` block for the function, with `aborts_if` conditions for the `assert!` and `abort` sites,
+// `modifies` for the global storage operations, and the `ensures result == result` placeholders
+// for the return values.
+// Conditions referring to the local variables of the function body are skipped.
+// If the function already has a spec, in the same or in the `.spec.move` file, missing conditions
+// are added to it instead.
+//
+// ```
+// module 0x1::m {
+//     struct S has key { val: u8 }
+//     public fun $0set_val(addr: address, val: u8): u8 acquires S {
+//         assert!(val > 0, 1);
+//         borrow_global_mut<S>(addr).val = val;
+//         val
+//     }
+// }
+// ```
+// ->
+// ```
+//     ...
+//     spec set_val {
+//         aborts_if val <= 0 with 1;
+//         modifies global<S>(addr);
+//         ensures result == result;
+//     }
+// ```
+pub(crate) fn generate_spec(acc: &mut LocalAssists, ctx: &AssistContext) -> Option<()> {
+    let fun = ctx.find_node_at_offset::<ast::Fun>()?;
+    let body = fun.body()?;
+    // only available on the signature
+    if body.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let module = fun.syntax().containing_module()?;
+    if fun.is_test() || module.is_test_only() {
+        return None;
+    }
+    let fun_name = fun.name()?.as_string();
+
+    let mut spec_stmts = vec![];
+    spec_stmts.extend(aborts_if_stmts(ctx, &body));
+    spec_stmts.extend(modifies_stmts(ctx, &body));
+    spec_stmts.extend(ensures_stmts(ctx, &fun));
+    dedup(&mut spec_stmts);
+
+    let file_id = ctx.file_id();
+    let item_spec_locs =
+        item_specs::get_item_specs_for_fun(ctx.db(), fun.clone().in_file(file_id).loc());
+    let existing_spec = match item_spec_locs.first() {
+        Some(item_spec_loc) => Some(item_spec_loc.to_ast::<ast::ItemSpec>(ctx.db())?),
+        None => None,
+    };
+    let module_owner = ast::AnyHasItems::cast(module.syntax().clone())?;
+
+    match existing_spec {
+        Some(item_spec) => {
+            // the spec might be in the separate `.spec.move` file
+            let (spec_file_id, item_spec) = item_spec.unpack();
+            let spec_block = item_spec.spec_block()?;
+            let existing_stmts = spec_block
+                .stmts()
+                .map(|it| normalize_whitespace(&it.syntax().text().to_string()))
+                .collect::<Vec<_>>();
+            spec_stmts.retain(|it| !existing_stmts.contains(&normalize_whitespace(it)));
+            if spec_stmts.is_empty() {
+                return None;
+            }
+            let spec_owner = item_spec.syntax().containing_items_owner()?;
+            let signer_import_owner =
+                missing_signer_import(&[module_owner, spec_owner.clone()], spec_owner, &spec_stmts);
+            acc.add_source_change(
+                AssistId::generate("generate-spec"),
+                format!("Add missing conditions to the spec of `{fun_name}`"),
+                fun.name()?.syntax().text_range(),
+                |builder| {
+                    let mut editor = builder.make_editor(item_spec.syntax());
+                    let make = SyntaxFactory::new();
+                    let indent = IndentLevel::from_node(item_spec.syntax()) + 1;
+                    let mut elements = vec![];
+                    for spec_stmt in spec_stmts {
+                        elements.push(make.whitespace(&format!("\n{indent}")).syntax_element());
+                        elements.push(make.spec_stmt_from_text(&spec_stmt).syntax_element());
+                    }
+                    match spec_block.stmts().last() {
+                        Some(last_stmt) => {
+                            editor.insert_all(Position::after(last_stmt.syntax()), elements);
+                        }
+                        None => {
+                            let outer_indent = IndentLevel::from_node(item_spec.syntax());
+                            elements
+                                .push(make.whitespace(&format!("\n{outer_indent}")).syntax_element());
+                            editor.insert_all(Position::after(spec_block.l_curly_token()?), elements);
+                        }
+                    }
+                    editor.add_mappings(make.finish_with_mappings());
+                    if let Some(items_owner) = signer_import_owner {
+                        imports::add_import_for_import_path(
+                            &items_owner,
+                            "std::signer".to_string(),
+                            None,
+                        )(&mut editor)?;
+                    }
+                    builder.add_file_edits(spec_file_id, editor);
+                    Some(())
+                },
+            );
+        }
+        None => {
+            let signer_import_owner =
+                missing_signer_import(&[module_owner.clone()], module_owner, &spec_stmts);
+            acc.add(
+                AssistId::generate("generate-spec"),
+                format!("Generate spec for `{fun_name}`"),
+                fun.name()?.syntax().text_range(),
+                |editor| {
+                    let make = SyntaxFactory::new();
+                    let spec_text = if spec_stmts.is_empty() {
+                        format!("spec {fun_name} {{}}")
+                    } else {
+                        let stmts = spec_stmts
+                            .iter()
+                            .map(|it| format!("    {it}"))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        format!("spec {fun_name} {{\n{stmts}\n}}")
+                    };
+                    let indent = IndentLevel::from_node(fun.syntax());
+                    let item_spec = make.item_spec_from_text(&spec_text).indent_inner(indent);
+                    editor.insert_all(
+                        Position::after(fun.syntax()),
+                        vec![
+                            make.whitespace(&format!("\n\n{indent}")).syntax_element(),
+                            item_spec.syntax().syntax_element(),
+                        ],
+                    );
+                    editor.add_mappings(make.finish_with_mappings());
+                    if let Some(items_owner) = signer_import_owner {
+                        imports::add_import_for_import_path(
+                            &items_owner,
+                            "std::signer".to_string(),
+                            None,
+                        )(editor)?;
+                    }
+                    Some(())
+                },
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn aborts_if_stmts(ctx: &AssistContext, body: &ast::BlockExpr) -> Vec<String> {
+    let mut stmts = vec![];
+    for node in body.syntax().descendants() {
+        let (abort_conditions, error_expr) =
+            if let Some(assert_expr) = ast::AssertMacroExpr::cast(node.clone()) {
+                if !matches!(assert_expr.assert_kind(), AssertKind::Plain) {
+                    continue;
+                }
+                let Some(Some(assert_condition)) = assert_expr.arg_exprs().first().cloned() else {
+                    continue;
+                };
+                if refers_to_locals(ctx, &assert_condition) {
+                    continue;
+                }
+                let Some(mut conditions) = path_conditions(ctx, assert_expr.syntax(), body) else {
+                    continue;
+                };
+                conditions.push(negate(&assert_condition));
+                (conditions, assert_expr.error_expr())
+            } else if let Some(abort_expr) = ast::AbortExpr::cast(node) {
+                let Some(conditions) = path_conditions(ctx, abort_expr.syntax(), body) else {
+                    continue;
+                };
+                (conditions, abort_expr.error_expr())
+            } else {
+                continue;
+            };
+        if error_expr.as_ref().is_some_and(|it| refers_to_locals(ctx, it)) {
+            continue;
+        }
+        let abort_condition = match abort_conditions.len() {
+            0 => "true".to_string(),
+            1 => abort_conditions[0].clone(),
+            _ => abort_conditions
+                .iter()
+                .map(|it| {
+                    if is_logic_expr_text(it) {
+                        format!("({it})")
+                    } else {
+                        it.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" && "),
+        };
+        match error_expr {
+            Some(error_expr) => stmts.push(format!("aborts_if {abort_condition} with {error_expr};")),
+            None => stmts.push(format!("aborts_if {abort_condition};")),
+        }
+    }
+    stmts
+}
+
+/// Conditions under which the `site` is reached from the start of the function body,
+/// or `None` if it cannot be expressed without the control flow analysis (loops, matches, lambdas,
+/// early returns and reassignments) or refers to the local variables.
+fn path_conditions(
+    ctx: &AssistContext,
+    site: &SyntaxNode,
+    body: &ast::BlockExpr,
+) -> Option<Vec<String>> {
+    // early returns and reassigned parameters change the conditions of the later sites
+    let site_start = site.text_range().start();
+    for node in body.syntax().descendants() {
+        if node.text_range().end() > site_start {
+            continue;
+        }
+        if ast::ReturnExpr::can_cast(node.kind()) {
+            return None;
+        }
+        if let Some(path_expr) = ast::PathExpr::cast(node)
+            && path_expr.is_reassignment()
+        {
+            return None;
+        }
+    }
+
+    let mut conditions = vec![];
+    let mut node = site.clone();
+    while let Some(parent) = node.parent() {
+        if &parent == body.syntax() {
+            break;
+        }
+        if let Some(if_expr) = ast::IfExpr::cast(parent.clone()) {
+            let condition = if_expr.condition_expr()?;
+            if refers_to_locals(ctx, &condition) {
+                return None;
+            }
+            if if_expr.then_branch().is_some_and(|it| it.syntax() == &node) {
+                conditions.push(condition.syntax().text().to_string());
+            } else if if_expr.else_branch().is_some_and(|it| it.syntax() == &node) {
+                conditions.push(negate(&condition));
+            } else {
+                // inside the condition itself
+                return None;
+            }
+        }
+        if ast::LoopExpr::can_cast(parent.kind())
+            || ast::WhileExpr::can_cast(parent.kind())
+            || ast::ForExpr::can_cast(parent.kind())
+            || ast::MatchExpr::can_cast(parent.kind())
+            || ast::LambdaExpr::can_cast(parent.kind())
+        {
+            return None;
+        }
+        node = parent;
+    }
+    conditions.reverse();
+    Some(conditions)
+}
+
+fn modifies_stmts(ctx: &AssistContext, body: &ast::BlockExpr) -> Vec<String> {
+    let file_id = ctx.file_id();
+    let mut stmts = vec![];
+    for call_expr in body.syntax().descendants().filter_map(ast::CallExpr::cast) {
+        let Some(path) = call_expr.path() else {
+            continue;
+        };
+        let Some(name) = path.reference_name() else {
+            continue;
+        };
+        if !matches!(name.as_str(), "borrow_global_mut" | "move_from" | "move_to") {
+            continue;
+        }
+        let is_builtin = ctx
+            .sema
+            .resolve_to_element::<ast::AnyFun>(path.reference().in_file(file_id))
+            .and_then(|fun| ctx.sema.fun_module(fun))
+            .is_some_and(|module| module.value.is_builtins());
+        if !is_builtin {
+            continue;
+        }
+        let args = call_expr.arg_exprs();
+        let resource_type = match path.type_args().first().and_then(|it| it.type_()) {
+            Some(type_) => type_.syntax().text().to_string(),
+            None => {
+                let resource_expr = match name.as_str() {
+                    "move_to" => args.get(1).cloned().flatten(),
+                    _ => Some(call_expr.clone().into()),
+                };
+                let Some(resource_ty) = resource_expr
+                    .and_then(|it| ctx.sema.get_expr_type(&it.in_file(file_id)))
+                    .map(|it| it.unwrap_all_refs())
+                else {
+                    continue;
+                };
+                ctx.sema.render_ty_for_ui(&resource_ty, file_id)
+            }
+        };
+        let Some(Some(first_arg)) = args.first().cloned() else {
+            continue;
+        };
+        if refers_to_locals(ctx, &first_arg) {
+            continue;
+        }
+        let address = match name.as_str() {
+            "move_to" => format!("signer::address_of({first_arg})"),
+            _ => first_arg.syntax().text().to_string(),
+        };
+        stmts.push(format!("modifies global<{resource_type}>({address});"));
+    }
+    stmts
+}
+
+/// Whether the expression uses the variables bound inside the function body, like `let` bindings,
+/// which are not visible in the function spec.
+fn refers_to_locals(ctx: &AssistContext, expr: &ast::Expr) -> bool {
+    let file_id = ctx.file_id();
+    expr.syntax()
+        .descendants_of_type::<ast::PathExpr>()
+        .any(|path_expr| {
+            let path = path_expr.path();
+            if !path.is_local() {
+                return false;
+            }
+            ctx.sema
+                .resolve_to_element::<ast::IdentPat>(path.in_file(file_id))
+                .is_some_and(|ident_pat| {
+                    !matches!(ident_pat.value.ident_owner(), Some(ast::IdentPatOwner::Param(_)))
+                })
+        })
+}
+
+/// Returns the `spec_owner` to add `use std::signer;` to, if the generated conditions call
+/// `signer::address_of` and none of the `items_owners` imports it already.
+fn missing_signer_import(
+    items_owners: &[ast::AnyHasItems],
+    spec_owner: ast::AnyHasItems,
+    spec_stmts: &[String],
+) -> Option<ast::AnyHasItems> {
+    if !spec_stmts.iter().any(|it| it.contains("signer::address_of(")) {
+        return None;
+    }
+    let has_signer_import = items_owners.iter().flat_map(|it| it.use_stmts()).any(|use_stmt| {
+        let Some(use_speck_kind) = use_stmt.use_speck().and_then(|it| it.kind()) else {
+            return false;
+        };
+        match use_speck_kind {
+            UseSpeckKind::Module { path, alias: None } => {
+                path.reference_name().is_some_and(|it| it == "signer")
+            }
+            UseSpeckKind::Group { module_path, .. } => {
+                module_path.reference_name().is_some_and(|it| it == "signer")
+                    && use_stmt.group_use_specks().iter().any(|it| it.is_group_self())
+            }
+            _ => false,
+        }
+    });
+    if has_signer_import {
+        return None;
+    }
+    Some(spec_owner)
+}
+
+fn ensures_stmts(ctx: &AssistContext, fun: &ast::Fun) -> Vec<String> {
+    let Some(ret_type) = fun.return_type() else {
+        return vec![];
+    };
+    let ret_ty = ctx.sema.lower_type(ret_type.in_file(ctx.file_id()), false);
+    match ret_ty {
+        Ty::Unit => vec![],
+        Ty::Tuple(ty_tuple) => (1..=ty_tuple.types.len())
+            .map(|i| format!("ensures result_{i} == result_{i};"))
+            .collect(),
+        _ => vec!["ensures result == result;".to_string()],
+    }
+}
+
+fn negate(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::BinExpr(bin_expr) => {
+            if let Some((lhs, (_, BinaryOp::CmpOp(cmp_op)), Some(rhs))) = bin_expr.unpack() {
+                let negated_op = match cmp_op {
+                    CmpOp::Eq { negated } => CmpOp::Eq { negated: !negated },
+                    CmpOp::Ord { ordering, strict } => CmpOp::Ord {
+                        ordering: match ordering {
+                            Ordering::Less => Ordering::Greater,
+                            Ordering::Greater => Ordering::Less,
+                        },
+                        strict: !strict,
+                    },
+                };
+                return format!("{lhs} {negated_op} {rhs}");
+            }
+            format!("!({expr})")
+        }
+        ast::Expr::BangExpr(bang_expr) => bang_expr
+            .expr()
+            .map(|it| it.syntax().text().to_string())
+            .unwrap_or_default(),
+        _ => format!("!{expr}"),
+    }
+}
+
+fn is_logic_expr_text(text: &str) -> bool {
+    (text.contains("&&") || text.contains("||")) && !is_parenthesized(text)
+}
+
+fn is_parenthesized(text: &str) -> bool {
+    if !text.starts_with('(') {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => (),
+        }
+        if depth == 0 {
+            return i == text.len() - 1;
+        }
+    }
+    false
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn dedup(stmts: &mut Vec<String>) {
+    let mut seen = vec![];
+    stmts.retain(|it| {
+        if seen.contains(it) {
+            return false;
+        }
+        seen.push(it.clone());
+        true
+    });
+}
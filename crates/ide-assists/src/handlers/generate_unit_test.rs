@@ -0,0 +1,347 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::assist_context::AssistContext;
+use ide_db::assist_context::LocalAssists;
+use ide_db::assists::AssistId;
+use lang::types::ty::Ty;
+use std::collections::HashSet;
+use syntax::ast::edit::{AstNodeEdit, IndentLevel};
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::node_ext::syntax_node::SyntaxNodeExt;
+use syntax::ast::node_ext::use_speck::UseSpeckKind;
+use syntax::ast::syntax_factory::SyntaxFactory;
+use syntax::ast::{HasAttrs, HasItems, UseStmtsOwner};
+use syntax::files::InFileExt;
+use syntax::syntax_editor::{Element, Position};
+use syntax::{AstNode, ast};
+
+// Assist: generate_unit_test
+//
+// Generates a `#[test]` function for a public function in the `#[test_only]` test module,
+// with the `#[expected_failure]` variant for every error constant the function aborts with.
+// Private `entry` functions can't be called from the test module, so their tests are added
+// to the same module (`#[test]` functions are test-only already).
+//
+// ```
+// module 0x1::coin {
+//     const E_ZERO: u64 = 1;
+//     public fun $0mint(account: &signer, amount: u64) {
+//         assert!(amount > 0, E_ZERO);
+//     }
+// }
+// ```
+// ->
+// ```
+// module 0x1::coin { ... }
+//
+// #[test_only]
+// module 0x1::coin_tests {
+//     use 0x1::coin;
+//
+//     #[test(account = @0x123)]
+//     fun test_mint(account: signer) {
+//         coin::mint(&account, 0);
+//     }
+//
+//     #[test(account = @0x123)]
+//     #[expected_failure(abort_code = coin::E_ZERO)]
+//     fun test_mint_e_zero(account: signer) {
+//         coin::mint(&account, 0);
+//     }
+// }
+// ```
+pub(crate) fn generate_unit_test(acc: &mut LocalAssists, ctx: &AssistContext) -> Option<()> {
+    let fun = ctx.find_node_at_offset::<ast::Fun>()?;
+    // only available on the signature
+    if fun
+        .body()
+        .is_some_and(|it| it.syntax().text_range().contains_inclusive(ctx.offset()))
+    {
+        return None;
+    }
+    // the test module can only call public functions
+    let is_public = fun.visibility_modifier().is_some_and(|it| it.is_public());
+    let in_same_module = !is_public && fun.is_entry();
+    if !(is_public || in_same_module) || fun.is_test() || fun.is_native() {
+        return None;
+    }
+    let module = fun.syntax().containing_module()?;
+    if module.is_test_only() {
+        return None;
+    }
+    let fun_name = fun.name()?.as_string();
+    if in_same_module {
+        let existing_fun_names = fun_names(&module);
+        let test_fun_name = unique_name(format!("test_{fun_name}"), &existing_fun_names);
+        let test_funs =
+            test_functions_text(ctx, &fun, &module, true, &test_fun_name, &existing_fun_names);
+        acc.add(
+            AssistId::generate("generate-unit-test"),
+            format!("Generate unit test for `{fun_name}`"),
+            fun.name()?.syntax().text_range(),
+            |editor| {
+                let make = SyntaxFactory::new();
+                let items_owner = ast::AnyHasItems::cast(module.syntax().clone())?;
+                let indent = IndentLevel::from_node(module.syntax()) + 1;
+                for test_fun_text in test_funs {
+                    let test_fun = make.fun_from_text(&test_fun_text).indent_inner(indent);
+                    items_owner.add_item(editor, &test_fun);
+                }
+                editor.add_mappings(make.finish_with_mappings());
+                Some(())
+            },
+        );
+        return Some(());
+    }
+    let module_name = module.name()?.as_string();
+    let address = module.self_or_parent_address_ref()?.syntax().text().to_string();
+
+    let test_module_name = format!("{module_name}_tests");
+    let existing_test_module = ctx
+        .source_file()
+        .syntax()
+        .descendants_of_type::<ast::Module>()
+        .find(|it| {
+            it.is_test_only() && it.name().is_some_and(|name| name.as_string() == test_module_name)
+        });
+
+    let existing_fun_names = existing_test_module.as_ref().map(fun_names).unwrap_or_default();
+    let test_fun_name = unique_name(format!("test_{fun_name}"), &existing_fun_names);
+
+    let test_funs = test_functions_text(ctx, &fun, &module, false, &test_fun_name, &existing_fun_names);
+    let module_path = format!("{address}::{module_name}");
+
+    acc.add(
+        AssistId::generate("generate-unit-test"),
+        format!("Generate unit test for `{fun_name}`"),
+        fun.name()?.syntax().text_range(),
+        |editor| {
+            let make = SyntaxFactory::new();
+            match existing_test_module {
+                Some(test_module) => {
+                    let test_items_owner = ast::AnyHasItems::cast(test_module.syntax().clone())?;
+                    if !has_module_import(&test_module, &module_path) {
+                        let indent = IndentLevel::from_node(test_module.syntax()) + 1;
+                        let use_speck =
+                            make.root_use_speck(make.path_from_import_path(module_path), None, None);
+                        let use_stmt = make.use_stmt(None, use_speck).indent_inner(indent);
+                        test_items_owner.add_use_stmt(editor, &use_stmt);
+                    }
+                    let indent = IndentLevel::from_node(test_module.syntax()) + 1;
+                    for test_fun_text in test_funs {
+                        let test_fun = make.fun_from_text(&test_fun_text).indent_inner(indent);
+                        test_items_owner.add_item(editor, &test_fun);
+                    }
+                }
+                None => {
+                    let module_header = match module.address_ref() {
+                        Some(_) => format!("{address}::{test_module_name}"),
+                        // module is declared inside the `address 0x1 {}` block
+                        None => test_module_name,
+                    };
+                    let test_module_text = format!(
+                        "#[test_only]\nmodule {module_header} {{\n    use {module_path};\n\n{}\n}}",
+                        test_funs
+                            .iter()
+                            .map(|it| IndentLevel(1).to_string() + &it.replace('\n', "\n    "))
+                            .collect::<Vec<_>>()
+                            .join("\n\n")
+                    );
+                    let indent = IndentLevel::from_node(module.syntax());
+                    let test_module = make.module_from_text(&test_module_text).indent_inner(indent);
+                    editor.insert_all(
+                        Position::after(module.syntax()),
+                        vec![
+                            make.whitespace(&format!("\n\n{indent}")).syntax_element(),
+                            test_module.syntax().syntax_element(),
+                        ],
+                    );
+                }
+            }
+            editor.add_mappings(make.finish_with_mappings());
+            Some(())
+        },
+    );
+
+    Some(())
+}
+
+fn test_functions_text(
+    ctx: &AssistContext,
+    fun: &ast::Fun,
+    module: &ast::Module,
+    in_same_module: bool,
+    test_fun_name: &str,
+    existing_fun_names: &HashSet<String>,
+) -> Vec<String> {
+    let file_id = ctx.file_id();
+    let module_prefix = if in_same_module {
+        String::new()
+    } else {
+        let module_name = module.name().map(|it| it.as_string()).unwrap_or_default();
+        format!("{module_name}::")
+    };
+
+    let mut signers = vec![];
+    let mut args = vec![];
+    for param in fun.params() {
+        let param_name = param.ident_name();
+        let param_ty = param
+            .type_()
+            .map(|it| ctx.sema.lower_type(it.in_file(file_id), false))
+            .unwrap_or(Ty::Unknown);
+        match &param_ty {
+            Ty::Signer => {
+                args.push(param_name.clone());
+                signers.push(param_name);
+            }
+            Ty::Reference(ty_ref) if ty_ref.referenced() == Ty::Signer => {
+                let ref_prefix = if ty_ref.is_mut() { "&mut " } else { "&" };
+                args.push(format!("{ref_prefix}{param_name}"));
+                signers.push(param_name);
+            }
+            _ => args.push(placeholder_value(ctx, &param_ty)),
+        }
+    }
+
+    let test_attr = if signers.is_empty() {
+        "#[test]".to_string()
+    } else {
+        let signer_addresses = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| format!("{signer} = @0x{:x}", 0x123 + i))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("#[test({signer_addresses})]")
+    };
+    let signer_params = signers
+        .iter()
+        .map(|it| format!("{it}: signer"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let fun_name = fun.name().map(|it| it.as_string()).unwrap_or_default();
+    let body = format!("{{\n    {module_prefix}{fun_name}({});\n}}", args.join(", "));
+
+    let mut test_funs = vec![format!(
+        "{test_attr}\nfun {test_fun_name}({signer_params}) {body}"
+    )];
+    let mut fun_names = existing_fun_names.clone();
+    fun_names.insert(test_fun_name.to_string());
+    for error_const in abort_error_consts(ctx, fun, module) {
+        let failure_fun_name = unique_name(
+            format!("{test_fun_name}_{}", error_const.to_lowercase()),
+            &fun_names,
+        );
+        let failure_attr = format!("#[expected_failure(abort_code = {module_prefix}{error_const})]");
+        test_funs.push(format!(
+            "{test_attr}\n{failure_attr}\nfun {failure_fun_name}({signer_params}) {body}"
+        ));
+        fun_names.insert(failure_fun_name);
+    }
+    test_funs
+}
+
+/// Collects names of the module constants used as abort codes in `abort` and `assert!` expressions.
+/// Constants wrapped into the calls, like `error::invalid_argument(E_ZERO)`, are skipped,
+/// as the actual abort code is the call result.
+fn abort_error_consts(ctx: &AssistContext, fun: &ast::Fun, module: &ast::Module) -> Vec<String> {
+    let file_id = ctx.file_id();
+    let Some(body) = fun.body() else {
+        return vec![];
+    };
+    let error_exprs = body
+        .syntax()
+        .descendants()
+        .filter_map(|node| {
+            if let Some(abort_expr) = ast::AbortExpr::cast(node.clone()) {
+                return abort_expr.error_expr();
+            }
+            ast::AssertMacroExpr::cast(node).and_then(|it| it.error_expr())
+        })
+        .collect::<Vec<_>>();
+
+    let mut error_consts = vec![];
+    for error_expr in error_exprs {
+        let ast::Expr::PathExpr(path_expr) = error_expr else {
+            continue;
+        };
+        let path = path_expr.path();
+        if !path.is_local() {
+            continue;
+        }
+        let Some(error_const) = ctx.sema.resolve_to_element::<ast::Const>(path.in_file(file_id)) else {
+            continue;
+        };
+        if error_const
+            .value
+            .module()
+            .is_none_or(|it| it.syntax() != module.syntax())
+        {
+            continue;
+        }
+        let Some(const_name) = error_const.value.name().map(|it| it.as_string()) else {
+            continue;
+        };
+        if !error_consts.contains(&const_name) {
+            error_consts.push(const_name);
+        }
+    }
+    error_consts
+}
+
+fn placeholder_value(ctx: &AssistContext, ty: &Ty) -> String {
+    match ty {
+        Ty::Bool => "false".to_string(),
+        Ty::Integer(_) | Ty::Num => "0".to_string(),
+        Ty::Address => "@0x1".to_string(),
+        Ty::Seq(_) => "vector[]".to_string(),
+        Ty::Reference(ty_ref) => {
+            let referenced = placeholder_value(ctx, &ty_ref.referenced());
+            if ty_ref.is_mut() {
+                format!("&mut {referenced}")
+            } else {
+                format!("&{referenced}")
+            }
+        }
+        _ => format!("/* {} */", ctx.sema.render_ty_for_ui(ty, ctx.file_id())),
+    }
+}
+
+fn has_module_import(test_module: &ast::Module, module_path: &str) -> bool {
+    test_module.use_stmts().any(|use_stmt| {
+        let Some(use_speck_kind) = use_stmt.use_speck().and_then(|it| it.kind()) else {
+            return false;
+        };
+        match use_speck_kind {
+            UseSpeckKind::Module { path, .. } => path.syntax().text() == module_path,
+            UseSpeckKind::Group {
+                module_path: group_module_path,
+                ..
+            } => {
+                group_module_path.syntax().text() == module_path
+                    && use_stmt.group_use_specks().iter().any(|it| it.is_group_self())
+            }
+            _ => false,
+        }
+    })
+}
+
+fn fun_names(module: &ast::Module) -> HashSet<String> {
+    module
+        .functions()
+        .into_iter()
+        .filter_map(|fun| fun.name().map(|it| it.as_string()))
+        .collect()
+}
+
+fn unique_name(name: String, existing_names: &HashSet<String>) -> String {
+    if !existing_names.contains(&name) {
+        return name;
+    }
+    (1..)
+        .map(|i| format!("{name}_{i}"))
+        .find(|it| !existing_names.contains(it))
+        .unwrap()
+}
@@ -0,0 +1,1470 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::assist_context::AssistContext;
+use base_db::SourceDatabase;
+use base_db::inputs::InternFileId;
+use base_db::package_root::PackageId;
+use ide_db::RootDatabase;
+use ide_db::assist_context::LocalAssists;
+use ide_db::assists::{AssistId, AssistKind};
+use ide_db::helpers::package_name;
+use ide_db::rename::move_file_stem;
+use ide_db::search::{FileReferenceNode, SearchScope, item_usages};
+use ide_db::source_change::SourceChangeBuilder;
+use lang::loc::{SyntaxLoc, SyntaxLocFileExt};
+use lang::nameres::name_resolution::{WalkScopesCtx, get_entries_from_walking_scopes};
+use lang::nameres::namespaces::{MODULES, NsSet};
+use lang::nameres::scope::{ScopeEntry, ScopeEntryExt};
+use lang::node_ext::ModuleLangExt;
+use lang::{Semantics, item_specs};
+use std::cell::{OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
+use syntax::SyntaxKind::*;
+use syntax::ast::edit::{AstNodeEdit, IndentLevel};
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::node_ext::syntax_node::SyntaxNodeExt;
+use syntax::ast::syntax_factory::SyntaxFactory;
+use syntax::ast::{HasAttrs, HasItems, UseStmtsOwner};
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::syntax_editor::{Element, Position, SyntaxEditor};
+use syntax::{AstNode, Direction, SyntaxElement, SyntaxNode, TextRange, TextSize, algo, ast};
+use vfs::{AbsPath, FileId};
+
+// Assist: move_item_to_module
+//
+// Moves a function, struct, enum or constant into another module of the same or another local
+// package, together with its specs.
+// Imports and fully qualified paths are updated, and the visibility of the functions is relaxed
+// to `package` or `public` (or `friend` declarations are added) where the move requires it.
+// Packages which use the item get the dependency on the target package.
+//
+// ```
+// module 0x1::a {
+//     public fun $0f(): u8 { 1 }
+// }
+// module 0x1::b {}
+// module 0x1::main {
+//     use 0x1::a::f;
+//     fun main() { f(); }
+// }
+// ```
+// ->
+// ```
+// module 0x1::a {}
+// module 0x1::b {
+//     public fun f(): u8 { 1 }
+// }
+// module 0x1::main {
+//     use 0x1::b::f;
+//     fun main() { f(); }
+// }
+// ```
+pub(crate) fn move_item_to_module(acc: &mut LocalAssists, ctx: &AssistContext) -> Option<()> {
+    let name = ctx.find_node_at_offset::<ast::Name>()?;
+    let item_syntax = name.syntax().parent()?;
+    if !matches!(item_syntax.kind(), FUN | STRUCT | ENUM | CONST) {
+        return None;
+    }
+    let source_module = ast::Module::cast(item_syntax.parent()?)?.in_file(ctx.file_id());
+    let item = ast::NamedElement::cast(item_syntax)?.in_file(ctx.file_id());
+    let moved_item = MovedItem::new(ctx, item, source_module)?;
+    if moved_item.has_source_module_only_usages(ctx) {
+        return None;
+    }
+
+    let mut idx = 0;
+    for target in moved_item.target_modules(ctx) {
+        if idx == MAX_TARGET_MODULES {
+            break;
+        }
+        let Some(plan) = moved_item.plan(ctx, target) else {
+            continue;
+        };
+        if !moved_item.is_allowed_by_usages(ctx, &plan) {
+            continue;
+        }
+        // all usages are searched in the whole workspace, so it's done only when the assist is resolved
+        acc.add_source_change(
+            AssistId("move-item-to-module", AssistKind::Refactor, Some(idx)),
+            format!("Move `{}` to `{}`", moved_item.name, plan.target_path),
+            name.syntax().text_range(),
+            |builder| {
+                let usages = moved_item.usages(ctx);
+                let plan = moved_item.plan_for_usages(ctx, plan, usages);
+                moved_item.apply(ctx, builder, plan, usages)
+            },
+        );
+        idx += 1;
+    }
+
+    Some(())
+}
+
+/// Number of the target modules offered for the item, the closest ones go first.
+const MAX_TARGET_MODULES: usize = 10;
+
+struct MovedItem {
+    item: InFile<ast::NamedElement>,
+    name: String,
+    source_module: InFile<ast::Module>,
+    /// The item itself and its specs.
+    moved_nodes: Vec<InFile<SyntaxNode>>,
+    /// Usages outside of the moved nodes.
+    usages: OnceCell<Vec<Usage>>,
+    references: Vec<Reference>,
+    /// Modules of the structs and enums which are packed, unpacked or have their fields accessed
+    /// in the moved nodes. It's only allowed in the declaring module.
+    struct_internals: Vec<InFile<ast::Module>>,
+    /// `m::` qualifiers in the moved nodes, dropped if `m` is the target module.
+    module_qualifiers: Vec<(FileRange, InFile<ast::Module>)>,
+    /// Dependencies of the modules after the move, without the moved item.
+    deps_cache: RefCell<HashMap<SyntaxLoc, Vec<InFile<ast::Module>>>>,
+}
+
+struct Usage {
+    file_range: FileRange,
+    kind: UsageKind,
+    has_alias: bool,
+    owner_module: Option<InFile<ast::Module>>,
+    is_msl: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UsageKind {
+    /// `use 0x1::m::item;`
+    Use,
+    /// `use 0x1::m::{item, other};`
+    GroupUse,
+    /// `m::item()`
+    Qualified,
+    /// `item()`
+    Unqualified,
+    Other,
+}
+
+/// Module-level item referenced from the moved nodes.
+struct Reference {
+    element: InFile<ast::NamedElement>,
+    module: InFile<ast::Module>,
+    /// Name which needs to be resolvable in the target module, for unqualified references.
+    name: Option<String>,
+}
+
+struct MovePlan {
+    target: InFile<ast::Module>,
+    target_path: String,
+    /// Modules referenced from the moved nodes.
+    item_deps: Vec<InFile<ast::Module>>,
+    /// Imports required by the moved nodes in the target module.
+    imports: Vec<(String, Option<String>)>,
+    visibility_fixes: Vec<(InFile<ast::Fun>, VisibilityFix)>,
+}
+
+#[derive(Debug, Clone)]
+enum VisibilityFix {
+    Change(&'static str),
+    AddFriends(Vec<InFile<ast::Module>>),
+}
+
+impl MovedItem {
+    fn new(
+        ctx: &AssistContext,
+        item: InFile<ast::NamedElement>,
+        source_module: InFile<ast::Module>,
+    ) -> Option<Self> {
+        let sema = &ctx.sema;
+        let db = ctx.db();
+        let name = item.value.name()?.as_string();
+
+        let mut moved_nodes = vec![item.as_ref().map(|it| it.syntax().clone())];
+        let item_specs = item_specs::get_item_specs_for_items_in_file(db, item.file_id.intern(db));
+        for item_spec_loc in item_specs.get(&item.loc()).into_iter().flatten() {
+            moved_nodes.push(item_spec_loc.to_syntax_node(db)?);
+        }
+        let is_moved = |file_range: FileRange| is_in_nodes(&moved_nodes, file_range);
+
+        let mut references = vec![];
+        let mut struct_internals = vec![];
+        let mut module_qualifiers = vec![];
+        for node in &moved_nodes {
+            let file_id = node.file_id;
+            for path in node.value.descendants_of_type::<ast::Path>() {
+                if path.syntax().ancestors().any(|it| it.kind() == USE_SPECK) {
+                    continue;
+                }
+                let Some(element) =
+                    sema.resolve_to_element::<ast::NamedElement>(path.clone().in_file(file_id))
+                else {
+                    continue;
+                };
+                if is_moved(element.loc().file_range()) || sema.is_builtins_file(element.file_id) {
+                    continue;
+                }
+                let is_adt = matches!(
+                    element.value,
+                    ast::NamedElement::Struct(_)
+                        | ast::NamedElement::Enum(_)
+                        | ast::NamedElement::Variant(_)
+                );
+                if is_adt
+                    && is_struct_internals_path(&path)
+                    && !path.syntax().is_msl_context()
+                    && let Some(adt_module) = element.value.syntax().containing_module()
+                {
+                    struct_internals.push(adt_module.in_file(element.file_id));
+                }
+                let module = match &element.value {
+                    ast::NamedElement::Module(module) => module.clone(),
+                    _ => {
+                        let Some(module) = element.value.syntax().parent().and_then(ast::Module::cast)
+                        else {
+                            continue;
+                        };
+                        module
+                    }
+                };
+                let module = module.in_file(element.file_id);
+                if let ast::NamedElement::Module(_) = &element.value
+                    && let Some(parent_path) = path.syntax().parent().and_then(ast::Path::cast)
+                    && let Some(coloncolon) = parent_path.coloncolon_token()
+                {
+                    let range = TextRange::new(
+                        path.syntax().text_range().start(),
+                        coloncolon.text_range().end(),
+                    );
+                    module_qualifiers.push((FileRange { file_id, range }, module.clone()));
+                }
+                let name = match path.qualifier() {
+                    Some(_) => None,
+                    None => path.reference_name(),
+                };
+                references.push(Reference { element, module, name });
+            }
+            for method_call_expr in node.value.descendants_of_type::<ast::MethodCallExpr>() {
+                let Some(fun) = sema.resolve_to_element::<ast::Fun>(method_call_expr.in_file(file_id))
+                else {
+                    continue;
+                };
+                if is_moved(fun.loc().file_range()) {
+                    continue;
+                }
+                let Some(module) = fun.value.syntax().parent().and_then(ast::Module::cast) else {
+                    continue;
+                };
+                let module = module.in_file(fun.file_id);
+                references.push(Reference {
+                    element: fun.map_into(),
+                    module,
+                    name: None,
+                });
+            }
+            for dot_expr in node.value.descendants_of_type::<ast::DotExpr>() {
+                if dot_expr.syntax().is_msl_context() {
+                    continue;
+                }
+                let Some(named_field) =
+                    sema.resolve_to_element::<ast::NamedField>(dot_expr.in_file(file_id))
+                else {
+                    continue;
+                };
+                if is_moved(named_field.loc().file_range()) {
+                    continue;
+                }
+                if let Some(adt_module) = named_field.value.syntax().containing_module() {
+                    struct_internals.push(adt_module.in_file(named_field.file_id));
+                }
+            }
+        }
+
+        Some(MovedItem {
+            item,
+            name,
+            source_module,
+            moved_nodes,
+            usages: OnceCell::new(),
+            references,
+            struct_internals,
+            module_qualifiers,
+            deps_cache: RefCell::new(HashMap::new()),
+        })
+    }
+
+    fn usages(&self, ctx: &AssistContext) -> &[Usage] {
+        self.usages.get_or_init(|| self.find_usages(ctx))
+    }
+
+    fn find_usages(&self, ctx: &AssistContext) -> Vec<Usage> {
+        let sema = &ctx.sema;
+        let is_moved = |file_range: FileRange| is_in_nodes(&self.moved_nodes, file_range);
+
+        let mut usages = vec![];
+        for (file_id, file_references) in item_usages(sema, self.item.clone()).fetch_all().iter() {
+            for file_reference in file_references {
+                let FileReferenceNode::NameRef(name_ref) = &file_reference.name else {
+                    continue;
+                };
+                let file_range = FileRange {
+                    file_id,
+                    range: file_reference.range,
+                };
+                if is_moved(file_range) {
+                    continue;
+                }
+                let (kind, has_alias) = usage_kind(name_ref);
+                let owner_module = name_ref
+                    .syntax()
+                    .containing_items_owner()
+                    .and_then(|it| items_owner_module(sema, it.in_file(file_id)));
+                usages.push(Usage {
+                    file_range,
+                    kind,
+                    has_alias,
+                    owner_module,
+                    is_msl: name_ref.syntax().is_msl_context(),
+                });
+            }
+        }
+        usages
+    }
+
+    /// Checks for the usages which are only allowed in the declaring module, like the private constants,
+    /// struct literals and field accesses. They cannot be anywhere else, so only the source module
+    /// and its specs are searched.
+    fn has_source_module_only_usages(&self, ctx: &AssistContext) -> bool {
+        let sema = &ctx.sema;
+        let item = &self.item;
+        let is_moved = |file_range: FileRange| is_in_nodes(&self.moved_nodes, file_range);
+        let scope = SearchScope::from_module_and_module_spec(ctx.db(), self.source_module.clone());
+
+        let has_disallowed_usages = item_usages(sema, item.clone())
+            .in_scope(scope.clone())
+            .fetch_all()
+            .iter()
+            .any(|(file_id, file_references)| {
+                file_references.iter().any(|file_reference| {
+                    let FileReferenceNode::NameRef(name_ref) = &file_reference.name else {
+                        return false;
+                    };
+                    let file_range = FileRange {
+                        file_id,
+                        range: file_reference.range,
+                    };
+                    !is_moved(file_range) && !is_allowed_item_usage(&item.value, name_ref)
+                })
+            });
+        if has_disallowed_usages {
+            return true;
+        }
+        // fields are accessible only in the declaring module
+        let named_fields = match &item.value {
+            ast::NamedElement::Struct(struct_) => struct_.named_fields(),
+            ast::NamedElement::Enum(enum_) => enum_.named_fields(),
+            _ => vec![],
+        };
+        named_fields.into_iter().any(|named_field| {
+            let named_field = ast::NamedElement::from(named_field).in_file(item.file_id);
+            item_usages(sema, named_field)
+                .in_scope(scope.clone())
+                .fetch_all()
+                .file_ranges()
+                .any(|it| !is_moved(it))
+        })
+    }
+
+    /// Modules of the local packages, the item can be moved into the package which doesn't depend
+    /// on the source package. Modules of the same file, modules referenced by the item and modules
+    /// of the same package go first.
+    fn target_modules(&self, ctx: &AssistContext) -> Vec<InFile<ast::Module>> {
+        let sema = &ctx.sema;
+        let db = ctx.db();
+        let source_file_id = self.source_module.file_id;
+        let source_package_id = db.file_package_id(source_file_id);
+        if sema.is_library(source_package_id) {
+            return vec![];
+        }
+        let source_loc = self.source_module.loc();
+        let source_is_test_only = self.source_module.value.is_test_only();
+        let referenced_locs = self
+            .references
+            .iter()
+            .map(|it| it.module.loc())
+            .collect::<HashSet<_>>();
+
+        let mut targets = vec![];
+        for package_id in db.all_package_ids().data(db) {
+            let package_root = sema.package_root(package_id);
+            if package_root.is_library() || package_root.is_builtin() {
+                continue;
+            }
+            if package_id != source_package_id
+                && sema.dependencies(package_id).contains(&source_package_id)
+            {
+                continue;
+            }
+            for file_id in package_root.file_ids() {
+                if move_file_stem(db, file_id).is_none() || sema.is_builtins_file(file_id) {
+                    continue;
+                }
+                for module in sema.parse(file_id).all_modules() {
+                    if module.is_test_only() && !source_is_test_only {
+                        continue;
+                    }
+                    let module = module.in_file(file_id);
+                    let module_loc = module.loc();
+                    if module_loc == source_loc {
+                        continue;
+                    }
+                    let Some(module_path) = module_path(&module.value) else {
+                        continue;
+                    };
+                    let priority = (
+                        file_id != source_file_id,
+                        !referenced_locs.contains(&module_loc),
+                        package_id != source_package_id,
+                    );
+                    targets.push((priority, module_path, module));
+                }
+            }
+        }
+        targets.sort_by(|(a_priority, a_path, _), (b_priority, b_path, _)| {
+            (a_priority, a_path).cmp(&(b_priority, b_path))
+        });
+        targets.into_iter().map(|(_, _, module)| module).collect()
+    }
+
+    /// Returns `None` if the moved nodes cannot be placed into the `target`.
+    /// Doesn't look at the usages of the item, see [`MovedItem::is_allowed_by_usages`].
+    fn plan(&self, ctx: &AssistContext, target: InFile<ast::Module>) -> Option<MovePlan> {
+        let sema = &ctx.sema;
+        let db = ctx.db();
+        let target_loc = target.loc();
+        let target_path = module_path(&target.value)?;
+        let target_scope = target.as_ref().map(|it| it.syntax().clone());
+        let target_package_deps = sema.dependencies(db.file_package_id(target.file_id));
+
+        // `item` is already declared or imported in the target module
+        let item_entry = self.item.clone().to_entry()?;
+        if entries_in_scope(db, &target_scope, item_entry.ns.into(), &self.name)
+            .iter()
+            .any(|it| it.node_loc != item_entry.node_loc)
+        {
+            return None;
+        }
+        if self.struct_internals.iter().any(|it| it.loc() != target_loc) {
+            return None;
+        }
+
+        let mut imports = vec![];
+        let mut visibility_fixes: Vec<(InFile<ast::Fun>, VisibilityFix)> = vec![];
+        let mut item_deps = vec![];
+        for reference in &self.references {
+            if reference.module.loc() == target_loc {
+                continue;
+            }
+            // not accessible from the target package
+            if !target_package_deps.contains(&db.file_package_id(reference.module.file_id)) {
+                return None;
+            }
+            item_deps.push(reference.module.clone());
+            match &reference.element.value {
+                // constants are private to the declaring module
+                ast::NamedElement::Const(_) => return None,
+                ast::NamedElement::Fun(fun) => {
+                    let fun = fun.clone().in_file(reference.element.file_id);
+                    if let Some(fix) = visibility_fix(sema, &fun, &reference.module, &[target.clone()]) {
+                        if sema.is_library(db.file_package_id(fun.file_id)) {
+                            return None;
+                        }
+                        if !visibility_fixes.iter().any(|(it, _)| it.loc() == fun.loc()) {
+                            visibility_fixes.push((fun, fix));
+                        }
+                    }
+                }
+                _ => (),
+            }
+            if let Some(name) = &reference.name {
+                let entry = reference.element.clone().to_entry()?;
+                let entries = entries_in_scope(db, &target_scope, entry.ns.into(), name);
+                if entries.iter().any(|it| it.node_loc == entry.node_loc) {
+                    continue;
+                }
+                if !entries.is_empty() {
+                    return None;
+                }
+                let import = (
+                    element_path(&reference.element.value)?,
+                    (*name != entry.name).then(|| name.clone()),
+                );
+                if !imports.contains(&import) {
+                    imports.push(import);
+                }
+            }
+        }
+
+        Some(MovePlan {
+            target,
+            target_path,
+            item_deps,
+            imports,
+            visibility_fixes,
+        })
+    }
+
+    /// Adds the visibility fix of the moved function required by its usages.
+    fn plan_for_usages(&self, ctx: &AssistContext, mut plan: MovePlan, usages: &[Usage]) -> MovePlan {
+        let user_modules = usages
+            .iter()
+            .filter(|it| !it.is_msl)
+            .filter_map(|it| it.owner_module.clone())
+            .collect::<Vec<_>>();
+        if let ast::NamedElement::Fun(fun) = &self.item.value {
+            let fun = fun.clone().in_file(self.item.file_id);
+            if let Some(fix) = visibility_fix(&ctx.sema, &fun, &plan.target, &user_modules) {
+                plan.visibility_fixes.push((fun, fix));
+            }
+        }
+        plan
+    }
+
+    /// Checks the usages of the item which prevent the move into the `plan.target`: the aliased imports
+    /// in the target module and the usages in the modules the target depends on after the move,
+    /// as they would create the dependency cycle. Only the target and the modules it depends on
+    /// are searched.
+    fn is_allowed_by_usages(&self, ctx: &AssistContext, plan: &MovePlan) -> bool {
+        let sema = &ctx.sema;
+        let target_loc = plan.target.loc();
+        let Some(target_deps) = self.target_deps_after_move(ctx, plan) else {
+            return false;
+        };
+        let mut scope = SearchScope::empty();
+        scope.add_file_range(target_loc.file_range());
+        for module in &target_deps {
+            scope.add_file_range(module.loc().file_range());
+        }
+
+        let is_moved = |file_range: FileRange| is_in_nodes(&self.moved_nodes, file_range);
+        for (file_id, file_references) in item_usages(sema, self.item.clone())
+            .in_scope(scope)
+            .fetch_all()
+            .iter()
+        {
+            for file_reference in file_references {
+                let FileReferenceNode::NameRef(name_ref) = &file_reference.name else {
+                    continue;
+                };
+                let file_range = FileRange {
+                    file_id,
+                    range: file_reference.range,
+                };
+                if is_moved(file_range) {
+                    continue;
+                }
+                let Some(owner_module) = name_ref
+                    .syntax()
+                    .containing_items_owner()
+                    .and_then(|it| items_owner_module(sema, it.in_file(file_id)))
+                else {
+                    continue;
+                };
+                if owner_module.loc() == target_loc {
+                    let (_, has_alias) = usage_kind(name_ref);
+                    if has_alias {
+                        return false;
+                    }
+                } else if !name_ref.syntax().is_msl_context() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Modules reachable from the target module in the module dependency graph after the move,
+    /// `None` if the target module reaches itself.
+    fn target_deps_after_move(
+        &self,
+        ctx: &AssistContext,
+        plan: &MovePlan,
+    ) -> Option<Vec<InFile<ast::Module>>> {
+        let sema = &ctx.sema;
+        let db = ctx.db();
+        let target = &plan.target;
+        let target_loc = target.loc();
+        let target_package_id = db.file_package_id(target.file_id);
+        let source_loc = self.source_module.loc();
+        let source_package_id = db.file_package_id(self.source_module.file_id);
+
+        let deps_after_move = |module: &InFile<ast::Module>| {
+            let module_loc = module.loc();
+            let mut deps = self
+                .deps_cache
+                .borrow_mut()
+                .entry(module_loc.clone())
+                .or_insert_with(|| {
+                    // the moved nodes are not a part of the source module anymore
+                    let skip_ranges = self
+                        .moved_nodes
+                        .iter()
+                        .filter(|it| module_loc == source_loc && it.file_id == module.file_id)
+                        .map(|it| it.value.text_range())
+                        .collect::<Vec<_>>();
+                    module_path_deps(sema, module.as_ref().map(|it| it.syntax()), &skip_ranges)
+                })
+                .clone();
+            if module_loc == target_loc {
+                deps.extend(plan.item_deps.iter().cloned());
+            }
+            deps
+        };
+
+        let mut visited = HashSet::new();
+        let mut reachable = vec![];
+        let mut stack = deps_after_move(target);
+        while let Some(module) = stack.pop() {
+            let module_loc = module.loc();
+            if module_loc == target_loc {
+                return None;
+            }
+            if !visited.insert(module_loc) {
+                continue;
+            }
+            // modules which can neither use the item nor depend on the target
+            let package_id = db.file_package_id(module.file_id);
+            let package_deps = sema.dependencies(package_id);
+            if sema.is_library(package_id)
+                || !(package_deps.contains(&source_package_id)
+                    || package_deps.contains(&target_package_id))
+            {
+                continue;
+            }
+            stack.extend(deps_after_move(&module));
+            reachable.push(module);
+        }
+        Some(reachable)
+    }
+
+    fn apply(
+        &self,
+        ctx: &AssistContext,
+        builder: &mut SourceChangeBuilder,
+        plan: MovePlan,
+        usages: &[Usage],
+    ) -> Option<()> {
+        let sema = &ctx.sema;
+        let make = SyntaxFactory::without_mappings();
+        let mut files = FileEditors::new(sema);
+        let mut headers = ItemsOwnerHeaders::default();
+        let mut removed_use_stmts: Vec<InFile<TextRange>> = vec![];
+
+        let target_loc = plan.target.loc();
+        let target_range = target_loc.file_range();
+        let target = files.find_node::<ast::Module>(target_range)?;
+        let target_indent = IndentLevel::from_node(target.syntax()) + 1;
+        let item_loc = self.item.loc();
+
+        for (path, alias) in plan.imports.iter().cloned() {
+            headers.add_use(target_range, path, alias);
+        }
+
+        // visibility
+        let mut moved_fun_visibility = None;
+        for (fun, fix) in &plan.visibility_fixes {
+            let decl_module_range = if fun.loc() == item_loc {
+                target_range
+            } else {
+                fun.value
+                    .syntax()
+                    .containing_module()?
+                    .in_file(fun.file_id)
+                    .loc()
+                    .file_range()
+            };
+            match fix {
+                VisibilityFix::Change(visibility) if fun.loc() == item_loc => {
+                    moved_fun_visibility = Some(*visibility);
+                }
+                VisibilityFix::Change(visibility) => {
+                    let fun_range = fun.loc().file_range();
+                    let fun = files.find_node::<ast::Fun>(fun_range)?;
+                    let new_modifier = make.visibility_modifier(visibility);
+                    let editor = files.editor(fun_range.file_id);
+                    match fun.visibility_modifier() {
+                        Some(modifier) => editor.replace(modifier.syntax(), new_modifier.syntax()),
+                        None => {
+                            let anchor = first_non_attr_child(fun.syntax())?;
+                            editor.insert_all(
+                                Position::before(anchor),
+                                vec![
+                                    new_modifier.syntax().syntax_element(),
+                                    make.whitespace(" ").syntax_element(),
+                                ],
+                            );
+                        }
+                    }
+                }
+                VisibilityFix::AddFriends(friend_modules) => {
+                    for friend_module in friend_modules {
+                        headers.add_friend(decl_module_range, module_path(&friend_module.value)?);
+                    }
+                }
+            }
+        }
+
+        // usages
+        let source_loc = self.source_module.loc();
+        for usage in usages {
+            let file_id = usage.file_range.file_id;
+            let Some(name_ref) = files.find_node::<ast::NameRef>(usage.file_range) else {
+                continue;
+            };
+            let Some(path) = name_ref.syntax().ancestors().nth(2).and_then(ast::Path::cast) else {
+                continue;
+            };
+            let is_in_target = usage
+                .owner_module
+                .as_ref()
+                .is_some_and(|it| it.loc() == target_loc);
+            let target_module_path = make.path_from_import_path(plan.target_path.clone());
+            match usage.kind {
+                UsageKind::Use => {
+                    let use_stmt = path.syntax().ancestors().find_map(ast::UseStmt::cast)?;
+                    if is_in_target {
+                        use_stmt.delete(files.editor(file_id));
+                        removed_use_stmts.push(use_stmt.syntax().text_range().in_file(file_id));
+                    } else {
+                        let qualifier = path.qualifier()?;
+                        files
+                            .editor(file_id)
+                            .replace(qualifier.syntax(), target_module_path.syntax());
+                    }
+                }
+                UsageKind::GroupUse => {
+                    let use_speck = path.syntax().parent().and_then(ast::UseSpeck::cast)?;
+                    let use_stmt = use_speck.parent_use_group()?.use_stmt()?;
+                    if use_stmt.group_use_specks().len() == 1 {
+                        if is_in_target {
+                            use_stmt.delete(files.editor(file_id));
+                            removed_use_stmts.push(use_stmt.syntax().text_range().in_file(file_id));
+                        } else {
+                            let module_path = use_stmt.use_speck()?.path()?;
+                            files
+                                .editor(file_id)
+                                .replace(module_path.syntax(), target_module_path.syntax());
+                        }
+                        continue;
+                    }
+                    use_stmt.delete_group_use_specks(files.editor(file_id), vec![use_speck.clone()]);
+                    if !is_in_target {
+                        let owner = name_ref.syntax().containing_items_owner()?;
+                        let alias = use_speck
+                            .use_alias()
+                            .and_then(|it| it.name())
+                            .map(|it| it.as_string());
+                        let owner_range = FileRange {
+                            file_id,
+                            range: owner.syntax().text_range(),
+                        };
+                        headers.add_use(
+                            owner_range,
+                            format!("{}::{}", plan.target_path, self.name),
+                            alias,
+                        );
+                    }
+                }
+                UsageKind::Qualified => {
+                    let qualifier = path.qualifier()?;
+                    let editor = files.editor(file_id);
+                    if is_in_target {
+                        editor.delete(qualifier.syntax());
+                        editor.delete(path.coloncolon_token()?);
+                        continue;
+                    }
+                    let module_name = qualifier
+                        .qualifier()
+                        .is_none()
+                        .then(|| {
+                            let walk_ctx = WalkScopesCtx {
+                                allowed_ns: MODULES,
+                                start_at: name_ref.syntax().clone().in_file(file_id),
+                                expected_name: None,
+                            };
+                            get_entries_from_walking_scopes(sema.db, walk_ctx)
+                                .into_iter()
+                                .find(|it| it.node_loc == target_loc)
+                                .map(|it| it.name)
+                        })
+                        .flatten();
+                    let new_qualifier = match module_name {
+                        Some(module_name) => make.path_from_import_path(module_name),
+                        None => target_module_path,
+                    };
+                    editor.replace(qualifier.syntax(), new_qualifier.syntax());
+                }
+                UsageKind::Unqualified => {
+                    // was declared in the same module
+                    if usage
+                        .owner_module
+                        .as_ref()
+                        .is_some_and(|it| it.loc() == source_loc)
+                    {
+                        let owner = name_ref.syntax().containing_items_owner()?;
+                        let owner_range = FileRange {
+                            file_id,
+                            range: owner.syntax().text_range(),
+                        };
+                        headers.add_use(
+                            owner_range,
+                            format!("{}::{}", plan.target_path, self.name),
+                            None,
+                        );
+                    }
+                }
+                UsageKind::Other => (),
+            }
+        }
+
+        // moved item and its specs
+        let mut new_items = vec![];
+        for node in &self.moved_nodes {
+            let node_range = node.value.text_range();
+            let mut text_edits = vec![];
+            if node.value.kind() == FUN
+                && let Some(visibility) = moved_fun_visibility
+            {
+                let fun = ast::Fun::cast(node.value.clone())?;
+                match fun.visibility_modifier() {
+                    Some(modifier) => {
+                        text_edits.push((modifier.syntax().text_range(), visibility.to_string()))
+                    }
+                    None => {
+                        let offset = first_non_attr_child(fun.syntax())?.text_range().start();
+                        text_edits.push((TextRange::empty(offset), format!("{visibility} ")));
+                    }
+                }
+            }
+            for (qualifier_range, module) in &self.module_qualifiers {
+                if qualifier_range.file_id == node.file_id
+                    && node_range.contains_range(qualifier_range.range)
+                    && module.loc() == target_loc
+                {
+                    text_edits.push((qualifier_range.range, String::new()));
+                }
+            }
+            let item_text =
+                apply_text_edits(&node.value.text().to_string(), node_range.start(), text_edits);
+            let source_indent = IndentLevel::from_node(&node.value);
+            let new_item = make
+                .item_from_text(&item_text)
+                .dedent_inner(source_indent)
+                .indent_inner(target_indent);
+            new_items.push(new_item.syntax().clone());
+        }
+        let mut moved_nodes_by_file: HashMap<FileId, Vec<SyntaxNode>> = HashMap::new();
+        for node in &self.moved_nodes {
+            let file_range = FileRange {
+                file_id: node.file_id,
+                range: node.value.text_range(),
+            };
+            let node = files.find_node::<ast::Item>(file_range)?;
+            moved_nodes_by_file
+                .entry(file_range.file_id)
+                .or_default()
+                .push(node.syntax().clone());
+        }
+        for (file_id, nodes) in moved_nodes_by_file {
+            delete_items(files.editor(file_id), nodes);
+        }
+
+        // imports, friends and the moved item
+        let mut target_header = vec![];
+        for OwnerHeader { owner_range, uses, friends } in headers.headers {
+            let owner = files.find_node::<ast::AnyHasItems>(owner_range)?;
+            let mut header = vec![];
+            for (path, alias) in uses {
+                let use_speck = make.use_speck(
+                    make.path_from_import_path(path),
+                    alias.map(|it| make.use_alias(&it)),
+                );
+                header.push(make.use_stmt(None, use_speck).syntax().clone());
+            }
+            for friend_path in friends {
+                header.push(
+                    make.friend(make.path_from_import_path(friend_path))
+                        .syntax()
+                        .clone(),
+                );
+            }
+            if owner_range == target_range {
+                target_header = header;
+                continue;
+            }
+            insert_into_items_owner(
+                files.editor(owner_range.file_id),
+                &make,
+                &owner,
+                header,
+                vec![],
+                &[],
+            );
+        }
+        let target_owner = ast::AnyHasItems::cast(target.syntax().clone())?;
+        let removed_target_use_stmts = removed_use_stmts
+            .into_iter()
+            .filter(|it| it.file_id == target_range.file_id)
+            .map(|it| it.value)
+            .collect::<Vec<_>>();
+        insert_into_items_owner(
+            files.editor(target_range.file_id),
+            &make,
+            &target_owner,
+            target_header,
+            new_items,
+            &removed_target_use_stmts,
+        );
+
+        for (file_id, editor) in files.editors {
+            builder.add_file_edits(file_id, editor);
+        }
+
+        // packages which use the item now depend on the target package
+        let db = ctx.db();
+        let target_package_id = db.file_package_id(plan.target.file_id);
+        let mut user_package_ids = vec![];
+        for usage in usages {
+            let package_id = db.file_package_id(usage.file_range.file_id);
+            if !user_package_ids.contains(&package_id)
+                && !sema.dependencies(package_id).contains(&target_package_id)
+            {
+                user_package_ids.push(package_id);
+            }
+        }
+        for package_id in user_package_ids {
+            add_local_dependency(sema, builder, package_id, target_package_id)?;
+        }
+        Some(())
+    }
+}
+
+/// Adds `Dep = { local = "../Dep" }` to the `[dependencies]` of the package manifest.
+fn add_local_dependency(
+    sema: &Semantics<'_, RootDatabase>,
+    builder: &mut SourceChangeBuilder,
+    package_id: PackageId,
+    dep_package_id: PackageId,
+) -> Option<()> {
+    let db = sema.db;
+    let package_root = sema.package_root(package_id);
+    let dep_package_root = sema.package_root(dep_package_id);
+    let manifest_file_id = package_root.manifest_file_id?;
+    let package_dir = package_root.path_for_file(&manifest_file_id)?.parent()?;
+    let dep_package_dir = dep_package_root
+        .path_for_file(&dep_package_root.manifest_file_id?)?
+        .parent()?;
+    let local_path = relative_path(package_dir.as_path()?, dep_package_dir.as_path()?);
+    let dep = format!(
+        "{} = {{ local = \"{local_path}\" }}",
+        package_name(db, dep_package_id)
+    );
+
+    let manifest_text = db.file_text(manifest_file_id).text(db);
+    let mut is_in_dependencies = false;
+    let mut dependencies_end = None;
+    let mut offset = TextSize::new(0);
+    for line in manifest_text.split_inclusive('\n') {
+        let line_text = line.trim();
+        if line_text.starts_with('[') {
+            is_in_dependencies = line_text == "[dependencies]";
+        }
+        if is_in_dependencies && !line_text.is_empty() && !line_text.starts_with('#') {
+            dependencies_end = Some(offset + TextSize::of(line.trim_end()));
+        }
+        offset += TextSize::of(line);
+    }
+    builder.edit_file(manifest_file_id);
+    match dependencies_end {
+        Some(dependencies_end) => builder.insert(dependencies_end, format!("\n{dep}")),
+        None => builder.insert(
+            TextSize::of(manifest_text.trim_end()),
+            format!("\n\n[dependencies]\n{dep}"),
+        ),
+    }
+    Some(())
+}
+
+/// `../B` for the `A` and `B` directories of the same parent.
+fn relative_path(from: &AbsPath, to: &AbsPath) -> String {
+    let from = from.components().collect::<Vec<_>>();
+    let to = to.components().collect::<Vec<_>>();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts = vec![".."; from.len() - common];
+    parts.extend(to[common..].iter().map(|it| it.as_str()));
+    parts.join("/")
+}
+
+/// Editors for all files changed by the assist, operating on the single syntax tree per file.
+struct FileEditors<'a> {
+    sema: &'a Semantics<'a, RootDatabase>,
+    roots: HashMap<FileId, SyntaxNode>,
+    editors: HashMap<FileId, SyntaxEditor>,
+}
+
+impl<'a> FileEditors<'a> {
+    fn new(sema: &'a Semantics<'a, RootDatabase>) -> Self {
+        FileEditors {
+            sema,
+            roots: HashMap::new(),
+            editors: HashMap::new(),
+        }
+    }
+
+    fn root(&mut self, file_id: FileId) -> &SyntaxNode {
+        let sema = self.sema;
+        self.roots
+            .entry(file_id)
+            .or_insert_with(|| sema.parse(file_id).syntax().clone())
+    }
+
+    fn find_node<N: AstNode>(&mut self, file_range: FileRange) -> Option<N> {
+        let root = self.root(file_range.file_id);
+        algo::find_node_at_range::<N>(root, file_range.range)
+    }
+
+    fn editor(&mut self, file_id: FileId) -> &mut SyntaxEditor {
+        let root = self.root(file_id).clone();
+        self.editors
+            .entry(file_id)
+            .or_insert_with(|| SyntaxEditor::new(root))
+    }
+}
+
+/// `use` statements and `friend` declarations to add to the items owner.
+struct OwnerHeader {
+    owner_range: FileRange,
+    uses: Vec<(String, Option<String>)>,
+    friends: Vec<String>,
+}
+
+#[derive(Default)]
+struct ItemsOwnerHeaders {
+    headers: Vec<OwnerHeader>,
+}
+
+impl ItemsOwnerHeaders {
+    fn header(&mut self, owner_range: FileRange) -> &mut OwnerHeader {
+        let idx = match self.headers.iter().position(|it| it.owner_range == owner_range) {
+            Some(idx) => idx,
+            None => {
+                self.headers.push(OwnerHeader {
+                    owner_range,
+                    uses: vec![],
+                    friends: vec![],
+                });
+                self.headers.len() - 1
+            }
+        };
+        &mut self.headers[idx]
+    }
+
+    fn add_use(&mut self, owner_range: FileRange, path: String, alias: Option<String>) {
+        let uses = &mut self.header(owner_range).uses;
+        if !uses.contains(&(path.clone(), alias.clone())) {
+            uses.push((path, alias));
+        }
+    }
+
+    fn add_friend(&mut self, owner_range: FileRange, module_path: String) {
+        let friends = &mut self.header(owner_range).friends;
+        if !friends.contains(&module_path) {
+            friends.push(module_path);
+        }
+    }
+}
+
+/// Inserts `use` statements and `friend` declarations after the existing ones, and items after the last item.
+fn insert_into_items_owner(
+    editor: &mut SyntaxEditor,
+    make: &SyntaxFactory,
+    owner: &ast::AnyHasItems,
+    header: Vec<SyntaxNode>,
+    items: Vec<SyntaxNode>,
+    removed_ranges: &[TextRange],
+) -> Option<()> {
+    if header.is_empty() && items.is_empty() {
+        return Some(());
+    }
+    let l_curly = owner
+        .syntax()
+        .children_with_tokens()
+        .find(|it| it.kind() == L_CURLY)?;
+    let indent = IndentLevel::from_node(owner.syntax()) + 1;
+    let last_header_node = owner
+        .syntax()
+        .children()
+        .filter(|it| matches!(it.kind(), USE_STMT | FRIEND))
+        .filter(|it| !removed_ranges.contains(&it.text_range()))
+        .last();
+    let last_item = owner.items().last().map(|it| it.syntax().clone());
+
+    if last_header_node.is_none() && last_item.is_none() {
+        // empty module, recreate the whitespace between the braces
+        for ws in owner
+            .syntax()
+            .children_with_tokens()
+            .skip_while(|it| it.kind() != L_CURLY)
+            .filter(|it| it.kind() == WHITESPACE)
+        {
+            editor.delete(ws);
+        }
+        let mut elements = vec![];
+        for node in &header {
+            elements.push(make.whitespace(&format!("\n{indent}")).syntax_element());
+            elements.push(node.syntax_element());
+        }
+        for node in &items {
+            let ws = if elements.is_empty() { "\n" } else { "\n\n" };
+            elements.push(make.whitespace(&format!("{ws}{indent}")).syntax_element());
+            elements.push(node.syntax_element());
+        }
+        let outer_indent = IndentLevel::from_node(owner.syntax());
+        elements.push(make.whitespace(&format!("\n{outer_indent}")).syntax_element());
+        editor.insert_all(Position::after(l_curly), elements);
+        return Some(());
+    }
+
+    if !header.is_empty() {
+        let anchor = last_header_node
+            .clone()
+            .map(|it| it.syntax_element())
+            .unwrap_or_else(|| l_curly.clone());
+        // separate the new header from the items with the blank line
+        let needs_blank_line = last_header_node.is_none()
+            && !anchor.next_token().is_some_and(|it| {
+                it.kind() == WHITESPACE && it.text().chars().filter(|c| *c == '\n').count() > 1
+            });
+        let mut elements = vec![];
+        for node in header {
+            elements.push(make.whitespace(&format!("\n{indent}")).syntax_element());
+            elements.push(node.syntax_element());
+        }
+        if needs_blank_line {
+            elements.push(make.newline().into());
+        }
+        editor.insert_all(Position::after(anchor), elements);
+    }
+    if !items.is_empty() {
+        let anchor = last_item.or(last_header_node)?;
+        let mut elements = vec![];
+        for node in items {
+            elements.push(make.whitespace(&format!("\n\n{indent}")).syntax_element());
+            elements.push(node.syntax_element());
+        }
+        editor.insert_all(Position::after(anchor), elements);
+    }
+    Some(())
+}
+
+/// Deletes the items together with the whitespace around them, keeping the blank lines
+/// between the remaining items intact.
+fn delete_items(editor: &mut SyntaxEditor, mut nodes: Vec<SyntaxNode>) {
+    nodes.sort_by_key(|it| it.text_range().start());
+
+    // adjacent items are deleted together
+    let mut groups: Vec<Vec<SyntaxNode>> = vec![];
+    for node in nodes {
+        if let Some(group) = groups.last_mut()
+            && let Some(last) = group.last()
+            && last.parent() == node.parent()
+            && last
+                .next_siblings_with_tokens()
+                .take_while(|it| it.as_node() != Some(&node))
+                .all(|it| it.kind() == WHITESPACE)
+        {
+            group.push(node);
+            continue;
+        }
+        groups.push(vec![node]);
+    }
+
+    let mut deleted = HashSet::new();
+    let mut delete = |editor: &mut SyntaxEditor, element: SyntaxElement| {
+        if deleted.insert(element.clone()) {
+            editor.delete(element);
+        }
+    };
+    for group in groups {
+        let (Some(first), Some(last)) = (group.first().cloned(), group.last().cloned()) else {
+            continue;
+        };
+        for element in first
+            .siblings_with_tokens(Direction::Next)
+            .take_while(|it| it.text_range().end() <= last.text_range().end())
+        {
+            delete(editor, element);
+        }
+        let ws_before = first.prev_sibling_or_token().filter(|it| it.kind() == WHITESPACE);
+        let ws_after = last.next_sibling_or_token().filter(|it| it.kind() == WHITESPACE);
+        let is_last = last
+            .next_siblings_with_tokens()
+            .find(|it| it.kind() != WHITESPACE)
+            .is_none_or(|it| it.kind() == R_CURLY);
+        let is_first = first
+            .prev_siblings_with_tokens()
+            .find(|it| it.kind() != WHITESPACE)
+            .is_some_and(|it| it.kind() == L_CURLY);
+        let ws_to_delete = match (is_first, is_last) {
+            // the owner becomes empty, `module 0x1::m {}`
+            (true, true) => vec![ws_before, ws_after],
+            (true, false) => vec![ws_after.or(ws_before)],
+            (false, _) => vec![ws_before.or(ws_after)],
+        };
+        for ws in ws_to_delete.into_iter().flatten() {
+            delete(editor, ws);
+        }
+    }
+}
+
+fn visibility_fix(
+    sema: &Semantics<'_, RootDatabase>,
+    fun: &InFile<ast::Fun>,
+    decl_module: &InFile<ast::Module>,
+    users: &[InFile<ast::Module>],
+) -> Option<VisibilityFix> {
+    let db = sema.db;
+    let decl_module_loc = decl_module.loc();
+    let mut user_locs = HashSet::new();
+    let users = users
+        .iter()
+        .filter(|it| it.loc() != decl_module_loc && user_locs.insert(it.loc()))
+        .cloned()
+        .collect::<Vec<_>>();
+    if users.is_empty() {
+        return None;
+    }
+    let decl_package_id = db.file_package_id(decl_module.file_id);
+    let is_same_package = users
+        .iter()
+        .all(|it| db.file_package_id(it.file_id) == decl_package_id);
+    let relaxed = if is_same_package { "package" } else { "public" };
+
+    let Some(modifier) = fun.value.visibility_modifier() else {
+        return Some(VisibilityFix::Change(relaxed));
+    };
+    if modifier.is_public() || modifier.is_public_script() {
+        return None;
+    }
+    if modifier.is_package() || modifier.is_public_package() {
+        return (!is_same_package).then_some(VisibilityFix::Change("public"));
+    }
+
+    // friend
+    let friend_locs = decl_module
+        .value
+        .friend_decls()
+        .into_iter()
+        .filter_map(|it| it.path())
+        .filter_map(|it| sema.resolve_to_element::<ast::Module>(it.in_file(decl_module.file_id)))
+        .map(|it| it.loc())
+        .collect::<HashSet<_>>();
+    let new_friends = users
+        .into_iter()
+        .filter(|it| !friend_locs.contains(&it.loc()))
+        .collect::<Vec<_>>();
+    if new_friends.is_empty() {
+        return None;
+    }
+    // friends should be declared at the same address of the same package
+    let decl_address = decl_module.value.address();
+    let is_same_address = new_friends
+        .iter()
+        .all(|it| match (&decl_address, it.value.address()) {
+            (Some(decl_address), Some(address)) => decl_address.equals_to(db, &address, false),
+            _ => false,
+        });
+    if is_same_package && is_same_address {
+        Some(VisibilityFix::AddFriends(new_friends))
+    } else {
+        Some(VisibilityFix::Change(relaxed))
+    }
+}
+
+fn is_in_nodes(nodes: &[InFile<SyntaxNode>], file_range: FileRange) -> bool {
+    nodes.iter().any(|node| {
+        node.file_id == file_range.file_id && node.value.text_range().contains_range(file_range.range)
+    })
+}
+
+/// Modules referenced from the `node` with the `use` statements or qualified paths.
+fn module_path_deps(
+    sema: &Semantics<'_, RootDatabase>,
+    node: InFile<&SyntaxNode>,
+    skip_ranges: &[TextRange],
+) -> Vec<InFile<ast::Module>> {
+    let (file_id, node) = node.unpack();
+    let own_module_loc = ast::Module::cast(node.clone()).map(|it| it.in_file(file_id).loc());
+    let mut seen = HashSet::new();
+    let mut deps = vec![];
+    for path in node.descendants_of_type::<ast::Path>() {
+        let path_range = path.syntax().text_range();
+        if skip_ranges.iter().any(|it| it.contains_range(path_range)) {
+            continue;
+        }
+        let Some(parent) = path.syntax().parent() else {
+            continue;
+        };
+        let is_module_path = match parent.kind() {
+            USE_SPECK => true,
+            PATH => ast::Path::cast(parent).and_then(|it| it.qualifier()).as_ref() == Some(&path),
+            _ => false,
+        };
+        if !is_module_path {
+            continue;
+        }
+        let Some(module) = sema.resolve_to_element::<ast::Module>(path.in_file(file_id)) else {
+            continue;
+        };
+        let module_loc = module.loc();
+        if own_module_loc.as_ref() == Some(&module_loc) {
+            continue;
+        }
+        if seen.insert(module_loc) {
+            deps.push(module);
+        }
+    }
+    deps
+}
+
+/// Usages of the moved struct or enum which are only allowed in the declaring module.
+fn is_allowed_item_usage(item: &ast::NamedElement, name_ref: &ast::NameRef) -> bool {
+    match item {
+        ast::NamedElement::Const(_) => name_ref.syntax().ancestors().any(|it| it.kind() == ATTR),
+        ast::NamedElement::Struct(_) | ast::NamedElement::Enum(_) => {
+            let Some(path) = name_ref.syntax().ancestors().nth(2).and_then(ast::Path::cast) else {
+                return true;
+            };
+            // `E::V`
+            let is_qualifier = path.syntax().parent().is_some_and(|it| it.kind() == PATH);
+            !is_qualifier && !is_struct_internals_path(&path)
+        }
+        _ => true,
+    }
+}
+
+/// `S { f }`, `S(1)`, `borrow_global<S>()` and `acquires S` are only allowed in the module declaring `S`.
+fn is_struct_internals_path(path: &ast::Path) -> bool {
+    let Some(parent) = path.syntax().parent() else {
+        return false;
+    };
+    match parent.kind() {
+        STRUCT_LIT | STRUCT_PAT | TUPLE_STRUCT_PAT | PATH_EXPR | PATH_PAT => true,
+        PATH_TYPE => {
+            let mut ancestors = parent.ancestors().skip(1);
+            if ancestors.next().is_some_and(|it| it.kind() == ACQUIRES) {
+                return true;
+            }
+            let is_type_arg = parent.parent().is_some_and(|it| it.kind() == TYPE_ARG);
+            let callee_path = parent
+                .ancestors()
+                .nth(4)
+                .and_then(ast::Path::cast)
+                .filter(|it| it.qualifier().is_none());
+            is_type_arg
+                && callee_path.is_some_and(|it| {
+                    it.reference_name().is_some_and(|name| {
+                        matches!(
+                            name.as_str(),
+                            "move_to" | "move_from" | "borrow_global" | "borrow_global_mut" | "exists"
+                        )
+                    })
+                })
+        }
+        _ => false,
+    }
+}
+
+fn usage_kind(name_ref: &ast::NameRef) -> (UsageKind, bool) {
+    let Some(path) = name_ref.syntax().ancestors().nth(2).and_then(ast::Path::cast) else {
+        return (UsageKind::Other, false);
+    };
+    if let Some(use_speck) = path.syntax().parent().and_then(ast::UseSpeck::cast) {
+        let has_alias = use_speck.use_alias().is_some();
+        let kind = if use_speck.parent_use_group().is_some() {
+            UsageKind::GroupUse
+        } else if path.qualifier().is_some() {
+            UsageKind::Use
+        } else {
+            UsageKind::Other
+        };
+        return (kind, has_alias);
+    }
+    // part of the longer path, like `E::V`
+    if path.syntax().parent().is_some_and(|it| it.kind() == PATH) {
+        return (UsageKind::Other, false);
+    }
+    match path.qualifier() {
+        Some(_) => (UsageKind::Qualified, false),
+        None => (UsageKind::Unqualified, false),
+    }
+}
+
+fn items_owner_module(
+    sema: &Semantics<'_, RootDatabase>,
+    items_owner: InFile<ast::AnyHasItems>,
+) -> Option<InFile<ast::Module>> {
+    let (file_id, items_owner) = items_owner.unpack();
+    if let Some(module) = ast::Module::cast(items_owner.syntax().clone()) {
+        return Some(module.in_file(file_id));
+    }
+    let module_spec = ast::ModuleSpec::cast(items_owner.syntax().clone())?;
+    sema.resolve_to_element::<ast::Module>(module_spec.path()?.in_file(file_id))
+}
+
+fn entries_in_scope(
+    db: &RootDatabase,
+    scope: &InFile<SyntaxNode>,
+    ns: NsSet,
+    name: &str,
+) -> Vec<ScopeEntry> {
+    let walk_ctx = WalkScopesCtx {
+        allowed_ns: ns,
+        start_at: scope.clone(),
+        expected_name: Some(name.to_string()),
+    };
+    get_entries_from_walking_scopes(db, walk_ctx)
+}
+
+/// `0x1::m` for `module 0x1::m`.
+fn module_path(module: &ast::Module) -> Option<String> {
+    let address = module.self_or_parent_address_ref()?;
+    let name = module.name()?.as_string();
+    Some(format!("{}::{name}", address.syntax().text()))
+}
+
+fn element_path(element: &ast::NamedElement) -> Option<String> {
+    match element {
+        ast::NamedElement::Module(module) => module_path(module),
+        _ => {
+            let module = element.syntax().containing_module()?;
+            Some(format!(
+                "{}::{}",
+                module_path(&module)?,
+                element.name()?.as_string()
+            ))
+        }
+    }
+}
+
+fn first_non_attr_child(node: &SyntaxNode) -> Option<SyntaxElement> {
+    node.children_with_tokens()
+        .find(|it| !matches!(it.kind(), ATTR | WHITESPACE | COMMENT))
+}
+
+fn apply_text_edits(text: &str, offset: TextSize, mut edits: Vec<(TextRange, String)>) -> String {
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    let mut text = text.to_string();
+    for (range, replace_with) in edits {
+        let range = range - offset;
+        text.replace_range(std::ops::Range::<usize>::from(range), &replace_with);
+    }
+    text
+}
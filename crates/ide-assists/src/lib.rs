@@ -0,0 +1,52 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+//! `assists` crate provides a bunch of code assists, also known as code actions
+//! (in LSP) or intentions (in IntelliJ).
+//!
+//! An assist is a micro-refactoring, which is automatically activated in
+//! certain context. For example, if the cursor is over a public function,
+//! a "Generate unit test" assist becomes available.
+
+mod assist_context;
+mod handlers;
+
+use crate::assist_context::AssistContext;
+use ide_db::RootDatabase;
+use ide_db::assist_config::AssistConfig;
+use ide_db::assist_context::LocalAssists;
+use ide_db::assists::{Assist, AssistResolveStrategy};
+use lang::Semantics;
+use syntax::files::FileRange;
+
+/// Return all the assists applicable at the given position.
+pub fn assists(
+    db: &RootDatabase,
+    config: &AssistConfig,
+    resolve: AssistResolveStrategy,
+    range: FileRange,
+) -> Vec<Assist> {
+    let _p = tracing::info_span!("assists").entered();
+
+    let sema = Semantics::new(db, range.file_id);
+    let ctx = AssistContext::new(sema, config, range);
+    let Some(mut acc) = LocalAssists::new_for_file(range.file_id, ctx.source_file().clone(), resolve)
+    else {
+        return vec![];
+    };
+    for handler in handlers::all() {
+        handler(&mut acc, &ctx);
+    }
+    acc.assists()
+        .into_iter()
+        .filter(|it| {
+            config
+                .allowed
+                .as_ref()
+                .is_none_or(|allowed| allowed.iter().any(|kind| kind.contains(it.id.1)))
+        })
+        .collect()
+}
@@ -8,8 +8,11 @@ use crate::context::CompletionContext;
 use crate::item::{CompletionItem, CompletionItemBuilder, CompletionItemKind};
 use ide_db::RootDatabase;
 
+pub mod attrs;
 pub mod item_list;
+pub mod postfix;
 pub mod reference;
+pub mod spec_items;
 
 /// Represents an in-progress set of completions being built.
 #[derive(Debug, Default)]
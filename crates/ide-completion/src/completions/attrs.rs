@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::completions::Completions;
+use crate::context::CompletionContext;
+use crate::item::CompletionItemKind;
+use ide_db::attrs::{AttrTarget, KNOWN_ATTRIBUTES, find_attr_def};
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+/// Positions inside of the `#[...]` attribute.
+#[derive(Debug)]
+pub(crate) enum AttrItemKind {
+    /// `#[/*caret*/]`
+    Name {
+        target: Option<AttrTarget>,
+        existing_attrs: HashSet<String>,
+    },
+    /// `#[expected_failure(/*caret*/)]`
+    Key {
+        attr_name: String,
+        existing_keys: HashSet<String>,
+        fun_params: Vec<String>,
+    },
+    /// `#[resource_group(scope = /*caret*/)]`
+    Value { attr_name: String, key_name: String },
+}
+
+pub(crate) fn complete_attr_item(
+    acc: &RefCell<Completions>,
+    ctx: &CompletionContext,
+    kind: &AttrItemKind,
+) {
+    let _p = tracing::info_span!("complete_attr_item", ?kind).entered();
+    match kind {
+        AttrItemKind::Name { target, existing_attrs } => {
+            let mut acc = acc.borrow_mut();
+            for attr_def in KNOWN_ATTRIBUTES {
+                if existing_attrs.contains(attr_def.name) {
+                    continue;
+                }
+                if target.is_some_and(|target| !attr_def.is_applicable_to(target)) {
+                    continue;
+                }
+                let mut item = ctx.new_item(CompletionItemKind::Keyword, attr_def.name);
+                if !attr_def.keys.is_empty() {
+                    item.insert_snippet(format!("{}($0)", attr_def.name));
+                }
+                acc.add(item.build(ctx.db));
+            }
+        }
+        AttrItemKind::Key {
+            attr_name,
+            existing_keys,
+            fun_params,
+        } => {
+            let keys: Vec<&str> = if attr_name == "test" {
+                // `#[test(account = @0x1)]` assigns addresses to the signer parameters
+                fun_params.iter().map(|it| it.as_str()).collect()
+            } else {
+                let Some(attr_def) = find_attr_def(attr_name) else {
+                    return;
+                };
+                attr_def.keys.iter().map(|it| it.name).collect()
+            };
+            let mut acc = acc.borrow_mut();
+            for key in keys {
+                if existing_keys.contains(key) {
+                    continue;
+                }
+                let mut item = ctx.new_item(CompletionItemKind::Keyword, key);
+                item.insert_snippet(format!("{key} = $0"));
+                acc.add(item.build(ctx.db));
+            }
+        }
+        AttrItemKind::Value { attr_name, key_name } => {
+            let Some(attr_key) = find_attr_def(attr_name).and_then(|it| it.key(key_name)) else {
+                return;
+            };
+            let mut acc = acc.borrow_mut();
+            for value in attr_key.values {
+                acc.add(ctx.new_item(CompletionItemKind::Keyword, *value).build(ctx.db));
+            }
+        }
+    }
+}
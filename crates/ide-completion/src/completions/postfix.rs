@@ -0,0 +1,185 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+use crate::completions::Completions;
+use crate::context::CompletionContext;
+use crate::item::{CompletionItem, CompletionItemKind};
+use ide_db::text_edit::TextEdit;
+use lang::item_scope::ItemScope;
+use lang::nameres::name_resolution::{WalkScopesCtx, get_entries_from_walking_scopes};
+use lang::nameres::namespaces::MODULES;
+use lang::types::ty::Ty;
+use lang::types::ty::adt::TyAdt;
+use lang::types::ty::integer::IntegerKind;
+use std::cell::RefCell;
+use syntax::files::{InFile, InFileExt};
+use syntax::{AstNode, TextRange, ast};
+
+/// Completions like `cond.if` which wrap the receiver expression into a template.
+pub(crate) fn add_postfix_completions(
+    completions: &RefCell<Completions>,
+    ctx: &CompletionContext<'_>,
+    receiver_expr: InFile<ast::Expr>,
+) -> Option<()> {
+    if !ctx.config.enable_postfix_completions || ctx.msl {
+        return None;
+    }
+    let receiver_ty = ctx.sema.get_expr_type(&receiver_expr).unwrap_or(Ty::Unknown);
+
+    let receiver = receiver_expr.value;
+    let receiver_text = receiver.syntax().text().to_string();
+    // `(a == b).if` should not produce `if ((a == b))`
+    let cond_text = match &receiver {
+        ast::Expr::ParenExpr(paren_expr) => paren_expr
+            .expr()
+            .map(|it| it.syntax().text().to_string())
+            .unwrap_or_else(|| receiver_text.clone()),
+        _ => receiver_text.clone(),
+    };
+    let delete_range = TextRange::new(receiver.syntax().text_range().start(), ctx.source_range().end());
+
+    // `option::` templates import the module, unless it's in scope already
+    let option_import = (!is_option_module_in_scope(ctx))
+        .then(|| ("std::option".to_string(), ctx.original_token_item_scope()));
+
+    let acc = &mut completions.borrow_mut();
+    let mut add_postfix_item =
+        |label: &str, detail: &str, snippet: String, import_to_add: Option<(String, ItemScope)>| {
+            let mut item = CompletionItem::new(CompletionItemKind::Keyword, ctx.source_range(), label);
+            item.detail(detail);
+            if let Some((import_to_add, item_scope)) = import_to_add {
+                item.add_import(import_to_add, item_scope);
+            }
+            match ctx.config.allow_snippets {
+                Some(_) => {
+                    item.insert_snippet(&snippet);
+                    item.text_edit(TextEdit::replace(delete_range, snippet));
+                }
+                None => {
+                    let text = snippet.replace("$0", "");
+                    item.insert_text(&text);
+                    item.text_edit(TextEdit::replace(delete_range, text));
+                }
+            }
+            acc.add(item.build(ctx.db));
+        };
+    let mut add_postfix =
+        |label: &str, detail: &str, snippet: String| add_postfix_item(label, detail, snippet, None);
+
+    if receiver_ty == Ty::Bool {
+        add_postfix("if", "if (expr) {}", format!("if ({cond_text}) {{\n    $0\n}}"));
+        add_postfix("not", "!expr", format!("!{receiver_text}"));
+        add_postfix(
+            "assert",
+            "assert!(expr, code)",
+            format!("assert!({cond_text}, $0)"),
+        );
+    }
+    // abort codes are `u64`
+    if matches!(receiver_ty, Ty::Integer(IntegerKind::U64 | IntegerKind::Integer)) {
+        add_postfix("abort", "abort expr", format!("abort {receiver_text}"));
+    }
+    match &receiver_ty {
+        Ty::Reference(_) => {
+            add_postfix("deref", "*expr", format!("*{receiver_text}"));
+        }
+        _ => {
+            add_postfix("ref", "&expr", format!("&{receiver_text}"));
+            add_postfix("refm", "&mut expr", format!("&mut {receiver_text}"));
+        }
+    }
+    add_postfix("let", "let name = expr;", format!("let $0 = {receiver_text};"));
+
+    let unwrapped_ty = receiver_ty.unwrap_all_refs();
+    if let Some(ty_adt) = unwrapped_ty.clone().into_ty_adt()
+        && let Some(arms) = enum_match_arms(ctx, &ty_adt)
+    {
+        add_postfix(
+            "match",
+            "match (expr) {}",
+            format!("match ({cond_text}) {{\n{arms}\n}}"),
+        );
+    }
+    add_postfix("vec", "vector[expr]", format!("vector[{receiver_text}]"));
+
+    match unwrapped_ty.into_ty_adt() {
+        Some(ty_adt) if is_option(ctx, &ty_adt) => {
+            let receiver_ref = match &receiver_ty {
+                Ty::Reference(_) => receiver_text.clone(),
+                _ => format!("&{receiver_text}"),
+            };
+            add_postfix_item(
+                "some",
+                "option::is_some(&expr)",
+                format!("option::is_some({receiver_ref})"),
+                option_import.clone(),
+            );
+            add_postfix_item(
+                "none",
+                "option::is_none(&expr)",
+                format!("option::is_none({receiver_ref})"),
+                option_import,
+            );
+        }
+        _ => {
+            add_postfix_item(
+                "some",
+                "option::some(expr)",
+                format!("option::some({receiver_text})"),
+                option_import,
+            );
+        }
+    }
+
+    Some(())
+}
+
+fn enum_match_arms(ctx: &CompletionContext<'_>, ty_adt: &TyAdt) -> Option<String> {
+    let ast::StructOrEnum::Enum(enum_) = ty_adt.adt_item(ctx.db)?.value else {
+        return None;
+    };
+    let enum_name = enum_.name()?.as_string();
+    let mut arms = vec![];
+    for (i, variant) in enum_.variants().into_iter().enumerate() {
+        let variant_name = variant.name()?.as_string();
+        let fields = match variant.field_list() {
+            Some(ast::FieldList::NamedFieldList(_)) => " { .. }",
+            Some(ast::FieldList::TupleFieldList(_)) => "(..)",
+            None => "",
+        };
+        let body = if i == 0 { "{$0}" } else { "{}" };
+        arms.push(format!("    {enum_name}::{variant_name}{fields} => {body},"));
+    }
+    if arms.is_empty() {
+        return None;
+    }
+    Some(arms.join("\n"))
+}
+
+fn is_option_module_in_scope(ctx: &CompletionContext<'_>) -> bool {
+    let Some(start_at) = ctx.original_token.parent() else {
+        return false;
+    };
+    let walk_ctx = WalkScopesCtx {
+        allowed_ns: MODULES,
+        start_at: start_at.in_file(ctx.position.file_id),
+        expected_name: Some("option".to_string()),
+    };
+    !get_entries_from_walking_scopes(ctx.db, walk_ctx).is_empty()
+}
+
+fn is_option(ctx: &CompletionContext<'_>, ty_adt: &TyAdt) -> bool {
+    let Some(adt_item) = ty_adt.adt_item(ctx.db) else {
+        return false;
+    };
+    let is_option_name = adt_item.value.name().is_some_and(|it| it.as_string() == "Option");
+    let is_option_module = adt_item
+        .value
+        .module()
+        .name()
+        .is_some_and(|it| it.as_string() == "option");
+    is_option_name && is_option_module
+}
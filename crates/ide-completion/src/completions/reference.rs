@@ -7,8 +7,10 @@
 mod labels;
 mod method_or_field;
 pub(crate) mod paths;
+mod smart_constructors;
 
 use crate::completions::Completions;
+use crate::completions::postfix::add_postfix_completions;
 use crate::completions::reference::labels::add_label_completions;
 use crate::completions::reference::method_or_field::add_method_or_field_completions;
 use crate::completions::reference::paths::add_path_completions;
@@ -31,7 +33,9 @@ pub(crate) fn add_reference_completions(
     match reference_kind {
         ReferenceKind::Path { fake_path } => add_path_completions(completions, ctx, fake_path),
         ReferenceKind::DotExpr { original_receiver_expr } => {
-            add_method_or_field_completions(completions, ctx, original_receiver_expr.in_file(file_id))
+            let receiver_expr = original_receiver_expr.in_file(file_id);
+            add_method_or_field_completions(completions, ctx, receiver_expr.clone());
+            add_postfix_completions(completions, ctx, receiver_expr)
         }
         ReferenceKind::Label { fake_label, source_range } => {
             add_label_completions(completions, ctx, fake_label, source_range)
@@ -45,6 +49,9 @@ pub(crate) fn add_reference_completions(
         ReferenceKind::StructPatField { original_struct_pat } => {
             add_struct_pat_fields_completions(completions, ctx, original_struct_pat.in_file(file_id))
         }
+        ReferenceKind::SchemaLitField { original_schema_lit } => {
+            add_schema_lit_fields_completions(completions, ctx, original_schema_lit.in_file(file_id))
+        }
     }
 }
 
@@ -132,3 +139,36 @@ fn add_struct_pat_fields_completions(
     }
     Some(())
 }
+
+fn add_schema_lit_fields_completions(
+    completions: &RefCell<Completions>,
+    ctx: &CompletionContext<'_>,
+    original_schema_lit: InFile<ast::SchemaLit>,
+) -> Option<()> {
+    let acc = &mut completions.borrow_mut();
+
+    let provided_field_names = original_schema_lit
+        .value
+        .fields()
+        .iter()
+        // do not account for the current field
+        .filter(|it| !it.syntax().text_range().contains_inclusive(ctx.original_offset()))
+        .filter_map(|it| it.field_name())
+        .collect::<HashSet<_>>();
+
+    let schema = ctx
+        .sema
+        .resolve_to_element::<ast::Schema>(original_schema_lit.and_then(|it| it.path())?)?;
+    for schema_field in schema.flat_map(|it| it.schema_fields()) {
+        let Some(field_name) = schema_field.value.ident_pat().and_then(|it| it.name()) else {
+            continue;
+        };
+        let field_name = field_name.as_string();
+        if provided_field_names.contains(&field_name) {
+            continue;
+        }
+        let item = render_type_owner(ctx, &field_name, schema_field.map_into());
+        acc.add(item.build(ctx.db));
+    }
+    Some(())
+}
@@ -5,6 +5,7 @@
 // Modifications have been made to the original code.
 
 use crate::completions::Completions;
+use crate::completions::reference::smart_constructors::add_smart_constructors;
 use crate::context::CompletionContext;
 use crate::item::{CompletionItem, CompletionItemBuilder, CompletionItemKind, CompletionRelevance};
 use crate::render::function::{FunctionKind, render_function};
@@ -134,6 +135,8 @@ pub(crate) fn add_path_completions(
         }
     }
 
+    add_smart_constructors(acc, ctx, &path_ctx);
+
     Some(())
 }
 
@@ -0,0 +1,241 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::completions::Completions;
+use crate::completions::reference::paths::{PathCompletionCtx, PathKind};
+use crate::context::CompletionContext;
+use crate::item::{
+    CompletionItem, CompletionItemKind, CompletionRelevance, CompletionRelevanceTypeMatch,
+};
+use crate::render::function::{FunctionKind, render_function};
+use ide_db::SymbolKind;
+use lang::loc::SyntaxLocFileExt;
+use lang::nameres::fq_named_element::ItemFQNameOwner;
+use lang::nameres::is_visible::is_visible_in_context;
+use lang::nameres::name_resolution::{WalkScopesCtx, get_entries_from_walking_scopes};
+use lang::nameres::namespaces::MODULES;
+use lang::nameres::scope::ScopeEntryExt;
+use lang::types::has_type_params_ext::GenericItemExt;
+use lang::types::inference::{InferenceCtx, TyVarIndex};
+use lang::types::substitution::ApplySubstitution;
+use lang::types::ty::Ty;
+use lang::types::ty_db;
+use std::collections::HashSet;
+use syntax::ast::HasItems;
+use syntax::files::{InFile, InFileExt};
+use syntax::{AstNode, ast};
+
+/// Completes constructors of the expected type, like struct literals, enum variants
+/// and functions returning the type.
+pub(crate) fn add_smart_constructors(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    path_ctx: &PathCompletionCtx,
+) -> Option<()> {
+    if path_ctx.has_qualifier || path_ctx.path_kind != PathKind::Expr || path_ctx.is_msl() {
+        return None;
+    }
+    let expected_ty = ctx.expected_type.clone()?;
+    let ty_adt = expected_ty.clone().into_ty_adt()?;
+    let adt_item = ty_adt.adt_item(ctx.db)?;
+    let adt_module = adt_item.value.module();
+    let adt_name = match &adt_item.value {
+        ast::StructOrEnum::Struct(struct_) => struct_.name()?.as_string(),
+        ast::StructOrEnum::Enum(enum_) => enum_.name()?.as_string(),
+    };
+
+    // struct and enum values can only be constructed in the declaring module
+    if ctx.containing_module() == Some(adt_module.clone()) {
+        match &adt_item.value {
+            ast::StructOrEnum::Struct(struct_) => {
+                add_constructor(acc, ctx, &adt_name, SymbolKind::Struct, struct_.field_list());
+            }
+            ast::StructOrEnum::Enum(enum_) => {
+                for variant in enum_.variants() {
+                    let Some(variant_name) = variant.name() else {
+                        continue;
+                    };
+                    let name = format!("{adt_name}::{}", variant_name.as_string());
+                    add_constructor(acc, ctx, &name, SymbolKind::EnumVariant, variant.field_list());
+                }
+            }
+        }
+    }
+
+    // functions of the current module are already in scope
+    let current_module_loc = ctx
+        .containing_module()
+        .map(|it| it.in_file(ctx.position.file_id).loc());
+    let mut candidate_modules = modules_in_scope(ctx)
+        .into_iter()
+        .map(|(module_name, module)| (module_name, module, None))
+        .collect::<Vec<_>>();
+    // modules out of scope are imported along with the completion
+    let adt_module = adt_module.in_file(adt_item.file_id);
+    if !candidate_modules
+        .iter()
+        .any(|(_, module, _)| module.loc() == adt_module.loc())
+    {
+        let module_fq_name = adt_module.fq_name(ctx.db)?;
+        candidate_modules.push((
+            module_fq_name.name(),
+            adt_module,
+            Some(module_fq_name.fq_identifier_text()),
+        ));
+    }
+    for (module_qualifier, module, module_import) in candidate_modules {
+        if Some(module.loc()) == current_module_loc {
+            continue;
+        }
+        add_function_constructors(
+            acc,
+            ctx,
+            &expected_ty,
+            &adt_name,
+            &module_qualifier,
+            module,
+            module_import,
+        );
+    }
+
+    Some(())
+}
+
+fn add_function_constructors(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    expected_ty: &Ty,
+    adt_name: &str,
+    module_qualifier: &str,
+    module: InFile<ast::Module>,
+    module_import: Option<String>,
+) {
+    let original_token_ctx = InFile::new(ctx.position.file_id, ctx.original_token.clone());
+    for fun in module.value.non_test_functions() {
+        // cheap check to avoid lowering signatures of the unrelated functions
+        if !returns_type_named(&fun, adt_name) {
+            continue;
+        }
+        let fun = fun.in_file(module.file_id);
+        let Some(fun_entry) = fun.clone().to_entry() else {
+            continue;
+        };
+        if is_visible_in_context(ctx.db, &fun_entry, original_token_ctx.clone()).is_some() {
+            continue;
+        }
+
+        let subst = fun.ty_vars_subst(&TyVarIndex::default());
+        let ret_ty = ty_db::lower_function(ctx.db, fun.clone(), ctx.msl)
+            .substitute(&subst)
+            .ret_type_ty();
+        let mut inference_ctx = InferenceCtx::new(ctx.db, fun.file_id, ctx.msl);
+        if inference_ctx.combine_types(expected_ty.clone(), ret_ty).is_err() {
+            continue;
+        }
+        let apply_subst = inference_ctx.fully_resolve_vars_fallback_to_origin(subst);
+
+        let fun_name = fun_entry.name.as_str();
+        let has_params = !fun.value.params().is_empty();
+        let mut item = render_function(
+            ctx,
+            false,
+            false,
+            fun_name,
+            fun.map_into(),
+            FunctionKind::Fun,
+            Some(apply_subst),
+        );
+        let qualified_name = format!("{module_qualifier}::{fun_name}");
+        let label = format!("{module_qualifier}::{}", item.label());
+        item.set_label(label);
+        if ctx.config.allow_snippets.is_some() {
+            let parens = if has_params { "($0)" } else { "()$0" };
+            item.insert_snippet(format!("{qualified_name}{parens}"));
+        }
+        if let Some(module_import) = &module_import {
+            item.add_import(module_import.clone(), ctx.original_token_item_scope());
+            item.with_relevance(|r| CompletionRelevance { is_out_of_scope: true, ..r });
+        }
+        acc.add(item.build(ctx.db));
+    }
+}
+
+fn returns_type_named(fun: &ast::Fun, type_name: &str) -> bool {
+    let Some(ast::Type::PathType(path_type)) = fun.ret_type().and_then(|it| it.type_()) else {
+        return false;
+    };
+    path_type
+        .path()
+        .reference_name()
+        .is_some_and(|it| it == type_name)
+}
+
+fn add_constructor(
+    acc: &mut Completions,
+    ctx: &CompletionContext<'_>,
+    name: &str,
+    kind: SymbolKind,
+    field_list: Option<ast::FieldList>,
+) {
+    let (label, snippet) = match field_list {
+        Some(ast::FieldList::NamedFieldList(named_field_list)) => {
+            let field_names = named_field_list
+                .fields()
+                .map(|it| it.field_name().as_string())
+                .collect::<Vec<_>>();
+            let placeholders = field_names
+                .iter()
+                .enumerate()
+                .map(|(i, field_name)| format!("${{{}:{field_name}}}", i + 1))
+                .collect::<Vec<_>>();
+            (
+                format!("{name} {{ {} }}", field_names.join(", ")),
+                format!("{name} {{ {} }}$0", placeholders.join(", ")),
+            )
+        }
+        Some(ast::FieldList::TupleFieldList(tuple_field_list)) => {
+            let placeholders = (1..=tuple_field_list.fields().count())
+                .map(|i| format!("${{{i}:_}}"))
+                .collect::<Vec<_>>();
+            (
+                format!("{name}(..)"),
+                format!("{name}({})$0", placeholders.join(", ")),
+            )
+        }
+        None => (name.to_string(), format!("{name}$0")),
+    };
+    let mut item = CompletionItem::new(CompletionItemKind::SymbolKind(kind), ctx.source_range(), label);
+    item.lookup_by(name);
+    match ctx.config.allow_snippets {
+        Some(_) => item.insert_snippet(snippet),
+        None => item.insert_text(name),
+    };
+    item.set_relevance(CompletionRelevance {
+        type_match: Some(CompletionRelevanceTypeMatch::Exact),
+        ..CompletionRelevance::default()
+    });
+    acc.add(item.build(ctx.db));
+}
+
+/// Modules available at the completion position with their names, like `coin`
+/// for `use aptos_framework::coin;`.
+fn modules_in_scope(ctx: &CompletionContext<'_>) -> Vec<(String, InFile<ast::Module>)> {
+    let Some(start_at) = ctx
+        .original_token
+        .parent()
+        .map(|it| it.in_file(ctx.position.file_id))
+    else {
+        return vec![];
+    };
+    let walk_ctx = WalkScopesCtx {
+        allowed_ns: MODULES,
+        start_at,
+        expected_name: None,
+    };
+    let mut seen_locs = HashSet::new();
+    get_entries_from_walking_scopes(ctx.db, walk_ctx)
+        .into_iter()
+        .filter(|it| seen_locs.insert(it.node_loc.clone()))
+        .filter_map(|it| Some((it.name.clone(), it.cast_into::<ast::Module>(ctx.db)?)))
+        .collect()
+}
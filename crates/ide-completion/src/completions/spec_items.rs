@@ -0,0 +1,84 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::completions::Completions;
+use crate::context::CompletionContext;
+use crate::item::CompletionItemKind;
+use crate::render::new_named_item;
+use ide_db::defs::{BOOL_SPEC_PRAGMAS, SPEC_PRAGMAS};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use syntax::AstNode;
+use syntax::ast::HasItems;
+
+/// Spec-only positions, which are not references.
+#[derive(Debug)]
+pub(crate) enum SpecItemKind {
+    PragmaName { existing_pragmas: HashSet<String> },
+    PragmaValue { pragma_name: String },
+    ApplyPattern { existing_patterns: HashSet<String> },
+}
+
+pub(crate) fn complete_spec_item(
+    acc: &RefCell<Completions>,
+    ctx: &CompletionContext,
+    kind: &SpecItemKind,
+) {
+    let _p = tracing::info_span!("complete_spec_item", ?kind).entered();
+    match kind {
+        SpecItemKind::PragmaName { existing_pragmas } => {
+            add_pragma_names(acc, ctx, existing_pragmas);
+        }
+        SpecItemKind::PragmaValue { pragma_name } => {
+            if BOOL_SPEC_PRAGMAS.contains(&pragma_name.as_str()) {
+                let mut acc = acc.borrow_mut();
+                acc.add(ctx.new_snippet_keyword("true$0"));
+                acc.add(ctx.new_snippet_keyword("false$0"));
+            }
+        }
+        SpecItemKind::ApplyPattern { existing_patterns } => {
+            add_apply_patterns(acc, ctx, existing_patterns);
+        }
+    }
+}
+
+fn add_pragma_names(
+    acc: &RefCell<Completions>,
+    ctx: &CompletionContext,
+    existing_pragmas: &HashSet<String>,
+) {
+    let mut acc = acc.borrow_mut();
+    for pragma_name in SPEC_PRAGMAS {
+        if existing_pragmas.contains(*pragma_name) {
+            continue;
+        }
+        let mut item = ctx.new_item(CompletionItemKind::Keyword, *pragma_name);
+        if !BOOL_SPEC_PRAGMAS.contains(pragma_name) {
+            item.insert_snippet(format!("{pragma_name} = $0"));
+        }
+        acc.add(item.build(ctx.db));
+    }
+}
+
+fn add_apply_patterns(
+    acc: &RefCell<Completions>,
+    ctx: &CompletionContext,
+    existing_patterns: &HashSet<String>,
+) -> Option<()> {
+    let module = ctx.containing_module()?;
+
+    let mut acc = acc.borrow_mut();
+    for fun in module.non_test_functions() {
+        let Some(fun_name) = fun.name().map(|it| it.as_string()) else {
+            continue;
+        };
+        if existing_patterns.contains(&fun_name) {
+            continue;
+        }
+        acc.add(new_named_item(ctx, &fun_name, fun.syntax().kind()).build(ctx.db));
+    }
+    if !existing_patterns.contains("*") {
+        acc.add(ctx.new_item(CompletionItemKind::Keyword, "*").build(ctx.db));
+    }
+    Some(())
+}
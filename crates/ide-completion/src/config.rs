@@ -10,4 +10,5 @@ use ide_db::AllowSnippets;
 pub struct CompletionConfig {
     pub allow_snippets: Option<AllowSnippets>,
     pub enable_imports_on_the_fly: bool,
+    pub enable_postfix_completions: bool,
 }
@@ -6,7 +6,9 @@
 
 mod analysis;
 
+use crate::completions::attrs::AttrItemKind;
 use crate::completions::item_list::ItemListKind;
+use crate::completions::spec_items::SpecItemKind;
 use crate::config::CompletionConfig;
 use crate::context::analysis::{AnalysisResult, completion_analysis};
 use crate::item::{CompletionItem, CompletionItemBuilder, CompletionItemKind};
@@ -32,6 +34,8 @@ pub(crate) enum CompletionAnalysis {
     Item(ItemListKind),
     Reference(ReferenceKind),
     TypeParam,
+    SpecItem(SpecItemKind),
+    AttrItem(AttrItemKind),
 }
 
 #[derive(Debug)]
@@ -55,6 +59,9 @@ pub enum ReferenceKind {
     StructPatField {
         original_struct_pat: ast::StructPat,
     },
+    SchemaLitField {
+        original_schema_lit: ast::SchemaLit,
+    },
 }
 
 /// `CompletionContext` is created early during completion to figure out, where
@@ -99,7 +106,11 @@ impl CompletionContext<'_> {
     /// The range of the identifier that is being completed.
     pub(crate) fn source_range(&self) -> TextRange {
         let kind = self.original_token.kind();
-        if matches!(kind, UNDERSCORE | INT_NUMBER | IDENT | QUOTE_IDENT) || kind.is_keyword() {
+        if matches!(
+            kind,
+            UNDERSCORE | INT_NUMBER | IDENT | QUOTE_IDENT | WILDCARD_IDENT
+        ) || kind.is_keyword()
+        {
             // completion might be called in the middle of the token,
             // this way we can't use just `self.original_token.text_range()`
             TextRange::new(self.original_token.text_range().start(), self.original_offset())
@@ -4,9 +4,13 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
+use crate::completions::attrs::AttrItemKind;
 use crate::completions::item_list::ItemListKind;
+use crate::completions::spec_items::SpecItemKind;
 use crate::context::{COMPLETION_MARKER, CompletionAnalysis, ReferenceKind};
 use ide_db::active_parameter::ActiveParameterInfo;
+use ide_db::attrs::{AttrTarget, find_attr_def};
+use ide_db::defs::BOOL_SPEC_PRAGMAS;
 use ide_db::{RootDatabase, active_parameter};
 use lang::Semantics;
 use lang::types::ty::Ty;
@@ -32,6 +36,18 @@ pub(crate) fn completion_analysis(
     // as we insert after the offset, right biased will *always* pick the identifier no matter
     // if there is an ident already typed or not
     let fake_token = fake_file.token_at_offset(original_offset).right_biased()?;
+    if let Some(spec_item_kind) = analyze_spec_item(&fake_token) {
+        return Some(AnalysisResult {
+            analysis: CompletionAnalysis::SpecItem(spec_item_kind),
+            expected,
+        });
+    }
+    if let Some(attr_item_kind) = analyze_attr_item(&fake_token) {
+        return Some(AnalysisResult {
+            analysis: CompletionAnalysis::AttrItem(attr_item_kind),
+            expected,
+        });
+    }
     if let Some(fake_ref) = fake_token
         .parent_ancestors()
         .find_map(ast::ReferenceElement::cast)
@@ -118,6 +134,112 @@ pub(crate) fn completion_analysis(
     })
 }
 
+fn analyze_spec_item(fake_token: &SyntaxToken) -> Option<SpecItemKind> {
+    let fake_parent = fake_token.parent()?;
+    if let Some(fake_attr_item) = fake_parent.ancestors().find_map(ast::PragmaAttrItem::cast) {
+        let pragma_name = fake_attr_item.ident_token()?;
+        if &pragma_name == fake_token {
+            let fake_pragma_stmt = fake_attr_item.syntax().parent_of_type::<ast::PragmaStmt>()?;
+            let existing_pragmas = fake_pragma_stmt
+                .attr_items()
+                .filter(|it| it != &fake_attr_item)
+                .filter_map(|it| it.ident_token())
+                .map(|it| it.text().to_string())
+                .collect();
+            return Some(SpecItemKind::PragmaName { existing_pragmas });
+        }
+        // other pragmas accept paths, like `pragma friend = 0x1::m::f`
+        if BOOL_SPEC_PRAGMAS.contains(&pragma_name.text()) {
+            let pragma_name = pragma_name.text().to_string();
+            return Some(SpecItemKind::PragmaValue { pragma_name });
+        }
+        return None;
+    }
+    if fake_token.kind() == WILDCARD_IDENT
+        && let Some(fake_apply_schema) = fake_parent.ancestors().find_map(ast::ApplySchema::cast)
+    {
+        let existing_patterns = fake_apply_schema
+            .apply_to_patterns()
+            .into_iter()
+            .chain(fake_apply_schema.apply_except_patterns())
+            .filter(|it| it.syntax() != &fake_parent)
+            .filter_map(|it| it.wildcard_ident_token())
+            .map(|it| it.text().to_string())
+            .collect();
+        return Some(SpecItemKind::ApplyPattern { existing_patterns });
+    }
+    None
+}
+
+fn analyze_attr_item(fake_token: &SyntaxToken) -> Option<AttrItemKind> {
+    let fake_path = fake_token.parent()?.ancestors().find_map(ast::Path::cast)?;
+    let fake_root_path = fake_path.root_path();
+
+    // `#[resource_group(scope = /*caret*/)]`
+    if let Some(fake_path_expr) = fake_root_path.path_expr()
+        && let Some(fake_initializer) = fake_path_expr.syntax().parent_of_type::<ast::Initializer>()
+    {
+        let fake_key_item = fake_initializer.syntax().parent_of_type::<ast::AttrItem>()?;
+        let attr_name = fake_key_item.parent_attr_item()?.path_text()?;
+        let key_name = fake_key_item.path_text()?;
+        // other keys accept arbitrary expressions, like `abort_code = E_NOT_FOUND`
+        let has_fixed_values = find_attr_def(&attr_name)
+            .and_then(|it| it.key(&key_name))
+            .is_some_and(|it| !it.values.is_empty());
+        if !has_fixed_values {
+            return None;
+        }
+        return Some(AttrItemKind::Value { attr_name, key_name });
+    }
+
+    let fake_attr_item = fake_root_path.syntax().parent_of_type::<ast::AttrItem>()?;
+    if fake_path.qualifier().is_some() {
+        return None;
+    }
+    if let Some(fake_attr) = fake_attr_item.attr() {
+        let owner = fake_attr.syntax().parent()?;
+        let existing_attrs = owner
+            .children()
+            .filter_map(ast::Attr::cast)
+            .flat_map(|it| it.attr_items())
+            .filter(|it| it != &fake_attr_item)
+            .filter_map(|it| it.path_text())
+            .collect();
+        return Some(AttrItemKind::Name {
+            target: AttrTarget::for_owner(&owner),
+            existing_attrs,
+        });
+    }
+
+    let fake_parent_item = fake_attr_item.parent_attr_item()?;
+    if !fake_parent_item.is_top_level() {
+        return None;
+    }
+    let attr_name = fake_parent_item.path_text()?;
+    let existing_keys = fake_attr_item
+        .parent_attr_item_list()?
+        .attr_items()
+        .filter(|it| it != &fake_attr_item)
+        .filter_map(|it| it.path_text())
+        .collect();
+    let fun_params = fake_parent_item
+        .attr()
+        .and_then(|it| it.syntax().parent_of_type::<ast::Fun>())
+        .map(|fun| {
+            fun.params_as_bindings()
+                .into_iter()
+                .filter_map(|it| it.name())
+                .map(|it| it.as_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(AttrItemKind::Key {
+        attr_name,
+        existing_keys,
+        fun_params,
+    })
+}
+
 fn analyze_ref(
     fake_ref: &ast::ReferenceElement,
     original_file: &ast::SourceFile,
@@ -179,6 +301,18 @@ fn analyze_path(fake_path: &ast::Path, original_file: &ast::SourceFile) -> Optio
         return Some(ReferenceKind::StructLitField { original_struct_lit });
     }
 
+    // check for schema lit field
+    if let Some(fake_path_expr) = fake_path.root_path().path_expr()
+        && let Some(fake_schema_lit_field) =
+        fake_path_expr.syntax().parent_of_type::<ast::SchemaLitField>()
+        // include S { val/*caret*/ }
+        && fake_schema_lit_field.name_ref().is_none()
+    {
+        let fake_schema_lit = fake_schema_lit_field.schema_lit()?;
+        let original_schema_lit = original_file.find_original_node(fake_schema_lit)?;
+        return Some(ReferenceKind::SchemaLitField { original_schema_lit });
+    }
+
     Some(ReferenceKind::Path { fake_path: fake_path.clone() })
 }
 
@@ -322,6 +322,9 @@ impl CompletionItemBuilder {
         self.lookup = Some(lookup.into());
         self
     }
+    pub(crate) fn label(&self) -> &str {
+        &self.label
+    }
     pub(crate) fn set_label(&mut self, label: impl Into<String>) -> &mut CompletionItemBuilder {
         self.label = label.into();
         self
@@ -6,7 +6,7 @@
 
 #![allow(dead_code)]
 
-use crate::completions::{Completions, item_list, reference};
+use crate::completions::{Completions, attrs, item_list, reference, spec_items};
 use crate::config::CompletionConfig;
 use crate::context::{CompletionAnalysis, CompletionContext};
 use crate::item::CompletionItem;
@@ -43,6 +43,12 @@ pub fn completions(
             CompletionAnalysis::Reference(reference_kind) => {
                 reference::add_reference_completions(&completions, &ctx, reference_kind);
             }
+            CompletionAnalysis::SpecItem(spec_item_kind) => {
+                spec_items::complete_spec_item(&completions, &ctx, &spec_item_kind);
+            }
+            CompletionAnalysis::AttrItem(attr_item_kind) => {
+                attrs::complete_attr_item(&completions, &ctx, &attr_item_kind);
+            }
             CompletionAnalysis::TypeParam => {
                 let generic_item = ctx
                     .original_file()?
@@ -50,7 +50,7 @@ pub(crate) fn item_to_kind(kind: SyntaxKind) -> CompletionItemKind {
         IDENT_PAT => CompletionItemKind::SymbolKind(SymbolKind::Local),
         LABEL => CompletionItemKind::SymbolKind(SymbolKind::Label),
         TYPE_PARAM => CompletionItemKind::SymbolKind(SymbolKind::TypeParam),
-        NAMED_FIELD => CompletionItemKind::SymbolKind(SymbolKind::Field),
+        NAMED_FIELD | SCHEMA_FIELD => CompletionItemKind::SymbolKind(SymbolKind::Field),
         VARIANT => CompletionItemKind::SymbolKind(SymbolKind::EnumVariant),
         SCHEMA => CompletionItemKind::SymbolKind(SymbolKind::Schema),
         GLOBAL_VARIABLE_DECL => CompletionItemKind::SymbolKind(SymbolKind::GlobalVariableDecl),
@@ -13,7 +13,6 @@ use syntax::syntax_editor::SyntaxEditor;
 use syntax::{AstNode, SyntaxNode, TextRange, ast};
 use vfs::FileId;
 
-// todo: use Assists from rust-analyzer if we ever need multi-file assists
 pub struct LocalAssists {
     file_id: FileId,
     source_file: ast::SourceFile,
@@ -69,13 +68,38 @@ impl LocalAssists {
         target: TextRange,
         f: impl FnOnce(&mut SyntaxEditor) -> Option<()>,
     ) -> Option<()> {
-        let id = AssistId::quick_fix(id);
+        self.add(AssistId::quick_fix(id), label, target, f)
+    }
+
+    pub fn add(
+        &mut self,
+        id: AssistId,
+        label: impl Into<String>,
+        target: TextRange,
+        f: impl FnOnce(&mut SyntaxEditor) -> Option<()>,
+    ) -> Option<()> {
+        let file_id = self.file_id;
+        let source_file = self.source_file.clone();
+        self.add_source_change(id, label, target, |builder| {
+            let mut editor = builder.make_editor(source_file.syntax());
+            f(&mut editor)?;
+            builder.add_file_edits(file_id, editor);
+            Some(())
+        })
+    }
+
+    /// Adds an assist which might edit other files besides the current one.
+    pub fn add_source_change(
+        &mut self,
+        id: AssistId,
+        label: impl Into<String>,
+        target: TextRange,
+        f: impl FnOnce(&mut SourceChangeBuilder) -> Option<()>,
+    ) -> Option<()> {
         let label = label.into();
         let source_change = if self.resolve.should_resolve(&id) {
             let mut builder = SourceChangeBuilder::new(self.file_id);
-            let mut editor = builder.make_editor(self.source_file.syntax());
-            f(&mut editor)?;
-            builder.add_file_edits(self.file_id, editor);
+            f(&mut builder)?;
             Some(builder.finish())
         } else {
             None
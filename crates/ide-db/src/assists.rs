@@ -111,6 +111,14 @@ impl AssistId {
     pub fn quick_fix(id: &'static str) -> AssistId {
         AssistId(id, AssistKind::QuickFix, None)
     }
+
+    pub fn generate(id: &'static str) -> AssistId {
+        AssistId(id, AssistKind::Generate, None)
+    }
+
+    pub fn refactor(id: &'static str) -> AssistId {
+        AssistId(id, AssistKind::Refactor, None)
+    }
 }
 
 /// A way to control how many assist to resolve during the assist resolution.
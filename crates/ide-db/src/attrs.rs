@@ -0,0 +1,187 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use syntax::SyntaxKind::*;
+use syntax::{AstNode, SyntaxNode, ast};
+
+/// Kind of item an attribute can be attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrTarget {
+    Module,
+    Fun,
+    Struct,
+    Enum,
+    Const,
+    Use,
+}
+
+impl AttrTarget {
+    pub fn for_attr(attr: &ast::Attr) -> Option<AttrTarget> {
+        Self::for_owner(&attr.syntax().parent()?)
+    }
+
+    pub fn for_owner(owner: &SyntaxNode) -> Option<AttrTarget> {
+        let target = match owner.kind() {
+            MODULE | SCRIPT => AttrTarget::Module,
+            FUN => AttrTarget::Fun,
+            STRUCT => AttrTarget::Struct,
+            ENUM => AttrTarget::Enum,
+            CONST => AttrTarget::Const,
+            USE_STMT => AttrTarget::Use,
+            _ => return None,
+        };
+        Some(target)
+    }
+}
+
+const ANY_ITEM: &[AttrTarget] = &[
+    AttrTarget::Module,
+    AttrTarget::Fun,
+    AttrTarget::Struct,
+    AttrTarget::Enum,
+    AttrTarget::Const,
+    AttrTarget::Use,
+];
+
+/// Nested argument of an attribute, like `abort_code` in `#[expected_failure(abort_code = 1)]`.
+#[derive(Debug)]
+pub struct AttrKey {
+    pub name: &'static str,
+    /// Accepted values, if the key only accepts a fixed set of identifiers.
+    pub values: &'static [&'static str],
+}
+
+impl AttrKey {
+    const fn new(name: &'static str) -> Self {
+        AttrKey { name, values: &[] }
+    }
+}
+
+#[derive(Debug)]
+pub struct AttrDef {
+    pub name: &'static str,
+    pub targets: &'static [AttrTarget],
+    pub keys: &'static [AttrKey],
+}
+
+impl AttrDef {
+    pub fn is_applicable_to(&self, target: AttrTarget) -> bool {
+        self.targets.contains(&target)
+    }
+
+    pub fn key(&self, name: &str) -> Option<&'static AttrKey> {
+        self.keys.iter().find(|it| it.name == name)
+    }
+}
+
+/// Attributes recognized by the Aptos Move compiler.
+///
+/// Keys of `#[test(...)]` are the names of the signer parameters of the test function,
+/// so they're not listed here.
+pub const KNOWN_ATTRIBUTES: &[AttrDef] = &[
+    AttrDef {
+        name: "test",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "test_only",
+        targets: ANY_ITEM,
+        keys: &[],
+    },
+    AttrDef {
+        name: "verify_only",
+        targets: ANY_ITEM,
+        keys: &[],
+    },
+    AttrDef {
+        name: "expected_failure",
+        targets: &[AttrTarget::Fun],
+        keys: &[
+            AttrKey::new("abort_code"),
+            AttrKey::new("arithmetic_error"),
+            AttrKey::new("vector_error"),
+            AttrKey::new("out_of_gas"),
+            AttrKey::new("major_status"),
+            AttrKey::new("minor_status"),
+            AttrKey::new("location"),
+        ],
+    },
+    AttrDef {
+        name: "view",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "event",
+        targets: &[AttrTarget::Struct, AttrTarget::Enum],
+        keys: &[],
+    },
+    AttrDef {
+        name: "resource_group",
+        targets: &[AttrTarget::Struct],
+        keys: &[AttrKey {
+            name: "scope",
+            values: &["global", "address", "module_"],
+        }],
+    },
+    AttrDef {
+        name: "resource_group_member",
+        targets: &[AttrTarget::Struct, AttrTarget::Enum],
+        keys: &[AttrKey::new("group")],
+    },
+    AttrDef {
+        name: "randomness",
+        targets: &[AttrTarget::Fun],
+        keys: &[AttrKey::new("max_gas")],
+    },
+    AttrDef {
+        name: "persistent",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "module_lock",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "deprecated",
+        targets: ANY_ITEM,
+        keys: &[],
+    },
+    AttrDef {
+        name: "legacy_entry_fun",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "bytecode_instruction",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "native_interface",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "lint::skip",
+        targets: &[AttrTarget::Module, AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "lint::allow_unsafe_randomness",
+        targets: &[AttrTarget::Fun],
+        keys: &[],
+    },
+    AttrDef {
+        name: "fmt::skip",
+        targets: ANY_ITEM,
+        keys: &[],
+    },
+];
+
+pub fn find_attr_def(name: &str) -> Option<&'static AttrDef> {
+    KNOWN_ATTRIBUTES.iter().find(|it| it.name == name)
+}
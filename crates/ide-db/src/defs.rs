@@ -34,6 +34,46 @@ pub static BUILTIN_MUT_RESOURCE_FUNCTIONS: LazyLock<HashSet<&str>> = LazyLock::n
     ])
 });
 
+/// Pragmas recognized by the Move Prover.
+pub const SPEC_PRAGMAS: &[&str] = &[
+    "verify",
+    "verify_duration_estimate",
+    "timeout",
+    "seed",
+    "opaque",
+    "intrinsic",
+    "aborts_if_is_strict",
+    "aborts_if_is_partial",
+    "emits_is_strict",
+    "emits_is_partial",
+    "requires_if_aborts",
+    "addition_overflow_unchecked",
+    "assume_no_abort_from_here",
+    "export_ensures",
+    "friend",
+    "disable_invariants_in_body",
+    "delegate_invariants_to_caller",
+    "bv",
+    "bv_ret",
+    "unroll",
+];
+
+/// Pragmas which take a boolean value, `pragma opaque;` is the same as `pragma opaque = true;`.
+pub const BOOL_SPEC_PRAGMAS: &[&str] = &[
+    "verify",
+    "opaque",
+    "aborts_if_is_strict",
+    "aborts_if_is_partial",
+    "emits_is_strict",
+    "emits_is_partial",
+    "requires_if_aborts",
+    "addition_overflow_unchecked",
+    "assume_no_abort_from_here",
+    "export_ensures",
+    "disable_invariants_in_body",
+    "delegate_invariants_to_caller",
+];
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Definition {
     NamedItem(SymbolKind, InFile<ast::NamedElement>),
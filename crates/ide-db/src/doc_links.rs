@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Links inside of the doc comments: plain URLs and intra-doc links to the items,
+//! like [`coin::transfer`] or [coin::transfer].
+
+use syntax::{AstToken, TextRange, TextSize, ast};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocLink {
+    Url(String),
+    /// Path of the item, without brackets and backticks.
+    Item(String),
+}
+
+/// Links in the doc comment, with their ranges in the file. Item links are resolved with
+/// [`lang::nameres::doc_links::resolve_doc_link`].
+pub fn doc_comment_links(comment: &ast::Comment) -> Vec<(TextRange, DocLink)> {
+    let comment_offset = comment.syntax().text_range().start();
+    links_in_text(comment.text())
+        .into_iter()
+        .map(|(range, link)| (range + comment_offset, link))
+        .collect()
+}
+
+/// Links in the text of the doc comment, with the ranges relative to the text.
+pub fn links_in_text(text: &str) -> Vec<(TextRange, DocLink)> {
+    let mut links = url_links(text);
+    for (range, path) in item_links(text) {
+        if !links
+            .iter()
+            .any(|(url_range, _)| url_range.intersect(range).is_some_and(|it| !it.is_empty()))
+        {
+            links.push((range, DocLink::Item(path)));
+        }
+    }
+    links.sort_by_key(|(range, _)| range.start());
+    links
+}
+
+fn url_links(text: &str) -> Vec<(TextRange, DocLink)> {
+    let mut links = vec![];
+    let mut search_from = 0;
+    while let Some(idx) = text[search_from..].find("http") {
+        let start = search_from + idx;
+        let rest = &text[start..];
+        if !rest.starts_with("http://") && !rest.starts_with("https://") {
+            search_from = start + "http".len();
+            continue;
+        }
+        let len = rest
+            .find(|c: char| c.is_whitespace() || "<>()[]\"'`".contains(c))
+            .unwrap_or(rest.len());
+        let url = rest[..len].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        links.push((text_range(start, url.len()), DocLink::Url(url.to_string())));
+        search_from = start + len.max(1);
+    }
+    links
+}
+
+/// `[path]` and `` [`path`] ``, but not the Markdown links `[text](url)` and `[text][ref]`.
+fn item_links(text: &str) -> Vec<(TextRange, String)> {
+    let mut links = vec![];
+    let mut search_from = 0;
+    while let Some(idx) = text[search_from..].find('[') {
+        let open_bracket = search_from + idx;
+        search_from = open_bracket + 1;
+        let Some(len) = text[search_from..].find(']') else {
+            break;
+        };
+        let close_bracket = search_from + len;
+        if text[close_bracket + 1..].starts_with(['(', '[', ':']) {
+            continue;
+        }
+        let inner = &text[open_bracket + 1..close_bracket];
+        let (path_start, path) = match inner.strip_prefix('`').and_then(|it| it.strip_suffix('`')) {
+            Some(path) => (open_bracket + 2, path),
+            None => (open_bracket + 1, inner),
+        };
+        if is_item_path(path) {
+            links.push((text_range(path_start, path.len()), path.to_string()));
+        }
+    }
+    links
+}
+
+/// Paths in the inline code spans, like `` `coin::transfer` ``, which are not the intra-doc links.
+/// Unlike the intra-doc links, these are often plain code, so only the resolved ones should be linked.
+pub fn code_span_paths(text: &str) -> Vec<(TextRange, String)> {
+    let mut paths = vec![];
+    let mut search_from = 0;
+    while let Some(idx) = text[search_from..].find('`') {
+        let open_backtick = search_from + idx;
+        let Some(len) = text[open_backtick + 1..].find('`') else {
+            break;
+        };
+        let close_backtick = open_backtick + 1 + len;
+        search_from = close_backtick + 1;
+        let is_link_text =
+            text[..open_backtick].ends_with('[') && text[close_backtick + 1..].starts_with(']');
+        let path = &text[open_backtick + 1..close_backtick];
+        if !is_link_text && is_item_path(path) {
+            paths.push((text_range(open_backtick + 1, path.len()), path.to_string()));
+        }
+    }
+    paths
+}
+
+fn is_item_path(path: &str) -> bool {
+    let starts_with_name = path.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+    let starts_with_address = path.starts_with("0x") && path.contains("::");
+    (starts_with_name || starts_with_address)
+        && path.split("::").all(|segment| {
+            !segment.is_empty() && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+fn text_range(start: usize, len: usize) -> TextRange {
+    TextRange::at(TextSize::new(start as u32), TextSize::new(len as u32))
+}
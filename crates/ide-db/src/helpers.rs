@@ -5,6 +5,8 @@
 // Modifications have been made to the original code.
 
 use crate::RootDatabase;
+use base_db::package_root::PackageId;
+use base_db::{SourceDatabase, source_db};
 use lang::Semantics;
 use syntax::ast::HasItems;
 use syntax::files::{InFile, InFileExt};
@@ -56,3 +58,10 @@ pub fn visit_item_specs(
         }
     }
 }
+
+/// Name of the package from `Move.toml`, or its index for the packages without manifest.
+pub fn package_name(db: &dyn SourceDatabase, package_id: PackageId) -> String {
+    source_db::metadata_for_package_id(db, package_id)
+        .and_then(|it| it.package_name)
+        .unwrap_or_else(|| format!("package #{}", package_id.idx(db)))
+}
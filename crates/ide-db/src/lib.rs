@@ -11,11 +11,16 @@ pub mod apply_change;
 pub mod assist_config;
 pub mod assist_context;
 pub mod assists;
+pub mod attrs;
 pub mod defs;
+pub mod doc_links;
 pub mod helpers;
 pub mod imports;
 pub mod label;
+pub mod library_cache;
 pub mod load;
+pub mod memory_usage;
+pub mod prime_caches;
 pub mod rename;
 pub mod root_db;
 pub mod search;
@@ -0,0 +1,269 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-disk cache of the symbol indices of the library packages.
+//!
+//! Framework and git dependencies under `~/.move` don't change for a given revision,
+//! so their module and item summaries (names, containers, syntax kinds and ranges) are stored
+//! on disk and restored in the next session without parsing the package files.
+//!
+//! Entries are keyed by the package manifest path (which includes the revision for the git
+//! dependencies) and the analyzer version, and are only used if the hashes
+//! of all package files are unchanged.
+
+use crate::RootDatabase;
+use crate::symbol_index::sym_db::{self, FileSymbol, SymbolIndex};
+use base_db::SourceDatabase;
+use base_db::inputs::{FileIdInput, InternFileId};
+use base_db::package_root::{PackageId, PackageRoot};
+use rustc_hash::FxHasher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use syntax::{SyntaxKind, TextRange, TextSize};
+use vfs::{AbsPathBuf, FileId, VfsPath};
+
+#[derive(Debug)]
+pub struct LibraryCache {
+    cache_dir: AbsPathBuf,
+    analyzer_version: String,
+    /// Indices which are restored from disk or stored in the current session, keyed by the manifest path.
+    indices: Mutex<HashMap<String, PackageIndex>>,
+}
+
+#[derive(Debug, Clone)]
+struct PackageIndex {
+    file_hashes: Vec<(FileId, u64)>,
+    symbol_index: Arc<SymbolIndex>,
+}
+
+impl LibraryCache {
+    pub fn new(cache_dir: AbsPathBuf, analyzer_version: String) -> Self {
+        LibraryCache {
+            cache_dir,
+            analyzer_version,
+            indices: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cache_file_name(&self, manifest_path: &str) -> String {
+        let mut hasher = FxHasher::default();
+        manifest_path.hash(&mut hasher);
+        self.analyzer_version.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn load(&self, manifest_path: &str) -> Option<CachedPackage> {
+        let cache_file_path = self
+            .cache_dir
+            .join(format!("{}.json", self.cache_file_name(manifest_path)));
+        let contents = std::fs::read_to_string(&cache_file_path).ok()?;
+        let cached_package = serde_json::from_str::<CachedPackage>(&contents)
+            .inspect_err(|err| tracing::warn!(?cache_file_path, ?err, "invalid library cache file"))
+            .ok()?;
+        if cached_package.manifest_path != manifest_path
+            || cached_package.analyzer_version != self.analyzer_version
+        {
+            return None;
+        }
+        Some(cached_package)
+    }
+
+    fn store(&self, cached_package: &CachedPackage) {
+        let cache_file_name = self.cache_file_name(&cached_package.manifest_path);
+        let cache_file_path = self.cache_dir.join(format!("{cache_file_name}.json"));
+        let res = (|| -> anyhow::Result<()> {
+            std::fs::create_dir_all(&self.cache_dir)?;
+            // write into the temporary file first, so that other sessions never read partial entries
+            let tmp_file_path = self
+                .cache_dir
+                .join(format!("{cache_file_name}.{}.tmp", std::process::id()));
+            std::fs::write(&tmp_file_path, serde_json::to_string(cached_package)?)?;
+            std::fs::rename(&tmp_file_path, &cache_file_path)?;
+            Ok(())
+        })();
+        if let Err(err) = res {
+            tracing::warn!(?cache_file_path, ?err, "cannot write library cache file");
+        }
+    }
+}
+
+/// Returns symbol index of the package. Library packages are restored from the on-disk cache
+/// if the package files are unchanged, and stored into it otherwise.
+pub fn package_symbol_index(db: &RootDatabase, package_id: PackageId) -> Arc<SymbolIndex> {
+    if let Some(symbol_index) = cached_symbol_index(db, package_id) {
+        return symbol_index;
+    }
+    let symbol_index = sym_db::world_symbols_in_package(db, package_id);
+    if let Some(library_cache) = db.library_cache()
+        && let Some((package_root, manifest_path)) = library_package_root(db, package_id)
+    {
+        let file_hashes = file_hashes(db, &package_root);
+        let cached_package = CachedPackage::new(
+            manifest_path.clone(),
+            library_cache.analyzer_version.clone(),
+            &package_root,
+            &file_hashes,
+            &symbol_index,
+        );
+        library_cache.store(&cached_package);
+        library_cache.indices.lock().unwrap().insert(
+            manifest_path,
+            PackageIndex {
+                file_hashes,
+                symbol_index: symbol_index.clone(),
+            },
+        );
+    }
+    symbol_index
+}
+
+/// Symbol index of the library package, if the cache entry for it exists and the package files
+/// are unchanged since it was stored.
+pub fn cached_symbol_index(db: &RootDatabase, package_id: PackageId) -> Option<Arc<SymbolIndex>> {
+    let library_cache = db.library_cache()?;
+    let (package_root, manifest_path) = library_package_root(db, package_id)?;
+    let file_hashes = file_hashes(db, &package_root);
+
+    if let Some(package_index) = library_cache.indices.lock().unwrap().get(&manifest_path)
+        && package_index.file_hashes == file_hashes
+    {
+        return Some(package_index.symbol_index.clone());
+    }
+
+    let _p = tracing::info_span!("restore library symbol index", ?manifest_path).entered();
+    let cached_package = library_cache.load(&manifest_path)?;
+    let symbol_index = Arc::new(
+        cached_package
+            .restore(&package_root, &file_hashes)?
+            .tracked(db, package_id),
+    );
+    library_cache.indices.lock().unwrap().insert(
+        manifest_path,
+        PackageIndex {
+            file_hashes,
+            symbol_index: symbol_index.clone(),
+        },
+    );
+    Some(symbol_index)
+}
+
+fn library_package_root(db: &RootDatabase, package_id: PackageId) -> Option<(Arc<PackageRoot>, String)> {
+    let package_root = db.package_root(package_id).data(db);
+    if !package_root.is_library() {
+        return None;
+    }
+    let manifest_file_id = package_root.manifest_file_id?;
+    let manifest_path = package_root.path_for_file(&manifest_file_id)?.to_string();
+    Some((package_root, manifest_path))
+}
+
+fn file_hashes(db: &RootDatabase, package_root: &PackageRoot) -> Vec<(FileId, u64)> {
+    let mut file_hashes = package_root
+        .file_ids()
+        .map(|file_id| (file_id, file_text_hash(db, file_id.intern(db))))
+        .collect::<Vec<_>>();
+    file_hashes.sort();
+    file_hashes
+}
+
+#[salsa_macros::tracked]
+fn file_text_hash(db: &dyn SourceDatabase, file_id: FileIdInput) -> u64 {
+    let text = db.file_text(file_id.data(db)).text(db);
+    let mut hasher = FxHasher::default();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPackage {
+    manifest_path: String,
+    analyzer_version: String,
+    files: Vec<CachedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFile {
+    path: String,
+    text_hash: u64,
+    symbols: Vec<CachedSymbol>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSymbol {
+    name: String,
+    container_name: Option<String>,
+    kind: u16,
+    start: u32,
+    end: u32,
+}
+
+impl CachedPackage {
+    fn new(
+        manifest_path: String,
+        analyzer_version: String,
+        package_root: &PackageRoot,
+        file_hashes: &[(FileId, u64)],
+        symbol_index: &SymbolIndex,
+    ) -> Self {
+        let mut symbols_by_file = HashMap::<FileId, Vec<CachedSymbol>>::new();
+        for symbol in symbol_index.symbols.iter() {
+            symbols_by_file
+                .entry(symbol.file_id)
+                .or_default()
+                .push(CachedSymbol {
+                    name: symbol.name.clone(),
+                    container_name: symbol.container_name.clone(),
+                    kind: symbol.kind.into(),
+                    start: symbol.range.start().into(),
+                    end: symbol.range.end().into(),
+                });
+        }
+        let files = file_hashes
+            .iter()
+            .filter_map(|(file_id, text_hash)| {
+                let path = package_root.path_for_file(file_id)?.to_string();
+                Some(CachedFile {
+                    path,
+                    text_hash: *text_hash,
+                    symbols: symbols_by_file.remove(file_id).unwrap_or_default(),
+                })
+            })
+            .collect();
+        CachedPackage {
+            manifest_path,
+            analyzer_version,
+            files,
+        }
+    }
+
+    /// Returns `None` if the set of the package files or any of their hashes is different.
+    fn restore(self, package_root: &PackageRoot, file_hashes: &[(FileId, u64)]) -> Option<SymbolIndex> {
+        if self.files.len() != file_hashes.len() {
+            return None;
+        }
+        let file_hashes = file_hashes.iter().copied().collect::<HashMap<_, _>>();
+        let mut symbols = vec![];
+        for cached_file in self.files {
+            let file_path = VfsPath::new_real_path(cached_file.path);
+            let file_id = *package_root.file_for_path(&file_path)?;
+            if file_hashes.get(&file_id) != Some(&cached_file.text_hash) {
+                return None;
+            }
+            for cached_symbol in cached_file.symbols {
+                symbols.push(FileSymbol {
+                    name: cached_symbol.name,
+                    file_id,
+                    kind: SyntaxKind::from(cached_symbol.kind),
+                    range: TextRange::new(
+                        TextSize::from(cached_symbol.start),
+                        TextSize::from(cached_symbol.end),
+                    ),
+                    container_name: cached_symbol.container_name,
+                });
+            }
+        }
+        Some(SymbolIndex::new(symbols.into_boxed_slice()))
+    }
+}
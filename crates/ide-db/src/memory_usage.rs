@@ -0,0 +1,157 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Estimated memory usage of the analyzer caches, broken down by query and by package.
+//!
+//! Only the values which are currently memoized are reported, nothing is computed for the report.
+//! Sizes are estimates of the data structures themselves, the salsa bookkeeping
+//! (dependencies, revisions and interned values) is not included.
+
+use crate::RootDatabase;
+use crate::helpers::package_name;
+use base_db::SourceDatabase;
+use base_db::memory_counters::ValuesSize;
+use lang::hir_db;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct MemoryUsage {
+    pub queries: Vec<QueryMemoryUsage>,
+    pub packages: Vec<PackageMemoryUsage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct QueryMemoryUsage {
+    pub name: &'static str,
+    pub count: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageMemoryUsage {
+    pub name: String,
+    pub is_library: bool,
+    pub files: usize,
+    pub text_bytes: usize,
+    pub parse_bytes: usize,
+    pub symbol_index_bytes: usize,
+}
+
+impl PackageMemoryUsage {
+    pub fn total_bytes(&self) -> usize {
+        self.text_bytes + self.parse_bytes + self.symbol_index_bytes
+    }
+}
+
+/// Memory usage of all packages of the workspace.
+///
+/// Syntax trees, symbol indices and inference results are only counted while they are memoized,
+/// the evicted ones are not recomputed.
+pub fn memory_usage(db: &RootDatabase) -> MemoryUsage {
+    let _p = tracing::info_span!("memory_usage").entered();
+
+    let memory_counters = db.memory_counters();
+    let parse_sizes = memory_counters.syntax_trees.sizes();
+    let symbol_index_sizes = memory_counters.symbol_indices.sizes();
+
+    let mut file_texts = QueryMemoryUsage::new("file texts");
+    let mut syntax_trees = QueryMemoryUsage::new("syntax trees");
+    let mut symbol_indices = QueryMemoryUsage::new("symbol indices");
+
+    let mut packages = vec![];
+    for package_id in db.all_package_ids().data(db) {
+        let package_root = db.package_root(package_id).data(db);
+        let mut package = PackageMemoryUsage {
+            name: package_name(db, package_id),
+            is_library: package_root.is_library(),
+            files: 0,
+            text_bytes: 0,
+            parse_bytes: 0,
+            symbol_index_bytes: 0,
+        };
+        for file_id in hir_db::source_file_ids_in_package(db, package_id) {
+            package.files += 1;
+            package.text_bytes += db.file_text(file_id).text(db).len();
+            if let Some(parse_size) = parse_sizes.get(&file_id) {
+                package.parse_bytes += parse_size.bytes;
+                syntax_trees.add(*parse_size);
+            }
+        }
+        if let Some(symbol_index_size) = symbol_index_sizes.get(&package_id) {
+            package.symbol_index_bytes = symbol_index_size.bytes;
+            symbol_indices.add(*symbol_index_size);
+        }
+        file_texts.add(ValuesSize {
+            count: package.files,
+            bytes: package.text_bytes,
+        });
+        packages.push(package);
+    }
+    packages.sort_by_key(|it| std::cmp::Reverse(it.total_bytes()));
+
+    let mut inference_results = QueryMemoryUsage::new("inference results");
+    for size in memory_counters.inference_results.sizes().into_values() {
+        inference_results.add(size);
+    }
+
+    MemoryUsage {
+        queries: vec![file_texts, syntax_trees, symbol_indices, inference_results],
+        packages,
+    }
+}
+
+impl QueryMemoryUsage {
+    fn new(name: &'static str) -> Self {
+        QueryMemoryUsage { name, count: 0, bytes: 0 }
+    }
+
+    fn add(&mut self, size: ValuesSize) {
+        self.count += size.count;
+        self.bytes += size.bytes;
+    }
+}
+
+impl fmt::Display for MemoryUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "By query:")?;
+        for query in &self.queries {
+            writeln!(
+                f,
+                "  {:<20} {:>10} {:>8} entries",
+                query.name,
+                format_bytes(query.bytes),
+                query.count
+            )?;
+        }
+        let total_bytes = self.queries.iter().map(|it| it.bytes).sum::<usize>();
+        writeln!(f, "  {:<20} {:>10}", "total", format_bytes(total_bytes))?;
+
+        writeln!(f, "\nBy package:")?;
+        for package in &self.packages {
+            writeln!(
+                f,
+                "  {:<30} {:>10} ({} files, texts {}, syntax trees {}, symbols {}){}",
+                package.name,
+                format_bytes(package.total_bytes()),
+                package.files,
+                format_bytes(package.text_bytes),
+                format_bytes(package.parse_bytes),
+                format_bytes(package.symbol_index_bytes),
+                if package.is_library { " [library]" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const KB: usize = 1024;
+    const MB: usize = 1024 * KB;
+    if bytes >= MB {
+        format!("{:.1}MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1}KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes}B")
+    }
+}
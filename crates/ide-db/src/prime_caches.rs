@@ -0,0 +1,155 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+//! Warms up the salsa caches after the workspace is loaded, so that the first requests
+//! (completion, workspace symbols, auto-import) don't have to compute everything from scratch.
+
+use crate::RootDatabase;
+use crate::helpers::package_name;
+use crate::library_cache;
+use base_db::inputs::InternFileId;
+use base_db::package_root::PackageId;
+use base_db::{SourceDatabase, source_db};
+use lang::hir_db;
+use salsa::Cancelled;
+use std::collections::{HashMap, HashSet};
+
+/// Progress of the [`parallel_prime_caches`], reported every time a package is started or finished.
+#[derive(Debug, Clone)]
+pub struct ParallelPrimeCachesProgress {
+    /// Names of the packages which are currently being indexed.
+    pub packages_currently_indexing: Vec<String>,
+    pub packages_total: usize,
+    pub packages_done: usize,
+}
+
+/// Parses every file, builds the symbol index and computes the import candidates for every
+/// package of the workspace, in the order of the package dependency graph.
+///
+/// Dependencies are primed first, independent packages are processed in parallel
+/// on `num_worker_threads` threads. Unwinds with [`Cancelled`] if the database is changed.
+pub fn parallel_prime_caches(
+    db: &RootDatabase,
+    num_worker_threads: usize,
+    cb: &(dyn Fn(ParallelPrimeCachesProgress) + Sync),
+) {
+    let _p = tracing::info_span!("parallel_prime_caches").entered();
+
+    let all_package_ids = db.all_package_ids().data(db);
+    let mut package_deps = HashMap::new();
+    for package_id in all_package_ids.iter().copied() {
+        let deps = hir_db::transitive_dep_package_ids(db, package_id)
+            .into_iter()
+            .filter(|dep_id| *dep_id != package_id && all_package_ids.contains(dep_id))
+            .collect::<HashSet<_>>();
+        package_deps.insert(package_id, deps);
+    }
+    let packages_total = package_deps.len();
+
+    let res = std::thread::scope(|scope| {
+        // workers are done when the work channel is closed, so the sender should be dropped before
+        // the scope is joined, both after everything is scheduled and on the early return
+        let (work_sender, work_receiver) = crossbeam_channel::unbounded::<(PackageId, String)>();
+        let (progress_sender, progress_receiver) = crossbeam_channel::unbounded::<PrimingProgress>();
+        let mut work_sender = Some(work_sender);
+
+        for idx in 0..num_worker_threads.max(1) {
+            let db = db.snapshot();
+            let work_receiver = work_receiver.clone();
+            let progress_sender = progress_sender.clone();
+            std::thread::Builder::new()
+                .name(format!("PrimeCaches#{idx}"))
+                .spawn_scoped(scope, move || {
+                    for (package_id, package_name) in work_receiver {
+                        progress_sender
+                            .send(PrimingProgress::Begin(package_name.clone()))
+                            .ok();
+                        let res = Cancelled::catch(|| prime_package(&db, package_id));
+                        progress_sender
+                            .send(PrimingProgress::End(package_id, package_name, res))
+                            .ok();
+                    }
+                })
+                .expect("failed to spawn thread");
+        }
+        drop(work_receiver);
+        drop(progress_sender);
+
+        let mut scheduled = HashSet::new();
+        let mut done = HashSet::new();
+        let mut packages_currently_indexing = vec![];
+        while done.len() < packages_total {
+            let mut ready = package_deps
+                .iter()
+                .filter(|(package_id, deps)| {
+                    !scheduled.contains(*package_id) && deps.iter().all(|dep| done.contains(dep))
+                })
+                .map(|(package_id, _)| *package_id)
+                .collect::<Vec<_>>();
+            if ready.is_empty() && scheduled.len() == done.len() {
+                // dependency cycle, schedule everything what's left
+                ready = package_deps
+                    .keys()
+                    .filter(|package_id| !scheduled.contains(*package_id))
+                    .copied()
+                    .collect();
+            }
+            if let Some(sender) = &work_sender {
+                for package_id in ready {
+                    scheduled.insert(package_id);
+                    sender.send((package_id, package_name(db, package_id))).ok();
+                }
+            }
+            if scheduled.len() == packages_total {
+                work_sender = None;
+            }
+
+            let Ok(progress) = progress_receiver.recv() else {
+                break;
+            };
+            match progress {
+                PrimingProgress::Begin(package_name) => {
+                    packages_currently_indexing.push(package_name);
+                }
+                PrimingProgress::End(package_id, package_name, res) => {
+                    if let Err(cancelled) = res {
+                        // the packages left in the channel are cancelled right away
+                        work_sender.take();
+                        return Err(cancelled);
+                    }
+                    packages_currently_indexing.retain(|it| *it != package_name);
+                    done.insert(package_id);
+                }
+            }
+            cb(ParallelPrimeCachesProgress {
+                packages_currently_indexing: packages_currently_indexing.clone(),
+                packages_total,
+                packages_done: done.len(),
+            });
+        }
+        Ok(())
+    });
+    if let Err(cancelled) = res {
+        std::panic::resume_unwind(Box::new(cancelled));
+    }
+}
+
+enum PrimingProgress {
+    Begin(String),
+    End(PackageId, String, Result<(), Cancelled>),
+}
+
+fn prime_package(db: &RootDatabase, package_id: PackageId) {
+    let source_file_ids = hir_db::source_file_ids_in_package(db, package_id);
+    for file_id in source_file_ids.iter() {
+        source_db::parse(db, file_id.intern(db));
+    }
+    // restored from the disk cache for the unchanged libraries
+    library_cache::package_symbol_index(db, package_id);
+    for file_id in source_file_ids {
+        hir_db::import_candidates(db, file_id);
+    }
+}
@@ -4,8 +4,10 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
+mod conflicts;
+
 use crate::search::{FileReference, FileReferenceNode};
-use crate::source_change::SourceChange;
+use crate::source_change::{ChangeAnnotation, FileSystemEdit, SourceChange};
 use crate::text_edit::{TextEdit, TextEditBuilder};
 use crate::{RootDatabase, search};
 use base_db::SourceDatabase;
@@ -13,6 +15,7 @@ use lang::Semantics;
 use std::fmt;
 use syntax::files::InFile;
 use syntax::{AstNode, SyntaxKind, TextRange, ast};
+use vfs::{AnchoredPathBuf, FileId};
 
 pub type Result<T, E = RenameError> = std::result::Result<T, E>;
 
@@ -52,6 +55,8 @@ pub fn rename_named_element<'db>(
         bail!("Cannot rename a non-local definition");
     }
 
+    conflicts::check_declaration_conflicts(sema, &named_element, new_name)?;
+
     let mut source_change = SourceChange::default();
 
     let usages = search::item_usages(sema, named_element.clone()).fetch_all();
@@ -63,14 +68,97 @@ pub fn rename_named_element<'db>(
 
     // This needs to come after the references edits, because we change the annotation of existing edits
     // if a conflict is detected.
+    let conflict_annotation = match named_element.clone().map(|it| it.ident_pat()) {
+        InFile {
+            file_id,
+            value: Some(ident_pat),
+        } if conflicts::has_local_rename_conflicts(
+            sema,
+            InFile::new(file_id, &ident_pat),
+            &usages,
+            new_name,
+        ) =>
+        {
+            Some(
+                source_change.insert_annotation(ChangeAnnotation {
+                    label: "This rename will change the program's meaning".to_string(),
+                    needs_confirmation: true,
+                    description: Some(
+                        "Some variables will shadow the renamed variable or be shadowed by it \
+                     if the rename is performed"
+                            .to_string(),
+                    ),
+                }),
+            )
+        }
+        _ => None,
+    };
+
+    if let Some(module) = named_element.clone().cast_into::<ast::Module>() {
+        source_change.extend(module_file_renames(sema, module, new_name));
+    }
+
     let (file_id, named_element) = named_element.unpack();
 
-    let edit = source_edit_from_def(named_element, new_name)?;
+    let mut edit = source_edit_from_def(named_element, new_name)?;
+    edit.set_annotation(conflict_annotation);
     source_change.insert_source_edit(file_id, edit);
 
     Ok(source_change)
 }
 
+/// Name of the file without the `.move` extension, like `coin` for `sources/coin.move`.
+pub fn move_file_stem(db: &dyn SourceDatabase, file_id: FileId) -> Option<String> {
+    let package_root = db.package_root(db.file_package_id(file_id)).data(db);
+    let (file_name, ext) = package_root.path_for_file(&file_id)?.name_and_extension()?;
+    if ext != Some("move") {
+        return None;
+    }
+    Some(file_name.to_string())
+}
+
+/// `coin.move` and `coin.spec.move` are renamed together with the `coin` module.
+fn module_file_renames(
+    sema: &Semantics<'_, RootDatabase>,
+    module: InFile<ast::Module>,
+    new_name: &str,
+) -> Vec<FileSystemEdit> {
+    let Some(old_name) = module.value.name().map(|it| it.as_string()) else {
+        return vec![];
+    };
+    let module_file_id = module.file_id;
+    if move_file_stem(sema.db, module_file_id).is_none_or(|it| it != old_name) {
+        return vec![];
+    }
+    let mut edits = vec![FileSystemEdit::MoveFile {
+        src: module_file_id,
+        dst: AnchoredPathBuf {
+            anchor: module_file_id,
+            path: format!("{new_name}.move"),
+        },
+    }];
+
+    let package_root = sema.package_root(sema.db.file_package_id(module_file_id));
+    let module_dir = package_root
+        .path_for_file(&module_file_id)
+        .and_then(|it| it.parent());
+    let spec_file_id = package_root.file_ids().find(|file_id| {
+        let file_path = package_root.path_for_file(file_id);
+        file_path.and_then(|it| it.parent()) == module_dir
+            && move_file_stem(sema.db, *file_id).is_some_and(|it| it == format!("{old_name}.spec"))
+    });
+    if let Some(spec_file_id) = spec_file_id {
+        edits.push(FileSystemEdit::MoveFile {
+            src: spec_file_id,
+            dst: AnchoredPathBuf {
+                anchor: spec_file_id,
+                path: format!("{new_name}.spec.move"),
+            },
+        });
+    }
+    edits
+}
+
 pub fn source_edit_from_references(
     references: &[FileReference],
     named_element: ast::NamedElement,
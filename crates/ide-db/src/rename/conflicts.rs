@@ -0,0 +1,224 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::RootDatabase;
+use crate::defs::{Definition, NameRefClass};
+use crate::rename::{Result, bail, format_err};
+use crate::search::{FileReferenceNode, UsageSearchResult};
+use base_db::SourceDatabase;
+use lang::Semantics;
+use lang::loc::SyntaxLocFileExt;
+use lang::nameres::name_resolution::{
+    WalkScopesCtx, get_entries_from_walking_scopes, get_modules_as_entries,
+};
+use lang::nameres::namespaces::{NAMES, Ns};
+use lang::nameres::scope::ScopeEntryExt;
+use lang::node_ext::ModuleLangExt;
+use syntax::SyntaxKind::*;
+use syntax::ast::idents::PRIMITIVE_TYPES;
+use syntax::files::{InFile, InFileExt};
+use syntax::{AstNode, SyntaxNode, ast};
+
+/// Fails if the new name clashes with another declaration of the same kind in the same scope,
+/// as the renamed program would not compile then.
+pub(super) fn check_declaration_conflicts(
+    sema: &Semantics<'_, RootDatabase>,
+    named_element: &InFile<ast::NamedElement>,
+    new_name: &str,
+) -> Result<()> {
+    let (file_id, element) = named_element.unpack_ref();
+    let element_syntax = element.syntax();
+
+    let sibling_names = |list: Option<SyntaxNode>| {
+        list.into_iter()
+            .flat_map(|it| it.children())
+            .filter(|it| it != element_syntax)
+            .filter_map(ast::NamedElement::cast)
+            .filter_map(|it| it.name())
+            .any(|it| it.as_string() == new_name)
+    };
+
+    match element {
+        ast::NamedElement::NamedField(_) => {
+            if sibling_names(element_syntax.parent()) {
+                bail!("Field `{new_name}` is already defined");
+            }
+        }
+        ast::NamedElement::Variant(_) => {
+            if sibling_names(element_syntax.parent()) {
+                bail!("Variant `{new_name}` is already defined");
+            }
+        }
+        ast::NamedElement::TypeParam(_) => {
+            if sibling_names(element_syntax.parent()) {
+                bail!("Type parameter `{new_name}` is already defined");
+            }
+        }
+        ast::NamedElement::IdentPat(ident_pat) => {
+            // fun main(a: u8, b: u8)
+            let param_list = ident_pat
+                .syntax()
+                .parent()
+                .filter(|it| it.kind() == PARAM)
+                .and_then(|it| it.parent());
+            let has_param_conflict = param_list.is_some_and(|param_list| {
+                param_list
+                    .descendants()
+                    .filter_map(ast::IdentPat::cast)
+                    .filter(|it| it != ident_pat)
+                    .filter_map(|it| it.name())
+                    .any(|it| it.as_string() == new_name)
+            });
+            if has_param_conflict {
+                bail!("Parameter `{new_name}` is already defined");
+            }
+        }
+        ast::NamedElement::Const(_)
+        | ast::NamedElement::Enum(_)
+        | ast::NamedElement::Fun(_)
+        | ast::NamedElement::GlobalVariableDecl(_)
+        | ast::NamedElement::Lemma(_)
+        | ast::NamedElement::Schema(_)
+        | ast::NamedElement::SpecFun(_)
+        | ast::NamedElement::Struct(_) => {
+            let entry = named_element
+                .clone()
+                .to_entry()
+                .ok_or_else(|| format_err!("No references found at position"))?;
+            if entry.ns == Ns::TYPE && PRIMITIVE_TYPES.contains(&new_name) {
+                bail!("Invalid name `{new_name}`: cannot rename to a builtin type");
+            }
+            let Some(item_scope) = element_syntax.parent() else {
+                return Ok(());
+            };
+            let walk_ctx = WalkScopesCtx {
+                allowed_ns: entry.ns.into(),
+                start_at: item_scope.in_file(file_id),
+                expected_name: Some(new_name.to_string()),
+            };
+            let existing_entries = get_entries_from_walking_scopes(sema.db, walk_ctx);
+            if existing_entries.iter().any(|it| it.node_loc != entry.node_loc) {
+                bail!("`{new_name}` is already defined in this module");
+            }
+        }
+        ast::NamedElement::Module(module) => {
+            let Some(address) = module.address() else {
+                return Ok(());
+            };
+            let package_id = sema.db.file_package_id(file_id);
+            let module_loc = named_element.loc();
+            let has_module_conflict = get_modules_as_entries(sema.db, package_id, address)
+                .into_iter()
+                .any(|it| it.name == new_name && it.node_loc != module_loc);
+            if has_module_conflict {
+                bail!("Module `{new_name}` is already defined at this address");
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
+
+/// Whether renaming the local binding changes what some of the names in the function refer to.
+///
+/// This happens when either some other binding with the new name shadows the renamed one
+/// at one of its usages, or the renamed binding captures an existing reference to the new name.
+pub(super) fn has_local_rename_conflicts(
+    sema: &Semantics<'_, RootDatabase>,
+    ident_pat: InFile<&ast::IdentPat>,
+    usages: &UsageSearchResult,
+    new_name: &str,
+) -> bool {
+    let (file_id, ident_pat) = ident_pat.unpack();
+    let Some(old_name) = ident_pat.name().map(|it| it.as_string()) else {
+        return false;
+    };
+    let ident_pat_loc = ident_pat.clone().in_file(file_id).loc();
+    let ident_pat_offset = ident_pat.syntax().text_range().start();
+
+    let first_visible_binding = |start_at: &SyntaxNode, name: &str| {
+        let walk_ctx = WalkScopesCtx {
+            allowed_ns: NAMES,
+            start_at: start_at.clone().in_file(file_id),
+            expected_name: Some(name.to_string()),
+        };
+        get_entries_from_walking_scopes(sema.db, walk_ctx)
+            .into_iter()
+            .next()
+    };
+
+    // let a = 1; let b = 2; a + b -> let b = 1; let b = 2; b + b
+    //                                                      ^ refers to the second `b` now
+    let usage_refs = usages.references.get(&file_id).into_iter().flatten();
+    for usage in usage_refs {
+        let FileReferenceNode::NameRef(name_ref) = &usage.name else {
+            continue;
+        };
+        let Some(shadowing_entry) = first_visible_binding(name_ref.syntax(), new_name) else {
+            continue;
+        };
+        let shadowing_loc = shadowing_entry.node_loc;
+        if shadowing_loc.file_id() == file_id
+            && shadowing_loc.kind() == IDENT_PAT
+            && shadowing_loc.node_offset() > ident_pat_offset
+        {
+            return true;
+        }
+    }
+
+    // let b = 1; let a = 2; b -> let b = 1; let b = 2; b
+    //                                                  ^ refers to the renamed binding now
+    let Some(owner) = ident_pat.syntax().ancestors().find(|it| {
+        matches!(
+            it.kind(),
+            FUN | SPEC_FUN | SPEC_INLINE_FUN | SCHEMA | ITEM_SPEC | LEMMA
+        )
+    }) else {
+        return false;
+    };
+    for name_ref in owner
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .filter(|it| it.text() == new_name)
+    {
+        if !is_unqualified_value_reference(&name_ref) {
+            continue;
+        }
+        let target = match NameRefClass::classify(sema, &name_ref) {
+            Some(NameRefClass::Definition(Definition::NamedItem(_, named_item))) => named_item,
+            Some(NameRefClass::FieldShorthand { ident_pat, .. }) => ident_pat.map_into(),
+            _ => continue,
+        };
+        let is_captured_target = match &target.value {
+            ast::NamedElement::IdentPat(target_ident_pat) => {
+                let target_offset = target_ident_pat.syntax().text_range().start();
+                target.file_id == file_id && target_offset < ident_pat_offset
+            }
+            ast::NamedElement::Const(_) | ast::NamedElement::GlobalVariableDecl(_) => true,
+            _ => false,
+        };
+        if !is_captured_target {
+            continue;
+        }
+        let renamed_is_visible = first_visible_binding(name_ref.syntax(), &old_name)
+            .is_some_and(|it| it.node_loc == ident_pat_loc);
+        if renamed_is_visible {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// `name` and `S { name }` in the expression position.
+fn is_unqualified_value_reference(name_ref: &ast::NameRef) -> bool {
+    let Some(parent) = name_ref.syntax().parent() else {
+        return false;
+    };
+    if let Some(struct_lit_field) = ast::StructLitField::cast(parent.clone()) {
+        return struct_lit_field.expr().is_none();
+    }
+    parent.parent().and_then(ast::Path::cast).is_some_and(|path| {
+        path.qualifier().is_none() && path.syntax().parent().is_some_and(|it| it.kind() == PATH_EXPR)
+    })
+}
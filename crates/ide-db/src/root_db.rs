@@ -4,13 +4,16 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
-use base_db::SourceDatabase;
+use crate::library_cache::LibraryCache;
 use base_db::change::ManifestFileId;
 use base_db::inputs::{
     FileIdInput, FileIdSet, FileText, Files, InternFileId, PackageIdSet, PackageMetadata,
     PackageMetadataInput, PackageRootInput,
 };
+use base_db::memory_counters::MemoryCounters;
 use base_db::package_root::{PackageId, PackageRoot};
+use base_db::{SourceDatabase, source_db};
+use lang::hir_db;
 use line_index::LineIndex;
 use salsa::Durability;
 use std::fmt;
@@ -27,6 +30,8 @@ pub struct RootDatabase {
     storage: ManuallyDrop<salsa::Storage<Self>>,
     files: Arc<Files>,
     builtins_file_id: Option<FileIdInput>,
+    library_cache: Option<Arc<LibraryCache>>,
+    memory_counters: Arc<MemoryCounters>,
 }
 
 impl std::panic::RefUnwindSafe for RootDatabase {}
@@ -46,6 +51,8 @@ impl Clone for RootDatabase {
             storage: self.storage.clone(),
             files: self.files.clone(),
             builtins_file_id: self.builtins_file_id.clone(),
+            library_cache: self.library_cache.clone(),
+            memory_counters: self.memory_counters.clone(),
         }
     }
 }
@@ -119,6 +126,10 @@ impl SourceDatabase for RootDatabase {
     fn all_package_ids(&self) -> PackageIdSet {
         self.files.package_ids()
     }
+
+    fn memory_counters(&self) -> &MemoryCounters {
+        &self.memory_counters
+    }
 }
 
 impl Default for RootDatabase {
@@ -133,6 +144,8 @@ impl RootDatabase {
             storage: ManuallyDrop::new(salsa::Storage::default()),
             files: Default::default(),
             builtins_file_id: None,
+            library_cache: None,
+            memory_counters: Default::default(),
         };
 
         let package_ids = PackageIdSet::builder(vec![])
@@ -150,8 +163,36 @@ impl RootDatabase {
             storage: self.storage.clone(),
             files: self.files.clone(),
             builtins_file_id: self.builtins_file_id,
+            library_cache: self.library_cache.clone(),
+            memory_counters: self.memory_counters.clone(),
         }
     }
+
+    /// Sets the number of the syntax trees and the inference results kept in memory,
+    /// `None` restores the default capacity.
+    pub fn update_lru_capacities(
+        &mut self,
+        parse_lru_cap: Option<usize>,
+        inference_lru_cap: Option<usize>,
+    ) {
+        source_db::set_parse_lru_capacity(
+            self,
+            parse_lru_cap.unwrap_or(source_db::DEFAULT_PARSE_LRU_CAP),
+        );
+        hir_db::set_inference_lru_capacity(
+            self,
+            inference_lru_cap.unwrap_or(hir_db::DEFAULT_INFERENCE_LRU_CAP),
+        );
+    }
+
+    pub fn library_cache(&self) -> Option<&LibraryCache> {
+        self.library_cache.as_deref()
+    }
+
+    /// On-disk cache of the library packages, disabled if `None`.
+    pub fn set_library_cache(&mut self, library_cache: Option<LibraryCache>) {
+        self.library_cache = library_cache.map(Arc::new);
+    }
 }
 
 pub fn line_index(db: &dyn SourceDatabase, file_id: FileId) -> Arc<LineIndex> {
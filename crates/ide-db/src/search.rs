@@ -6,14 +6,15 @@
 
 use crate::RootDatabase;
 use crate::defs::{Definition, NameClass, NameRefClass};
-use base_db::SourceDatabase;
+use base_db::inputs::{FileIdInput, InternFileId};
 use base_db::package_root::PackageId;
+use base_db::{SourceDatabase, source_db};
 use lang::loc::SyntaxLocFileExt;
 use lang::nameres::node_ext::ModuleResolutionExt;
 use lang::{Semantics, hir_db, item_specs};
 use memchr::memmem::Finder;
 use std::cell::LazyCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::{iter, mem};
 use syntax::ast::IdentPatOwner;
@@ -165,6 +166,23 @@ fn ident_pat_search_scope(db: &RootDatabase, ident_pat: InFile<ast::IdentPat>) -
     }
 }
 
+/// Names and name references of the file, the files without the searched name are skipped.
+/// Cached until the file changes, so that repeated package-wide searches don't rescan the files.
+pub fn file_name_likes(db: &dyn SourceDatabase, file_id: FileId) -> Arc<HashSet<String>> {
+    #[salsa_macros::tracked]
+    fn file_name_likes(db: &dyn SourceDatabase, file_id: FileIdInput) -> Arc<HashSet<String>> {
+        let file = source_db::parse(db, file_id).tree();
+        let names = file
+            .syntax()
+            .descendants()
+            .filter_map(ast::NameLike::cast)
+            .map(|it| it.as_string())
+            .collect();
+        Arc::new(names)
+    }
+    file_name_likes(db, file_id.intern(db))
+}
+
 pub fn item_usages<'a>(
     sema: &'a Semantics<'a, RootDatabase>,
     named_item: InFile<ast::NamedElement>,
@@ -173,6 +191,7 @@ pub fn item_usages<'a>(
         named_item,
         sema,
         scope: None,
+        skip_files_without_name: false,
     }
 }
 
@@ -334,6 +353,7 @@ pub struct FindUsages<'a> {
     // rename: Option<&'a Rename>, - alias
     sema: &'a Semantics<'a, RootDatabase>,
     scope: Option<SearchScope>,
+    skip_files_without_name: bool,
 }
 
 impl<'a> FindUsages<'a> {
@@ -349,6 +369,13 @@ impl<'a> FindUsages<'a> {
         self
     }
 
+    /// Skip the files which don't have the name of the item in them, see [`file_name_likes`].
+    /// Worth it for the repeated searches through the same files.
+    pub fn skip_files_without_name(mut self) -> Self {
+        self.skip_files_without_name = true;
+        self
+    }
+
     pub fn at_least_one(&self) -> bool {
         let mut found = false;
         self.search(&mut |_, _| {
@@ -426,6 +453,9 @@ impl<'a> FindUsages<'a> {
 
         let name_finder = &Finder::new(&name);
         for (text, file_id, search_range) in search_scope.files(sema.db) {
+            if self.skip_files_without_name && !file_name_likes(sema.db, file_id).contains(&name) {
+                continue;
+            }
             let tree = LazyCell::new(move || sema.parse(file_id).syntax().clone());
 
             for name_offset in Self::find_matches(&text, name_finder, search_range) {
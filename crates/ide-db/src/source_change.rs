@@ -14,16 +14,35 @@ use crate::syntax_helpers::tree_diff::tree_diff;
 use crate::text_edit::{TextEdit, TextEditBuilder};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
-use std::{iter, mem};
+use std::{fmt, iter, mem};
 use stdx::never;
 use syntax::syntax_editor::SyntaxEditor;
 use syntax::{AstNode, SyntaxNode, SyntaxNodePtr, TextRange, TextSize};
 use vfs::{AnchoredPathBuf, FileId};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChangeAnnotationId(u32);
+
+impl fmt::Display for ChangeAnnotationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Description of a group of edits, shown to the user in the refactoring preview.
+#[derive(Debug, Clone)]
+pub struct ChangeAnnotation {
+    pub label: String,
+    /// Whether the client should ask the user to confirm the edits before applying them.
+    pub needs_confirmation: bool,
+    pub description: Option<String>,
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct SourceChange {
     pub source_file_edits: HashMap<FileId, TextEdit>,
     pub file_system_edits: Vec<FileSystemEdit>,
+    pub annotations: HashMap<ChangeAnnotationId, ChangeAnnotation>,
 }
 
 impl SourceChange {
@@ -36,6 +55,7 @@ impl SourceChange {
         SourceChange {
             source_file_edits,
             file_system_edits,
+            annotations: HashMap::default(),
         }
     }
 
@@ -52,7 +72,10 @@ impl SourceChange {
         match self.source_file_edits.entry(file_id.into()) {
             Entry::Occupied(mut entry) => {
                 let value = entry.get_mut();
-                never!(value.union(edit).is_err(), "overlapping edits for same file");
+                never!(
+                    value.union(edit).is_err(),
+                    "overlapping or differently annotated edits for same file"
+                );
             }
             Entry::Vacant(entry) => {
                 entry.insert(edit);
@@ -64,12 +87,34 @@ impl SourceChange {
         self.file_system_edits.push(edit);
     }
 
+    pub fn insert_annotation(&mut self, annotation: ChangeAnnotation) -> ChangeAnnotationId {
+        let id = self
+            .annotations
+            .keys()
+            .map(|it| ChangeAnnotationId(it.0 + 1))
+            .max_by_key(|it| it.0)
+            .unwrap_or(ChangeAnnotationId(0));
+        self.annotations.insert(id, annotation);
+        id
+    }
+
     pub fn get_source_edit(&self, file_id: FileId) -> Option<&TextEdit> {
         self.source_file_edits.get(&file_id)
     }
 
     pub fn merge(mut self, other: SourceChange) -> SourceChange {
-        self.extend(other.source_file_edits);
+        // annotation ids are only unique within the change, re-insert the ones of `other`
+        let annotation_ids = other
+            .annotations
+            .into_iter()
+            .map(|(id, annotation)| (id, self.insert_annotation(annotation)))
+            .collect::<HashMap<_, _>>();
+        self.extend(other.source_file_edits.into_iter().map(|(file_id, mut edit)| {
+            if let Some(id) = edit.change_annotation() {
+                edit.set_annotation(annotation_ids.get(&id).copied());
+            }
+            (file_id, edit)
+        }));
         self.extend(other.file_system_edits);
         self
     }
@@ -97,6 +142,7 @@ impl From<HashMap<FileId, TextEdit>> for SourceChange {
         SourceChange {
             source_file_edits,
             file_system_edits: Vec::new(),
+            annotations: HashMap::default(),
         }
     }
 }
@@ -284,6 +330,56 @@ impl From<FileSystemEdit> for SourceChange {
         SourceChange {
             source_file_edits: Default::default(),
             file_system_edits: vec![edit],
+            annotations: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(label: &str) -> ChangeAnnotation {
+        ChangeAnnotation {
+            label: label.to_owned(),
+            needs_confirmation: true,
+            description: None,
+        }
+    }
+
+    fn annotated_change(file_id: FileId, label: &str) -> SourceChange {
+        let mut change = SourceChange::default();
+        let id = change.insert_annotation(annotation(label));
+        let mut edit = TextEdit::insert(0.into(), label.to_owned());
+        edit.set_annotation(Some(id));
+        change.insert_source_edit(file_id, edit);
+        change
+    }
+
+    #[test]
+    fn test_merge_keeps_annotations_of_both_changes() {
+        let (file_a, file_b) = (FileId::from_raw(0), FileId::from_raw(1));
+        let merged = annotated_change(file_a, "a").merge(annotated_change(file_b, "b"));
+
+        assert_eq!(merged.annotations.len(), 2);
+        for (file_id, label) in [(file_a, "a"), (file_b, "b")] {
+            let id = merged.source_file_edits[&file_id].change_annotation().unwrap();
+            assert_eq!(merged.annotations[&id].label, label);
         }
     }
+
+    #[test]
+    fn test_no_union_of_differently_annotated_edits() {
+        let mut change = SourceChange::default();
+        let (id_a, id_b) = (
+            change.insert_annotation(annotation("a")),
+            change.insert_annotation(annotation("b")),
+        );
+        let mut edit = TextEdit::insert(0.into(), "a".to_owned());
+        edit.set_annotation(Some(id_a));
+        let mut other = TextEdit::insert(1.into(), "b".to_owned());
+        other.set_annotation(Some(id_b));
+        assert!(edit.union(other).is_err());
+        assert_eq!(edit.change_annotation(), Some(id_a));
+    }
 }
@@ -30,6 +30,7 @@ mod collector;
 pub mod sym_db;
 
 use crate::RootDatabase;
+use crate::library_cache;
 use crate::symbol_index::sym_db::{FileSymbol, SymbolIndex};
 use base_db::SourceDatabase;
 use fst::Streamer;
@@ -49,7 +50,7 @@ pub fn world_symbols(db: &RootDatabase, query: Query) -> Vec<FileSymbol> {
         .all_package_ids()
         .data(db)
         .iter()
-        .map(|package_id| sym_db::world_symbols_in_package(db, *package_id))
+        .map(|package_id| library_cache::package_symbol_index(db, *package_id))
         .collect::<Vec<_>>();
 
     let mut res = vec![];
@@ -7,10 +7,9 @@
 use crate::symbol_index::sym_db::FileSymbol;
 use base_db::SourceDatabase;
 use indexmap::IndexSet;
-use lang::loc::SyntaxLocFileExt;
 use lang::node_ext::ModuleLangExt;
-use syntax::ast;
 use syntax::files::InFile;
+use syntax::{AstNode, ast};
 
 pub struct SymbolCollector<'a> {
     db: &'a dyn SourceDatabase,
@@ -89,9 +88,12 @@ impl<'a> SymbolCollector<'a> {
             }
             // collect fields for variants
         }
+        let item_syntax = named_item.value.syntax();
         self.symbols.insert(FileSymbol {
             name: item_name,
-            syntax_loc: named_item.loc(),
+            file_id: named_item.file_id,
+            kind: item_syntax.kind(),
+            range: item_syntax.text_range(),
             container_name,
         });
         Some(())
@@ -5,14 +5,18 @@
 // Modifications have been made to the original code.
 
 use crate::symbol_index::collector::SymbolCollector;
-use base_db::SourceDatabase;
+use base_db::inputs::InternFileId;
+use base_db::memory_counters::TrackedSize;
 use base_db::package_root::PackageId;
+use base_db::{SourceDatabase, source_db};
 use lang::hir_db;
 use lang::loc::SyntaxLoc;
 use std::cmp::Ordering;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use syntax::{AstNode, NodeOrToken, SyntaxKind, TextRange};
+use vfs::FileId;
 
 #[salsa_macros::tracked]
 pub fn world_symbols_in_package(db: &dyn SourceDatabase, package_id: PackageId) -> Arc<SymbolIndex> {
@@ -24,13 +28,14 @@ pub fn world_symbols_in_package(db: &dyn SourceDatabase, package_id: PackageId)
         symbol_collector.collect_module(module);
     }
 
-    Arc::new(SymbolIndex::new(symbol_collector.finish()))
+    Arc::new(SymbolIndex::new(symbol_collector.finish()).tracked(db, package_id))
 }
 
 #[derive(Default)]
 pub struct SymbolIndex {
     pub(crate) symbols: Box<[FileSymbol]>,
     pub(crate) map: fst::Map<Vec<u8>>,
+    memory_size: Option<TrackedSize<PackageId>>,
 }
 
 impl fmt::Debug for SymbolIndex {
@@ -56,7 +61,7 @@ impl Hash for SymbolIndex {
 }
 
 impl SymbolIndex {
-    fn new(mut symbols: Box<[FileSymbol]>) -> SymbolIndex {
+    pub(crate) fn new(mut symbols: Box<[FileSymbol]>) -> SymbolIndex {
         fn cmp(lhs: &FileSymbol, rhs: &FileSymbol) -> Ordering {
             let lhs_chars = lhs.name.as_str().chars().map(|c| c.to_ascii_lowercase());
             let rhs_chars = rhs.name.as_str().chars().map(|c| c.to_ascii_lowercase());
@@ -95,7 +100,18 @@ impl SymbolIndex {
                 })
             })
             .unwrap();
-        SymbolIndex { symbols, map }
+        SymbolIndex {
+            symbols,
+            map,
+            memory_size: None,
+        }
+    }
+
+    /// Counts the index in the memory usage of the database until it's dropped.
+    pub(crate) fn tracked(mut self, db: &dyn SourceDatabase, package_id: PackageId) -> SymbolIndex {
+        let bytes = self.memory_size();
+        self.memory_size = Some(db.memory_counters().symbol_indices.track(package_id, bytes));
+        self
     }
 
     pub fn len(&self) -> usize {
@@ -125,10 +141,31 @@ impl SymbolIndex {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FileSymbol {
     pub name: String,
-    pub syntax_loc: SyntaxLoc,
+    pub file_id: FileId,
+    /// Kind and range of the named item, resolved into the syntax node on demand,
+    /// which allows restoring the symbols from the on-disk cache without parsing.
+    pub kind: SyntaxKind,
+    pub range: TextRange,
     pub container_name: Option<String>,
     // /// Whether this symbol is a doc alias for the original symbol.
     // pub is_alias: bool,
     // pub is_import: bool,
     // pub do_not_complete: Complete,
 }
+
+impl FileSymbol {
+    pub fn syntax_loc(&self, db: &dyn SourceDatabase) -> Option<SyntaxLoc> {
+        let file = source_db::parse(db, self.file_id.intern(db)).tree();
+        if !file.syntax().text_range().contains_range(self.range) {
+            return None;
+        }
+        let covering_node = match file.syntax().covering_element(self.range) {
+            NodeOrToken::Node(node) => node,
+            NodeOrToken::Token(token) => token.parent()?,
+        };
+        let node = covering_node
+            .ancestors()
+            .find(|it| it.kind() == self.kind && it.text_range() == self.range)?;
+        Some(SyntaxLoc::new(self.file_id, &node))
+    }
+}
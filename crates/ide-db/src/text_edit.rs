@@ -4,6 +4,7 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
+use crate::source_change::ChangeAnnotationId;
 use itertools::Itertools;
 use std::cmp::max;
 use syntax::{TextRange, TextSize};
@@ -22,6 +23,7 @@ pub struct TextChange {
 pub struct TextEdit {
     /// Invariant: disjoint and sorted by `range`.
     changes: Vec<TextChange>,
+    annotation: Option<ChangeAnnotationId>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -122,7 +124,12 @@ impl TextEdit {
         assert_eq!(TextSize::of(&*text), total_len);
     }
 
+    /// Edits annotated differently are not merged, as the edit carries a single annotation.
     pub fn union(&mut self, other: TextEdit) -> Result<(), TextEdit> {
+        if self.annotation.is_some() && other.annotation.is_some() && self.annotation != other.annotation
+        {
+            return Err(other);
+        }
         let iter_merge = self
             .iter()
             .merge_by(other.iter(), |l, r| l.range.start() <= r.range.start());
@@ -135,9 +142,18 @@ impl TextEdit {
             .dedup_by(|a, b| a == b && !a.range.is_empty())
             .cloned()
             .collect();
+        self.annotation = other.annotation.or(self.annotation);
         Ok(())
     }
 
+    pub fn set_annotation(&mut self, annotation: Option<ChangeAnnotationId>) {
+        self.annotation = annotation;
+    }
+
+    pub fn change_annotation(&self) -> Option<ChangeAnnotationId> {
+        self.annotation
+    }
+
     pub fn apply_to_offset(&self, offset: TextSize) -> Option<TextSize> {
         let mut res = offset;
         for indel in &self.changes {
@@ -189,7 +205,7 @@ impl TextEditBuilder {
         let mut changes = self.text_changes;
         assert_disjoint_or_equal(&mut changes);
         changes = coalesce_text_changes(changes);
-        TextEdit { changes }
+        TextEdit { changes, annotation: None }
     }
     pub fn invalidates_offset(&self, offset: TextSize) -> bool {
         self.text_changes
@@ -13,6 +13,8 @@ pub struct DiagnosticsConfig {
     pub disabled: HashSet<String>,
     pub enable_only: HashSet<String>,
     pub needs_type_annotation: bool,
+    /// Whether to search the package for the usages of the private and test-only items.
+    pub unused_items: bool,
     pub assists_only: bool,
 }
 
@@ -23,6 +25,7 @@ impl DiagnosticsConfig {
             disabled: Default::default(),
             enable_only: Default::default(),
             needs_type_annotation: true,
+            unused_items: false,
             assists_only: false,
         }
     }
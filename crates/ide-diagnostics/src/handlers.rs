@@ -5,13 +5,17 @@
 // Modifications have been made to the original code.
 
 pub(crate) mod ability_checking;
+pub(crate) mod attr_checking;
 pub(crate) mod call_params;
 mod can_be_replaced_with_compound_expr;
 mod can_be_replaced_with_index_expr;
 mod can_be_replaced_with_method_call;
 pub(crate) mod check_syntax;
+pub(crate) mod const_eval;
+pub(crate) mod control_flow_checking;
 pub(crate) mod error_const_docs;
 pub(crate) mod field_shorthand;
+pub(crate) mod match_checking;
 pub(crate) mod missing_fields;
 pub(crate) mod missing_type_arguments;
 mod reduced_scope_import;
@@ -20,12 +24,15 @@ pub(crate) mod replace_with_friend;
 pub(crate) mod replace_with_package;
 pub(crate) mod replace_with_resource_index_expr;
 pub(crate) mod simplify_turbofish;
+pub(crate) mod spec_checking;
 pub(crate) mod spec_global_replace_with_index_expr;
 mod type_checking;
 mod unresolved_reference;
 pub(crate) mod unused_acquires;
 pub(crate) mod unused_import;
+pub(crate) mod unused_items;
 pub(crate) mod unused_variables;
+pub(crate) mod visibility_checking;
 
 pub(crate) use can_be_replaced_with_compound_expr::can_be_replaced_with_compound_expr;
 pub(crate) use can_be_replaced_with_index_expr::can_be_replaced_with_index_expr;
@@ -1,9 +1,12 @@
 use crate::DiagnosticsContext;
 use crate::diagnostic::Diagnostic;
+use lang::nameres::scope::VecExt;
+use lang::node_ext::ModuleLangExt;
 use lang::types::abilities::Ability;
 use lang::types::fold::TypeFoldable;
-use syntax::ast;
-use syntax::files::InFile;
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::files::{InFile, InFileExt};
+use syntax::{AstNode, ast};
 
 pub(crate) fn struct_field_type_ability_check(
     acc: &mut Vec<Diagnostic>,
@@ -49,3 +52,41 @@ pub(crate) fn struct_field_type_ability_check(
 
     Some(())
 }
+
+pub(crate) fn spec_resource_type_ability_check(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    call_expr: InFile<ast::CallExpr>,
+) -> Option<()> {
+    let _p = tracing::debug_span!("spec_resource_type_ability_check").entered();
+
+    let (file_id, call_expr) = call_expr.unpack();
+    if !call_expr.syntax().is_msl_context() {
+        return None;
+    }
+    let call_path = call_expr.path()?;
+    if !matches!(call_path.reference_name()?.as_str(), "global" | "exists") {
+        return None;
+    }
+    let fun = ctx
+        .sema
+        .resolve_to_element::<ast::AnyFun>(call_path.reference().in_file(file_id))?;
+    if !ctx.sema.fun_module(fun)?.value.is_builtins() {
+        return None;
+    }
+
+    let arg_type = call_path.type_args().single_or_none()?.type_()?;
+    let arg_ty = ctx.sema.lower_type(arg_type.clone().in_file(file_id), true);
+    if arg_ty.has_ty_type_param() || arg_ty.has_ty_infer() {
+        return None;
+    }
+    if arg_ty.abilities(ctx.sema.db)?.contains(&Ability::Key) {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "missing-ability",
+        format!("Missing required ability `{}`", Ability::Key),
+        arg_type.in_file(file_id).file_range(),
+    ));
+    Some(())
+}
@@ -0,0 +1,201 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use crate::handlers::spec_checking::SIDE_EFFECT_BUILTINS;
+use ide_db::attrs::find_attr_def;
+use lang::node_ext::ModuleLangExt;
+use std::collections::HashSet;
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::{AstNode, SyntaxNode, ast};
+
+pub(crate) fn unknown_attribute(
+    acc: &mut Vec<Diagnostic>,
+    _ctx: &DiagnosticsContext<'_>,
+    attr_item: InFile<ast::AttrItem>,
+) -> Option<()> {
+    let (file_id, attr_item) = attr_item.unpack();
+    if !attr_item.is_top_level() {
+        return None;
+    }
+    let path = attr_item.path()?;
+    // qualified attributes like `#[lint::my_lint]` belong to external tools
+    if path.qualifier().is_some() {
+        return None;
+    }
+    let attr_name = path.reference_name()?;
+    if find_attr_def(&attr_name).is_some() {
+        return None;
+    }
+    acc.push(Diagnostic::warning(
+        "unknown-attribute",
+        format!("Unknown attribute `{attr_name}`"),
+        FileRange {
+            file_id,
+            range: path.syntax().text_range(),
+        },
+    ));
+    Some(())
+}
+
+pub(crate) fn view_fun_attr(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    fun: InFile<ast::Fun>,
+) -> Option<()> {
+    let (file_id, fun) = fun.unpack();
+    let view_attr_item = find_attr_item(fun.syntax(), "view")?;
+
+    if fun.return_type().is_none() {
+        acc.push(Diagnostic::error(
+            "view-fun-without-return-type",
+            "View functions must return a value",
+            FileRange {
+                file_id,
+                range: view_attr_item.syntax().text_range(),
+            },
+        ));
+    }
+
+    let body = fun.body()?;
+    for call_expr in body.syntax().descendants().filter_map(ast::CallExpr::cast) {
+        let Some(path) = call_expr.path() else {
+            continue;
+        };
+        let Some(name) = path.reference_name() else {
+            continue;
+        };
+        if !SIDE_EFFECT_BUILTINS.contains(&name.as_str()) {
+            continue;
+        }
+        let is_builtin = ctx
+            .sema
+            .resolve_to_element::<ast::AnyFun>(path.reference().in_file(file_id))
+            .and_then(|fun| ctx.sema.fun_module(fun))
+            .is_some_and(|module| module.value.is_builtins());
+        if !is_builtin {
+            continue;
+        }
+        acc.push(Diagnostic::error(
+            "view-fun-modifies-state",
+            format!("View functions cannot modify global storage, `{name}` is not allowed"),
+            FileRange {
+                file_id,
+                range: path.syntax().text_range(),
+            },
+        ));
+    }
+    Some(())
+}
+
+pub(crate) fn event_struct_attr(
+    acc: &mut Vec<Diagnostic>,
+    _ctx: &DiagnosticsContext<'_>,
+    struct_or_enum: InFile<ast::StructOrEnum>,
+) -> Option<()> {
+    let (file_id, struct_or_enum) = struct_or_enum.unpack();
+    let event_attr_item = find_attr_item(struct_or_enum.syntax(), "event")?;
+
+    let abilities = struct_or_enum
+        .abilities()
+        .into_iter()
+        .map(|it| it.ident_token().text().to_string())
+        .collect::<HashSet<_>>();
+    let missing_abilities = ["drop", "store"]
+        .into_iter()
+        .filter(|it| !abilities.contains(*it))
+        .map(|it| format!("`{it}`"))
+        .collect::<Vec<_>>();
+    if missing_abilities.is_empty() {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "event-missing-abilities",
+        format!(
+            "Event types must have `drop` and `store` abilities, missing {}",
+            missing_abilities.join(", ")
+        ),
+        FileRange {
+            file_id,
+            range: event_attr_item.syntax().text_range(),
+        },
+    ));
+    Some(())
+}
+
+pub(crate) fn test_fun_signers(
+    acc: &mut Vec<Diagnostic>,
+    _ctx: &DiagnosticsContext<'_>,
+    fun: InFile<ast::Fun>,
+) -> Option<()> {
+    let (file_id, fun) = fun.unpack();
+    let test_attr_item = find_attr_item(fun.syntax(), "test")?;
+
+    // `#[test(account = @0x1)]`
+    let signer_items = test_attr_item
+        .attr_item_list()
+        .map(|it| it.attr_items().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|it| {
+            it.initializer().and_then(|it| it.expr()).is_some_and(
+                |expr| matches!(expr, ast::Expr::Literal(lit) if lit.address_lit().is_some()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let signer_names = signer_items
+        .iter()
+        .filter_map(|it| it.path_text())
+        .collect::<HashSet<_>>();
+
+    let params = fun.params_as_bindings();
+    let param_names = params
+        .iter()
+        .filter_map(|it| it.name())
+        .map(|it| it.as_string())
+        .collect::<HashSet<_>>();
+
+    for param in params {
+        let Some(param_name) = param.name().map(|it| it.as_string()) else {
+            continue;
+        };
+        if signer_names.contains(&param_name) {
+            continue;
+        }
+        acc.push(Diagnostic::error(
+            "test-missing-signer",
+            format!("Missing address assignment for the test parameter `{param_name}`"),
+            FileRange {
+                file_id,
+                range: param.syntax().text_range(),
+            },
+        ));
+    }
+    for signer_item in signer_items {
+        let Some(signer_path) = signer_item.path() else {
+            continue;
+        };
+        let signer_name = signer_path.syntax().text().to_string();
+        if param_names.contains(&signer_name) {
+            continue;
+        }
+        acc.push(Diagnostic::error(
+            "test-unknown-signer",
+            format!("Test function has no parameter named `{signer_name}`"),
+            FileRange {
+                file_id,
+                range: signer_path.syntax().text_range(),
+            },
+        ));
+    }
+    Some(())
+}
+
+fn find_attr_item(owner: &SyntaxNode, attr_name: &str) -> Option<ast::AttrItem> {
+    owner
+        .children()
+        .filter_map(ast::Attr::cast)
+        .flat_map(|it| it.attr_items())
+        .find(|it| it.path_text().is_some_and(|it| it == attr_name))
+}
@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use lang::const_eval::{ConstEvalError, evaluate_const};
+use syntax::ast;
+use syntax::files::{FileRange, InFile};
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn const_eval_errors(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    const_: InFile<ast::Const>,
+) -> Option<()> {
+    let file_id = const_.file_id;
+    let diagnostic = match evaluate_const(ctx.sema.db, const_) {
+        Ok(_) | Err(ConstEvalError::NotEvaluated) => return None,
+        Err(ConstEvalError::Overflow { range, kind }) => Diagnostic::error(
+            "const-overflow",
+            format!("Integer overflow in constant expression: result does not fit into `{kind}`"),
+            FileRange { file_id, range },
+        ),
+        Err(ConstEvalError::ShiftOutOfRange { range, kind }) => Diagnostic::error(
+            "const-shift-out-of-range",
+            format!("Shift amount in constant expression is out of range for `{kind}`"),
+            FileRange { file_id, range },
+        ),
+        Err(ConstEvalError::DivisionByZero { range }) => Diagnostic::error(
+            "const-division-by-zero",
+            "Division by zero in constant expression",
+            FileRange { file_id, range },
+        ),
+    };
+    acc.push(diagnostic);
+    Some(())
+}
@@ -0,0 +1,138 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use ide_db::assist_context::LocalAssists;
+use lang::control_flow::ControlFlowGraph;
+use syntax::SyntaxKind::WHITESPACE;
+use syntax::ast::HasStmts;
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::{AstNode, ast};
+use vfs::FileId;
+
+pub(crate) fn control_flow(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    fun: InFile<ast::Fun>,
+) -> Option<()> {
+    let (file_id, fun) = fun.unpack();
+    let body = fun.body()?;
+    let cfg = ControlFlowGraph::for_fun(&fun)?;
+
+    unreachable_code(acc, file_id, &body, &cfg);
+    missing_return_value(acc, file_id, &fun, &body, &cfg);
+    unused_labels(acc, ctx, file_id, &cfg);
+    Some(())
+}
+
+/// Statements after `return`, `abort`, `break`, `continue` or a `loop` without `break`.
+fn unreachable_code(
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    body: &ast::BlockExpr,
+    cfg: &ControlFlowGraph,
+) {
+    let blocks = body.syntax().descendants().filter_map(ast::BlockExpr::cast);
+    for block in blocks {
+        let mut elements = block.stmts().map(|it| it.syntax().clone()).collect::<Vec<_>>();
+        elements.extend(block.tail_expr().map(|it| it.syntax().clone()));
+        // only the first statement is reported, the block itself is reported if it's unreachable
+        let first_unreachable = elements
+            .iter()
+            .position(|it| cfg.is_block_element_reachable(it) == Some(false));
+        if let Some(idx) = first_unreachable
+            && idx > 0
+        {
+            acc.push(
+                Diagnostic::warning(
+                    "unreachable-code",
+                    "Unreachable code",
+                    FileRange {
+                        file_id,
+                        range: elements[idx].text_range(),
+                    },
+                )
+                .with_unused(true),
+            );
+        }
+    }
+}
+
+/// Function with the return type, which body can complete without a value.
+fn missing_return_value(
+    acc: &mut Vec<Diagnostic>,
+    file_id: FileId,
+    fun: &ast::Fun,
+    body: &ast::BlockExpr,
+    cfg: &ControlFlowGraph,
+) -> Option<()> {
+    let ret_type = fun.ret_type()?.type_()?;
+    if matches!(ret_type, ast::Type::UnitType(_)) {
+        return None;
+    }
+    if body.tail_expr().is_some() || !cfg.can_complete_normally() {
+        return None;
+    }
+    let r_curly = body.r_curly_token()?;
+    acc.push(Diagnostic::error(
+        "missing-return-value",
+        format!("Missing return value of type `{}`", ret_type.syntax().text()),
+        FileRange {
+            file_id,
+            range: r_curly.text_range(),
+        },
+    ));
+    Some(())
+}
+
+fn unused_labels(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    file_id: FileId,
+    cfg: &ControlFlowGraph,
+) {
+    for label_decl in cfg.unused_labels() {
+        let label_range = FileRange {
+            file_id,
+            range: label_decl.quote_ident_token().text_range(),
+        };
+        acc.push(
+            Diagnostic::warning(
+                "unused-label",
+                format!("Unused label `{}`", label_decl.name_as_string()),
+                label_range,
+            )
+            .with_unused(true)
+            .with_local_fixes(remove_label_fix(
+                ctx,
+                label_decl.in_file(file_id),
+                label_range,
+            )),
+        );
+    }
+}
+
+fn remove_label_fix(
+    ctx: &DiagnosticsContext<'_>,
+    label_decl: InFile<ast::LabelDecl>,
+    diagnostic_range: FileRange,
+) -> Option<LocalAssists> {
+    let mut assists = ctx.local_assists_for_node(label_decl.as_ref())?;
+    assists.add_fix(
+        "remove-unused-label",
+        "Remove unused label",
+        diagnostic_range.range,
+        |editor| {
+            let label_decl = label_decl.value;
+            let next_ws_sibling = label_decl.syntax().next_sibling_or_token();
+            editor.delete(label_decl.syntax());
+            if let Some(next_ws_sibling) = next_ws_sibling
+                && next_ws_sibling.kind() == WHITESPACE
+            {
+                editor.delete(next_ws_sibling);
+            }
+        },
+    );
+    Some(assists)
+}
@@ -0,0 +1,341 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod usefulness;
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use crate::handlers::match_checking::usefulness::{
+    Ctor, FieldsShape, Pat, PatTy, VariantShape, is_useful, missing_patterns,
+};
+use ide_db::assist_context::LocalAssists;
+use lang::types::ty::Ty;
+use syntax::ast::edit::IndentLevel;
+use syntax::ast::node_ext::struct_pat_field::PatFieldKind;
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::syntax_factory::SyntaxFactory;
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::syntax_editor::{Element, Position};
+use syntax::{AstNode, T, ast};
+use vfs::FileId;
+
+const MAX_MISSING_PATTERNS_IN_MESSAGE: usize = 3;
+
+/// Non-exhaustive `match` expressions and unreachable match arms.
+pub(crate) fn match_exhaustiveness(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    match_expr: InFile<ast::MatchExpr>,
+) -> Option<()> {
+    let (file_id, match_expr) = match_expr.unpack();
+    if match_expr.syntax().is_msl_context() {
+        return None;
+    }
+    let scrutinee = match_expr.expr()?;
+    let scrutinee_ty = ctx.sema.get_expr_type(&scrutinee.clone().in_file(file_id))?;
+    let scrutinee_pat_ty = lower_ty(ctx, scrutinee_ty.unwrap_all_refs())?;
+
+    // patterns with unresolved references are not checked, to not report misleading errors
+    let arms = match_expr.arms();
+    let mut arm_pats = vec![];
+    for arm in &arms {
+        let pat = lower_pat(ctx, file_id, arm.pat()?)?;
+        arm_pats.push((arm, pat));
+    }
+
+    let mut rows = vec![];
+    for (arm, pat) in arm_pats {
+        if !is_useful(&rows, &pat) {
+            let pat_range = arm.pat()?.syntax().text_range();
+            let message = match pat {
+                Pat::Wild => "Redundant wildcard arm: all values are already matched",
+                Pat::Ctor { .. } => "Unreachable match arm",
+            };
+            acc.push(
+                Diagnostic::warning(
+                    "unreachable-match-arm",
+                    message,
+                    FileRange { file_id, range: pat_range },
+                )
+                .with_unused(true),
+            );
+        }
+        // guarded arms might not match
+        if arm.match_guard().is_none() {
+            rows.push(pat);
+        }
+    }
+
+    let missing = missing_patterns(&rows, Some(&scrutinee_pat_ty));
+    if missing.is_empty() {
+        return None;
+    }
+    let missing_texts = missing.iter().map(|it| it.render()).collect::<Vec<_>>();
+    let mut message = format!(
+        "Non-exhaustive `match`: missing {}",
+        missing_texts
+            .iter()
+            .take(MAX_MISSING_PATTERNS_IN_MESSAGE)
+            .map(|it| format!("`{it}`"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if missing_texts.len() > MAX_MISSING_PATTERNS_IN_MESSAGE {
+        stdx::format_to!(
+            message,
+            " and {} more",
+            missing_texts.len() - MAX_MISSING_PATTERNS_IN_MESSAGE
+        );
+    }
+    let scrutinee_range = FileRange {
+        file_id,
+        range: scrutinee.syntax().text_range(),
+    };
+    acc.push(
+        Diagnostic::error("non-exhaustive-match", message, scrutinee_range).with_local_fixes(
+            add_missing_arms_fix(ctx, match_expr.in_file(file_id), missing_texts, scrutinee_range),
+        ),
+    );
+    Some(())
+}
+
+fn add_missing_arms_fix(
+    ctx: &DiagnosticsContext<'_>,
+    match_expr: InFile<ast::MatchExpr>,
+    missing_texts: Vec<String>,
+    diagnostic_range: FileRange,
+) -> Option<LocalAssists> {
+    let match_arm_list = match_expr.value.match_arm_list()?;
+    let l_curly = match_arm_list.l_curly_token()?;
+    let last_arm = match_expr.value.arms().pop();
+
+    let mut assists = ctx.local_assists_for_node(match_expr.as_ref())?;
+    assists.add_fix(
+        "add-missing-match-arms",
+        "Add missing match arms",
+        diagnostic_range.range,
+        |editor| {
+            let make = SyntaxFactory::new();
+            let indent = IndentLevel::from_node(match_expr.value.syntax()) + 1;
+            let mut elements = vec![];
+            if let Some(last_arm) = &last_arm
+                && last_arm.comma_token().is_none()
+                && !matches!(last_arm.expr(), Some(ast::Expr::BlockExpr(_)))
+            {
+                elements.push(make.token(T![,]).syntax_element());
+            }
+            for missing_text in missing_texts {
+                let arm = make.match_arm(&missing_text, "abort 0");
+                elements.push(make.whitespace(&format!("\n{indent}")).syntax_element());
+                elements.push(arm.syntax().syntax_element());
+            }
+            let anchor = match &last_arm {
+                Some(last_arm) => last_arm.syntax().syntax_element(),
+                None => {
+                    // `match (x) {}`
+                    let outer_indent = IndentLevel::from_node(match_expr.value.syntax());
+                    elements.push(make.whitespace(&format!("\n{outer_indent}")).syntax_element());
+                    l_curly.syntax_element()
+                }
+            };
+            editor.insert_all(Position::after(anchor), elements);
+            editor.add_mappings(make.finish_with_mappings());
+        },
+    );
+    Some(assists)
+}
+
+fn lower_ty(ctx: &DiagnosticsContext<'_>, ty: Ty) -> Option<PatTy> {
+    let pat_ty = match ty {
+        Ty::Unknown | Ty::Infer(_) | Ty::TypeParam(_) => return None,
+        Ty::Bool => PatTy::Bool,
+        Ty::Unit => PatTy::Tuple(0),
+        Ty::Tuple(ty_tuple) => PatTy::Tuple(ty_tuple.types.len()),
+        Ty::Adt(ty_adt) => {
+            let adt_item = ty_adt.adt_item(ctx.sema.db)?;
+            match adt_item.value {
+                ast::StructOrEnum::Enum(enum_) => enum_pat_ty(&enum_)?,
+                ast::StructOrEnum::Struct(struct_) => PatTy::Struct {
+                    name: struct_.name()?.as_string(),
+                    fields: fields_shape(&struct_.into()),
+                },
+            }
+        }
+        _ => PatTy::Other,
+    };
+    Some(pat_ty)
+}
+
+/// Returns `None` if the pattern cannot be analyzed.
+fn lower_pat(ctx: &DiagnosticsContext<'_>, file_id: FileId, pat: ast::Pat) -> Option<Pat> {
+    let pat = match pat {
+        ast::Pat::WildcardPat(_) | ast::Pat::RestPat(_) => Pat::Wild,
+        ast::Pat::ParenPat(paren_pat) => lower_pat(ctx, file_id, paren_pat.pat()?)?,
+        ast::Pat::UnitPat(_) => Pat::Ctor {
+            ctor: Ctor::Single,
+            ty: PatTy::Tuple(0),
+            fields: vec![],
+        },
+        ast::Pat::LiteralPat(literal_pat) => match literal_pat.literal().kind() {
+            ast::LiteralKind::Bool(value) => Pat::Ctor {
+                ctor: Ctor::Bool(value),
+                ty: PatTy::Bool,
+                fields: vec![],
+            },
+            _ => opaque_pat(literal_pat.syntax().text().to_string()),
+        },
+        ast::Pat::RangePat(range_pat) => opaque_pat(range_pat.syntax().text().to_string()),
+        ast::Pat::IdentPat(ident_pat) => {
+            // either a binding or a variant without fields
+            let variant = ctx
+                .sema
+                .resolve_to_element::<ast::Variant>(ident_pat.in_file(file_id));
+            match variant {
+                Some(variant) => variant_pat(&variant.value, vec![])?,
+                None => Pat::Wild,
+            }
+        }
+        ast::Pat::PathPat(path_pat) => {
+            let element = ctx
+                .sema
+                .resolve_to_element::<ast::NamedElement>(path_pat.path().in_file(file_id))?;
+            match element.value {
+                ast::NamedElement::Variant(variant) => variant_pat(&variant, vec![])?,
+                ast::NamedElement::Const(_) => opaque_pat(path_pat.syntax().text().to_string()),
+                _ => return None,
+            }
+        }
+        ast::Pat::StructPat(struct_pat) => {
+            let fields_owner = ctx
+                .sema
+                .resolve_to_element::<ast::FieldsOwner>(struct_pat.path().in_file(file_id))?
+                .value;
+            let mut fields = vec![];
+            for named_field in fields_owner.named_fields() {
+                let field_name = named_field.field_name().as_string();
+                let pat_field = struct_pat
+                    .fields()
+                    .into_iter()
+                    .find(|it| it.field_name().as_ref() == Some(&field_name));
+                let field_pat = match pat_field.map(|it| it.field_kind()) {
+                    Some(PatFieldKind::Full { pat: Some(pat), .. }) => lower_pat(ctx, file_id, pat)?,
+                    _ => Pat::Wild,
+                };
+                fields.push(field_pat);
+            }
+            fields_owner_pat(&fields_owner, fields)?
+        }
+        ast::Pat::TupleStructPat(tuple_struct_pat) => {
+            let fields_owner = ctx
+                .sema
+                .resolve_to_element::<ast::FieldsOwner>(tuple_struct_pat.path().in_file(file_id))?
+                .value;
+            let arity = fields_owner.tuple_fields().len();
+            let pats = tuple_struct_pat.fields().collect::<Vec<_>>();
+            let fields = lower_positional_pats(ctx, file_id, pats, arity)?;
+            fields_owner_pat(&fields_owner, fields)?
+        }
+        ast::Pat::TuplePat(tuple_pat) => {
+            let pats = tuple_pat.pats().collect::<Vec<_>>();
+            let has_rest = pats.iter().any(|it| matches!(it, ast::Pat::RestPat(_)));
+            if pats.len() == 1 && !has_rest {
+                // (a)
+                return lower_pat(ctx, file_id, pats.into_iter().next()?);
+            }
+            let arity = if has_rest { pats.len() - 1 } else { pats.len() };
+            Pat::Ctor {
+                ctor: Ctor::Single,
+                ty: PatTy::Tuple(arity),
+                fields: lower_positional_pats(ctx, file_id, pats, arity)?,
+            }
+        }
+    };
+    Some(pat)
+}
+
+/// Positional patterns with the optional `..`, like `S(a, .., b)`.
+fn lower_positional_pats(
+    ctx: &DiagnosticsContext<'_>,
+    file_id: FileId,
+    pats: Vec<ast::Pat>,
+    arity: usize,
+) -> Option<Vec<Pat>> {
+    let rest_idx = pats.iter().position(|it| matches!(it, ast::Pat::RestPat(_)));
+    let (before, after) = match rest_idx {
+        Some(rest_idx) => (pats[..rest_idx].to_vec(), pats[rest_idx + 1..].to_vec()),
+        None => (pats, vec![]),
+    };
+    let mut fields = vec![Pat::Wild; arity];
+    for (idx, pat) in before.into_iter().enumerate().take(arity) {
+        fields[idx] = lower_pat(ctx, file_id, pat)?;
+    }
+    let after_start = arity.saturating_sub(after.len());
+    for (idx, pat) in after.into_iter().enumerate() {
+        if let Some(field) = fields.get_mut(after_start + idx) {
+            *field = lower_pat(ctx, file_id, pat)?;
+        }
+    }
+    Some(fields)
+}
+
+fn fields_owner_pat(fields_owner: &ast::FieldsOwner, fields: Vec<Pat>) -> Option<Pat> {
+    match fields_owner {
+        ast::FieldsOwner::Variant(variant) => variant_pat(variant, fields),
+        ast::FieldsOwner::Struct(struct_) => Some(Pat::Ctor {
+            ctor: Ctor::Single,
+            ty: PatTy::Struct {
+                name: struct_.name()?.as_string(),
+                fields: fields_shape(fields_owner),
+            },
+            fields,
+        }),
+    }
+}
+
+fn variant_pat(variant: &ast::Variant, mut fields: Vec<Pat>) -> Option<Pat> {
+    let enum_ = variant.enum_();
+    let idx = enum_.variants().iter().position(|it| it == variant)?;
+    let ty = enum_pat_ty(&enum_)?;
+    let arity = fields_shape(&variant.clone().into()).len();
+    fields.resize(arity, Pat::Wild);
+    Some(Pat::Ctor {
+        ctor: Ctor::Variant(idx),
+        ty,
+        fields,
+    })
+}
+
+fn enum_pat_ty(enum_: &ast::Enum) -> Option<PatTy> {
+    let variants = enum_
+        .variants()
+        .into_iter()
+        .map(|variant| {
+            Some(VariantShape {
+                name: variant.name()?.as_string(),
+                fields: fields_shape(&variant.into()),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(PatTy::Enum {
+        name: enum_.name()?.as_string(),
+        variants,
+    })
+}
+
+fn fields_shape(fields_owner: &ast::FieldsOwner) -> FieldsShape {
+    if fields_owner.named_field_list().is_some() {
+        return FieldsShape::Named(fields_owner.named_field_names());
+    }
+    if fields_owner.tuple_field_list().is_some() {
+        return FieldsShape::Tuple(fields_owner.tuple_fields().len());
+    }
+    FieldsShape::Unit
+}
+
+fn opaque_pat(text: String) -> Pat {
+    Pat::Ctor {
+        ctor: Ctor::Opaque(text),
+        ty: PatTy::Other,
+        fields: vec![],
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Pattern usefulness, as described in "Warnings for pattern matching" by Luc Maranget.
+//!
+//! A pattern is useful with respect to the list of patterns, if it matches some value
+//! which is not matched by any of the patterns of the list. A match arm is unreachable if its pattern
+//! is not useful with respect to the previous arms, and the match is exhaustive if the wildcard
+//! is not useful with respect to all of the arms.
+
+use std::iter;
+
+/// Constructor of the value the pattern matches on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Ctor {
+    /// Enum variant, by its index in the enum declaration.
+    Variant(usize),
+    /// Struct or tuple, the only constructor of the type.
+    Single,
+    Bool(bool),
+    /// Integer literal, range or constant, compared by its text.
+    Opaque(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FieldsShape {
+    Unit,
+    Tuple(usize),
+    Named(Vec<String>),
+}
+
+impl FieldsShape {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            FieldsShape::Unit => 0,
+            FieldsShape::Tuple(len) => *len,
+            FieldsShape::Named(names) => names.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VariantShape {
+    pub(crate) name: String,
+    pub(crate) fields: FieldsShape,
+}
+
+/// Type of the matched value, as much as it's needed for the analysis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PatTy {
+    Enum {
+        name: String,
+        variants: Vec<VariantShape>,
+    },
+    Struct {
+        name: String,
+        fields: FieldsShape,
+    },
+    Tuple(usize),
+    Bool,
+    /// Integers, addresses and vectors, which cannot be matched exhaustively without a wildcard.
+    Other,
+}
+
+impl PatTy {
+    fn arity(&self, ctor: &Ctor) -> usize {
+        match (self, ctor) {
+            (PatTy::Enum { variants, .. }, Ctor::Variant(idx)) => {
+                variants.get(*idx).map(|it| it.fields.len()).unwrap_or(0)
+            }
+            (PatTy::Struct { fields, .. }, Ctor::Single) => fields.len(),
+            (PatTy::Tuple(len), Ctor::Single) => *len,
+            _ => 0,
+        }
+    }
+
+    /// All constructors of the type, `None` if there are too many to list.
+    fn all_ctors(&self) -> Option<Vec<Ctor>> {
+        match self {
+            PatTy::Enum { variants, .. } => Some((0..variants.len()).map(Ctor::Variant).collect()),
+            PatTy::Struct { .. } | PatTy::Tuple(_) => Some(vec![Ctor::Single]),
+            PatTy::Bool => Some(vec![Ctor::Bool(false), Ctor::Bool(true)]),
+            PatTy::Other => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Pat {
+    Wild,
+    Ctor { ctor: Ctor, ty: PatTy, fields: Vec<Pat> },
+}
+
+impl Pat {
+    fn ctor_with_wild_fields(ctor: Ctor, ty: &PatTy) -> Pat {
+        let fields = iter::repeat_n(Pat::Wild, ty.arity(&ctor)).collect();
+        Pat::Ctor { ctor, ty: ty.clone(), fields }
+    }
+
+    /// Renders the pattern in the source form, `Color::Rgb(..)`.
+    pub(crate) fn render(&self) -> String {
+        let Pat::Ctor { ctor, ty, fields } = self else {
+            return "_".to_string();
+        };
+        match (ty, ctor) {
+            (PatTy::Enum { name, variants }, Ctor::Variant(idx)) => {
+                let Some(variant) = variants.get(*idx) else {
+                    return "_".to_string();
+                };
+                render_fields(&format!("{name}::{}", variant.name), &variant.fields, fields)
+            }
+            (PatTy::Struct { name, fields: shape }, _) => render_fields(name, shape, fields),
+            (PatTy::Tuple(_), _) => {
+                format!(
+                    "({})",
+                    fields.iter().map(|it| it.render()).collect::<Vec<_>>().join(", ")
+                )
+            }
+            (_, Ctor::Bool(value)) => value.to_string(),
+            (_, Ctor::Opaque(text)) => text.clone(),
+            _ => "_".to_string(),
+        }
+    }
+}
+
+fn render_fields(path: &str, shape: &FieldsShape, fields: &[Pat]) -> String {
+    let all_wild = fields.iter().all(|it| *it == Pat::Wild);
+    match shape {
+        FieldsShape::Unit => path.to_string(),
+        FieldsShape::Tuple(_) if all_wild => format!("{path}(..)"),
+        FieldsShape::Tuple(_) => {
+            let fields = fields.iter().map(|it| it.render()).collect::<Vec<_>>();
+            format!("{path}({})", fields.join(", "))
+        }
+        FieldsShape::Named(_) if all_wild => format!("{path} {{ .. }}"),
+        FieldsShape::Named(names) => {
+            let fields = names
+                .iter()
+                .zip(fields)
+                .filter(|(_, pat)| **pat != Pat::Wild)
+                .map(|(name, pat)| format!("{name}: {}", pat.render()))
+                .collect::<Vec<_>>();
+            format!("{path} {{ {}, .. }}", fields.join(", "))
+        }
+    }
+}
+
+/// Whether the `pat` matches some values which are not matched by any of the `rows`.
+pub(crate) fn is_useful(rows: &[Pat], pat: &Pat) -> bool {
+    let rows = rows.iter().map(|it| vec![it.clone()]).collect::<Vec<_>>();
+    !compute_witnesses(&rows, &[pat.clone()], None).is_empty()
+}
+
+/// Values of the `ty` not matched by any of the `rows`.
+pub(crate) fn missing_patterns(rows: &[Pat], ty: Option<&PatTy>) -> Vec<Pat> {
+    let rows = rows.iter().map(|it| vec![it.clone()]).collect::<Vec<_>>();
+    compute_witnesses(&rows, &[Pat::Wild], ty)
+        .into_iter()
+        .filter_map(|it| it.into_iter().next())
+        .collect()
+}
+
+/// Rows of patterns matched by `v`, but not matched by any of the `rows`.
+/// `ty` is the type of the first column, if it's known.
+fn compute_witnesses(rows: &[Vec<Pat>], v: &[Pat], ty: Option<&PatTy>) -> Vec<Vec<Pat>> {
+    let Some((head, v_tail)) = v.split_first() else {
+        return if rows.is_empty() { vec![vec![]] } else { vec![] };
+    };
+    let column_ty = ty.cloned().or_else(|| {
+        rows.iter()
+            .filter_map(|row| row.first())
+            .chain(iter::once(head))
+            .find_map(|pat| match pat {
+                Pat::Ctor { ty, .. } => Some(ty.clone()),
+                Pat::Wild => None,
+            })
+    });
+
+    match head {
+        Pat::Ctor { ctor, ty, fields } => {
+            let arity = ty.arity(ctor);
+            let specialized_rows = specialize(rows, ctor, arity);
+            let v = fields.iter().chain(v_tail).cloned().collect::<Vec<_>>();
+            compute_witnesses(&specialized_rows, &v, None)
+                .into_iter()
+                .map(|witness| apply_ctor(witness, ctor, ty, arity))
+                .collect()
+        }
+        Pat::Wild => {
+            let present_ctors = rows
+                .iter()
+                .filter_map(|row| match row.first() {
+                    Some(Pat::Ctor { ctor, .. }) => Some(ctor.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            let all_ctors = column_ty.as_ref().and_then(|it| it.all_ctors());
+
+            if let (Some(column_ty), Some(all_ctors)) = (&column_ty, &all_ctors)
+                && all_ctors.iter().all(|it| present_ctors.contains(it))
+            {
+                // every constructor is present, split the wildcard into them
+                let mut witnesses = vec![];
+                for ctor in all_ctors {
+                    let arity = column_ty.arity(ctor);
+                    let specialized_rows = specialize(rows, ctor, arity);
+                    let v = iter::repeat_n(Pat::Wild, arity)
+                        .chain(v_tail.iter().cloned())
+                        .collect::<Vec<_>>();
+                    witnesses.extend(
+                        compute_witnesses(&specialized_rows, &v, None)
+                            .into_iter()
+                            .map(|witness| apply_ctor(witness, ctor, column_ty, arity)),
+                    );
+                }
+                return witnesses;
+            }
+
+            // some constructors are missing, only the rows starting with the wildcard could match them
+            let default_rows = rows
+                .iter()
+                .filter(|row| matches!(row.first(), Some(Pat::Wild)))
+                .map(|row| row[1..].to_vec())
+                .collect::<Vec<_>>();
+            let witnesses = compute_witnesses(&default_rows, v_tail, None);
+            if witnesses.is_empty() {
+                return vec![];
+            }
+            let missing_heads = match (&column_ty, all_ctors) {
+                (Some(column_ty), Some(all_ctors)) => all_ctors
+                    .into_iter()
+                    .filter(|it| !present_ctors.contains(it))
+                    .map(|ctor| Pat::ctor_with_wild_fields(ctor, column_ty))
+                    .collect(),
+                _ => vec![Pat::Wild],
+            };
+            let mut result = vec![];
+            for missing_head in missing_heads {
+                for witness in &witnesses {
+                    result.push(
+                        iter::once(missing_head.clone())
+                            .chain(witness.iter().cloned())
+                            .collect(),
+                    );
+                }
+            }
+            result
+        }
+    }
+}
+
+/// Rows which match the `ctor`, with the first column replaced by the constructor fields.
+fn specialize(rows: &[Vec<Pat>], ctor: &Ctor, arity: usize) -> Vec<Vec<Pat>> {
+    rows.iter()
+        .filter_map(|row| {
+            let (head, tail) = row.split_first()?;
+            let fields = match head {
+                Pat::Wild => vec![Pat::Wild; arity],
+                Pat::Ctor { ctor: head_ctor, fields, .. } if head_ctor == ctor => fields.clone(),
+                Pat::Ctor { .. } => return None,
+            };
+            Some(fields.into_iter().chain(tail.iter().cloned()).collect())
+        })
+        .collect()
+}
+
+/// Collapses the first `arity` patterns of the witness into the constructor pattern.
+fn apply_ctor(witness: Vec<Pat>, ctor: &Ctor, ty: &PatTy, arity: usize) -> Vec<Pat> {
+    let mut witness = witness.into_iter();
+    let fields = witness.by_ref().take(arity).collect();
+    let ctor_pat = Pat::Ctor {
+        ctor: ctor.clone(),
+        ty: ty.clone(),
+        fields,
+    };
+    iter::once(ctor_pat).chain(witness).collect()
+}
@@ -0,0 +1,404 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use ide_db::defs::SPEC_PRAGMAS;
+use lang::loc::{SyntaxLoc, SyntaxLocFileExt};
+use lang::nameres::name_resolution::{WalkScopesCtx, get_entries_from_walking_scopes};
+use lang::nameres::namespaces::NAMES;
+use lang::node_ext::ModuleLangExt;
+use lang::node_ext::item_spec;
+use std::collections::HashSet;
+use syntax::ast::HasItems;
+use syntax::ast::node_ext::spec_predicate_stmt::SpecPredicateKind;
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::{AstNode, SyntaxNode, TextRange, ast};
+
+pub(crate) const SIDE_EFFECT_BUILTINS: [&str; 3] = ["move_to", "move_from", "borrow_global_mut"];
+
+pub(crate) fn old_outside_post_condition(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    call_expr: InFile<ast::CallExpr>,
+) -> Option<()> {
+    let (file_id, call_expr) = call_expr.unpack();
+    if !call_expr.syntax().is_msl_context() {
+        return None;
+    }
+    let path = call_expr.path()?;
+    if path.reference_name()? != "old" {
+        return None;
+    }
+    let spec_fun = ctx
+        .sema
+        .resolve_to_element::<ast::SpecFun>(path.reference().in_file(file_id))?;
+    if !ctx.sema.fun_module(spec_fun)?.value.is_builtins() {
+        return None;
+    }
+
+    if is_in_post_state(call_expr.syntax())? {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "spec-invalid-old",
+        "`old(..)` can only be used in post-conditions",
+        path.in_file(file_id).file_range(),
+    ));
+    Some(())
+}
+
+pub(crate) fn result_outside_ensures(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    path_expr: InFile<ast::PathExpr>,
+) -> Option<()> {
+    let (file_id, path_expr) = path_expr.unpack();
+    let path = path_expr.path();
+    let path_name = path.reference_name()?;
+    if !path_name.starts_with("result") || path.qualifier().is_some() {
+        return None;
+    }
+    if !path_expr.syntax().is_msl_context() {
+        return None;
+    }
+    // shadowed by a local binding
+    if !ctx.sema.resolve(path.reference()).is_empty() {
+        return None;
+    }
+    let path_expr = path_expr.in_file(file_id);
+    item_spec::try_infer_spec_only_path_expr(ctx.sema.db, path_expr.as_ref())?;
+
+    if is_in_post_state(path_expr.value.syntax())? {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "spec-invalid-result",
+        format!("`{path_name}` can only be used in `ensures` conditions"),
+        path.in_file(file_id).file_range(),
+    ));
+    Some(())
+}
+
+/// Returns whether `node` is evaluated in the post-state of the function,
+/// `None` if the condition cannot be determined or the context allows both.
+fn is_in_post_state(node: &SyntaxNode) -> Option<bool> {
+    // inline specs have their own rules
+    if node.has_ancestor_strict::<ast::SpecBlockExpr>() {
+        return None;
+    }
+    for ancestor in node.ancestors().skip(1) {
+        if let Some(predicate) = ast::SpecPredicateStmt::cast(ancestor.clone()) {
+            return match predicate.kind()? {
+                SpecPredicateKind::Ensures => Some(true),
+                SpecPredicateKind::Requires
+                | SpecPredicateKind::Decreases
+                | SpecPredicateKind::Modifies => Some(false),
+                SpecPredicateKind::Assert | SpecPredicateKind::Assume => None,
+            };
+        }
+        if let Some(invariant) = ast::InvariantStmt::cast(ancestor.clone()) {
+            return Some(invariant.update_token().is_some());
+        }
+        if ast::AbortsIfStmt::can_cast(ancestor.kind())
+            || ast::AbortsWithStmt::can_cast(ancestor.kind())
+            || ast::AxiomStmt::can_cast(ancestor.kind())
+        {
+            return Some(false);
+        }
+        if ast::PostStmt::can_cast(ancestor.kind()) {
+            return Some(true);
+        }
+        if let Some(let_stmt) = ast::LetStmt::cast(ancestor.clone()) {
+            // skip `let` statements of the block expressions inside conditions
+            let is_spec_stmt = let_stmt
+                .syntax()
+                .parent()
+                .and_then(|block| block.parent())
+                .is_some_and(|it| {
+                    ast::ItemSpec::can_cast(it.kind()) || ast::Schema::can_cast(it.kind())
+                });
+            if is_spec_stmt {
+                return Some(let_stmt.is_post());
+            }
+        }
+        if ast::Stmt::can_cast(ancestor.kind()) || ast::Item::can_cast(ancestor.kind()) {
+            // `include`, `apply`, `update`, spec functions and schemas
+            if !ast::ExprStmt::can_cast(ancestor.kind()) && !ast::LetStmt::can_cast(ancestor.kind()) {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+pub(crate) fn impure_fun_call_in_spec(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    call_expr: InFile<ast::AnyCallExpr>,
+) -> Option<()> {
+    let (file_id, call_expr) = call_expr.unpack();
+    if !call_expr.syntax().is_msl_context() {
+        return None;
+    }
+    let (reference, name_range) = call_reference(&call_expr)?;
+    let fun = ctx
+        .sema
+        .resolve_to_element::<ast::Fun>(reference.in_file(file_id))?;
+    let fun_name = fun.value.name()?.as_string();
+    if !fun_has_side_effects(ctx, &fun, &mut HashSet::new()) {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "spec-impure-fun-call",
+        format!("Function `{fun_name}` has side effects and cannot be called from specifications"),
+        FileRange { file_id, range: name_range },
+    ));
+    Some(())
+}
+
+fn call_reference(call_expr: &ast::AnyCallExpr) -> Option<(ast::ReferenceElement, TextRange)> {
+    match call_expr {
+        ast::AnyCallExpr::CallExpr(call_expr) => {
+            let path = call_expr.path()?;
+            Some((path.reference(), path.syntax().text_range()))
+        }
+        ast::AnyCallExpr::MethodCallExpr(method_call_expr) => Some((
+            method_call_expr.clone().into(),
+            method_call_expr.name_ref()?.syntax().text_range(),
+        )),
+        _ => None,
+    }
+}
+
+/// Checks the function itself and everything it calls, `visited` guards against recursive calls.
+fn fun_has_side_effects(
+    ctx: &DiagnosticsContext<'_>,
+    fun: &InFile<ast::Fun>,
+    visited: &mut HashSet<SyntaxLoc>,
+) -> bool {
+    if !visited.insert(fun.loc()) {
+        return false;
+    }
+    let is_builtin = ctx
+        .sema
+        .fun_module(fun.clone())
+        .is_some_and(|it| it.value.is_builtins());
+    if is_builtin {
+        return fun
+            .value
+            .name()
+            .is_some_and(|it| SIDE_EFFECT_BUILTINS.contains(&it.as_string().as_str()));
+    }
+    let has_mut_ref_param = fun.value.params().iter().any(|param| {
+        param
+            .type_()
+            .is_some_and(|it| matches!(it, ast::Type::RefType(ref_type) if ref_type.is_mut()))
+    });
+    if has_mut_ref_param {
+        return true;
+    }
+    let Some(body) = fun.value.body() else {
+        return false;
+    };
+    body.syntax()
+        .descendants()
+        .filter_map(ast::AnyCallExpr::cast)
+        .any(|call_expr| {
+            call_reference(&call_expr)
+                .and_then(|(reference, _)| {
+                    ctx.sema
+                        .resolve_to_element::<ast::Fun>(reference.in_file(fun.file_id))
+                })
+                .is_some_and(|callee| fun_has_side_effects(ctx, &callee, visited))
+        })
+}
+
+pub(crate) fn schema_fields_in_include(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    include_schema: InFile<ast::IncludeSchema>,
+) -> Option<()> {
+    let (file_id, include_schema) = include_schema.unpack();
+    let schema_lits = include_schema.include_expr()?.schema_lits();
+    for schema_lit in schema_lits {
+        let Some(schema) = resolve_schema(ctx, schema_lit.clone().in_file(file_id)) else {
+            continue;
+        };
+        unknown_schema_lit_fields(acc, &schema, schema_lit.clone().in_file(file_id));
+
+        for missing_field in schema.missing_fields(schema_lit.fields()) {
+            let Some(field_name) = missing_field.ident_pat().and_then(|it| it.name()) else {
+                continue;
+            };
+            let field_name = field_name.as_string();
+            let walk_ctx = WalkScopesCtx {
+                allowed_ns: NAMES,
+                start_at: include_schema.syntax().clone().in_file(file_id),
+                expected_name: Some(field_name.clone()),
+            };
+            if !get_entries_from_walking_scopes(ctx.sema.db, walk_ctx).is_empty() {
+                continue;
+            }
+            acc.push(Diagnostic::error(
+                "spec-unbound-schema-field",
+                format!("Schema field `{field_name}` is not bound in the including context"),
+                schema_lit.path()?.in_file(file_id).file_range(),
+            ));
+        }
+    }
+    Some(())
+}
+
+pub(crate) fn schema_fields_in_apply(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    apply_schema: InFile<ast::ApplySchema>,
+) -> Option<()> {
+    let (file_id, apply_schema) = apply_schema.unpack();
+    let schema_lit = apply_schema.schema_lit()?;
+    let schema = resolve_schema(ctx, schema_lit.clone().in_file(file_id))?;
+    unknown_schema_lit_fields(acc, &schema, schema_lit.clone().in_file(file_id));
+
+    let module = apply_schema.syntax().containing_module()?;
+    let to_patterns = apply_schema.apply_to_patterns();
+    let except_patterns = apply_schema.apply_except_patterns();
+    let target_funs = module
+        .non_test_functions()
+        .into_iter()
+        .filter(|fun| to_patterns.iter().any(|it| wildcard_matches(it, fun)))
+        .filter(|fun| !except_patterns.iter().any(|it| wildcard_matches(it, fun)))
+        .collect::<Vec<_>>();
+
+    let mut reported = HashSet::new();
+    for missing_field in schema.missing_fields(schema_lit.fields()) {
+        let Some(field_name) = missing_field.ident_pat().and_then(|it| it.name()) else {
+            continue;
+        };
+        let field_name = field_name.as_string();
+        for fun in target_funs.iter() {
+            let has_param = fun
+                .params_as_bindings()
+                .iter()
+                .any(|it| it.name().is_some_and(|name| name.as_string() == field_name));
+            if has_param || !reported.insert(field_name.clone()) {
+                continue;
+            }
+            let fun_name = fun.name().map(|it| it.as_string()).unwrap_or_default();
+            acc.push(Diagnostic::error(
+                "spec-unbound-schema-field",
+                format!("Schema field `{field_name}` is not a parameter of function `{fun_name}`"),
+                schema_lit.path()?.in_file(file_id).file_range(),
+            ));
+        }
+    }
+    Some(())
+}
+
+fn resolve_schema(
+    ctx: &DiagnosticsContext<'_>,
+    schema_lit: InFile<ast::SchemaLit>,
+) -> Option<ast::Schema> {
+    let (file_id, schema_lit) = schema_lit.unpack();
+    let schema = ctx
+        .sema
+        .resolve_to_element::<ast::Schema>(schema_lit.path()?.reference().in_file(file_id))?;
+    Some(schema.value)
+}
+
+fn unknown_schema_lit_fields(
+    acc: &mut Vec<Diagnostic>,
+    schema: &ast::Schema,
+    schema_lit: InFile<ast::SchemaLit>,
+) {
+    let (file_id, schema_lit) = schema_lit.unpack();
+    let declared_names = schema
+        .schema_fields_as_bindings()
+        .into_iter()
+        .filter_map(|it| it.name())
+        .map(|it| it.as_string())
+        .collect::<HashSet<_>>();
+    for lit_field in schema_lit.fields() {
+        // shorthand fields are references to the local bindings, reported as unresolved instead
+        let Some(name_ref) = lit_field.name_ref() else {
+            continue;
+        };
+        let field_name = name_ref.as_string();
+        if declared_names.contains(&field_name) {
+            continue;
+        }
+        let range = name_ref.syntax().text_range();
+        acc.push(Diagnostic::error(
+            "spec-unknown-schema-field",
+            format!(
+                "Unknown field `{field_name}` for schema `{}`",
+                schema_name(schema)
+            ),
+            FileRange { file_id, range },
+        ));
+    }
+}
+
+fn schema_name(schema: &ast::Schema) -> String {
+    schema.name().map(|it| it.as_string()).unwrap_or_default()
+}
+
+fn wildcard_matches(pattern: &ast::WildcardPattern, fun: &ast::Fun) -> bool {
+    if let Some(modifier) = pattern.wildcard_pattern_modifier() {
+        let is_public = fun.visibility_modifier().is_some_and(|it| it.is_public());
+        if modifier.public_token().is_some() != is_public {
+            return false;
+        }
+    }
+    let (Some(wildcard), Some(fun_name)) = (pattern.wildcard_ident_token(), fun.name()) else {
+        return false;
+    };
+    glob_matches(wildcard.text(), &fun_name.as_string())
+}
+
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|i| text.is_char_boundary(*i))
+                .any(|i| glob_matches(rest, &text[i..]))
+        }
+    }
+}
+
+pub(crate) fn unknown_pragma(
+    acc: &mut Vec<Diagnostic>,
+    _ctx: &DiagnosticsContext<'_>,
+    pragma_stmt: InFile<ast::PragmaStmt>,
+) -> Option<()> {
+    let (file_id, pragma_stmt) = pragma_stmt.unpack();
+    let attr_items = pragma_stmt.attr_items().collect::<Vec<_>>();
+    let names = attr_items
+        .iter()
+        .filter_map(|it| it.ident_token())
+        .collect::<Vec<_>>();
+    // intrinsic pragmas map arbitrary names to the native implementations
+    if names.iter().any(|it| it.text() == "intrinsic") {
+        return None;
+    }
+    for name in names {
+        let pragma_name = name.text();
+        if SPEC_PRAGMAS.contains(&pragma_name) || pragma_name.starts_with("map_") {
+            continue;
+        }
+        acc.push(Diagnostic::warning(
+            "spec-unknown-pragma",
+            format!("Unknown pragma `{pragma_name}`"),
+            FileRange {
+                file_id,
+                range: name.text_range(),
+            },
+        ));
+    }
+    Some(())
+}
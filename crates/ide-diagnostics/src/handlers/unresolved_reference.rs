@@ -8,6 +8,7 @@ mod auto_import;
 
 use crate::DiagnosticsContext;
 use crate::diagnostic::Diagnostic;
+use crate::handlers::visibility_checking;
 use base_db::SourceDatabase;
 use ide_db::RootDatabase;
 use lang::nameres::is_visible::{ItemInvisibleReason, ScopeEntryWithVisExt};
@@ -20,7 +21,6 @@ use std::collections::HashSet;
 use syntax::ast::idents::PRIMITIVE_TYPES;
 use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
 use syntax::ast::node_ext::syntax_node::SyntaxNodeExt;
-use syntax::ast::visibility::Vis;
 use syntax::files::{InFile, InFileExt};
 use syntax::{AstNode, ast};
 
@@ -195,7 +195,9 @@ fn try_check_resolve(
         .path()
         .and_then(|it| auto_import::auto_import_fix(ctx, it.in_file(file_id), reference_range));
 
-    let mut resolved_entries = ctx.sema.resolve_in_file_with_reason(reference.in_file(file_id));
+    let mut resolved_entries = ctx
+        .sema
+        .resolve_in_file_with_reason(reference.clone().in_file(file_id));
     let visible_entries = resolved_entries.clone().into_visible_entries();
 
     match visible_entries.len() {
@@ -203,20 +205,19 @@ fn try_check_resolve(
             let mut error = format!("Unresolved reference `{}`: cannot resolve", reference_name);
             if resolved_entries.len() == 1 {
                 // there is an entry, but it's invisible for some reason
-                if let Some(reason) = resolved_entries.pop().unwrap().invis_reason {
+                let entry = resolved_entries.pop().unwrap();
+                if let Some(reason) = entry.invis_reason {
                     match reason {
-                        ItemInvisibleReason::Private { vis } => match vis {
-                            Vis::Restricted(vis_level) => {
-                                stdx::format_to!(
-                                    &mut error,
-                                    " (note: item is defined with `{}` visibility)",
-                                    vis_level
-                                )
-                            }
-                            _ => {
-                                stdx::format_to!(&mut error, " (note: item is private)",)
-                            }
-                        },
+                        ItemInvisibleReason::Private { vis } => {
+                            return visibility_checking::private_item_access(
+                                acc,
+                                ctx,
+                                reference.in_file(file_id).as_ref(),
+                                reference_range,
+                                &entry.scope_entry,
+                                vis,
+                            );
+                        }
                         ItemInvisibleReason::WrongItemScope { item_scope } => {
                             stdx::format_to!(
                                 &mut error,
@@ -0,0 +1,224 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use base_db::SourceDatabase;
+use ide_db::assist_context::LocalAssists;
+use ide_db::search::{SearchScope, item_usages};
+use syntax::SyntaxKind::{ATTR_ITEM, ITEM_SPEC_REF, WHITESPACE};
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::visibility::{Vis, VisLevel};
+use syntax::ast::{HasAttrs, HasVisibility};
+use syntax::files::{FileRange, InFile, InFileExt};
+use syntax::{AstNode, ast};
+
+/// Private, `friend` and `package` functions, private constants, and `#[test_only]` items,
+/// which are not referenced anywhere in the package code or in the specs.
+pub(crate) fn unused_items(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    module: InFile<ast::Module>,
+) -> Option<()> {
+    if !ctx.config.unused_items {
+        return None;
+    }
+    let db = ctx.sema.db;
+    if ctx.sema.is_library(db.file_package_id(module.file_id)) {
+        return None;
+    }
+
+    for fun in module.value.functions() {
+        // called by the VM or by the test runner
+        if fun.is_entry()
+            || fun.is_test()
+            || fun.name().is_some_and(|it| it.as_string() == "init_module")
+        {
+            continue;
+        }
+        unused_item(acc, ctx, fun.in_file(module.file_id), "Function", true);
+    }
+    for const_ in module.value.consts() {
+        unused_item(acc, ctx, const_.in_file(module.file_id), "Constant", true);
+    }
+    // struct types are accessible from the dependent packages, only test-only ones are checked
+    for struct_ in module.value.structs().into_iter().filter(|it| it.is_test_only()) {
+        // members are declared with the attribute argument
+        if struct_.has_attr_item("resource_group") {
+            continue;
+        }
+        unused_item(acc, ctx, struct_.in_file(module.file_id), "Struct", false);
+    }
+    for enum_ in module.value.enums().into_iter().filter(|it| it.is_test_only()) {
+        unused_item(acc, ctx, enum_.in_file(module.file_id), "Enum", false);
+    }
+
+    for friend in module.value.friend_decls() {
+        unused_friend(acc, ctx, &module, friend.in_file(module.file_id));
+    }
+
+    Some(())
+}
+
+fn unused_item<Item: HasAttrs + HasVisibility + Into<ast::NamedElement>>(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    item: InFile<Item>,
+    item_kind: &str,
+    is_module_private: bool,
+) -> Option<()> {
+    let db = ctx.sema.db;
+    let is_test_only = item.value.is_test_only();
+    let scope = match item.value.vis() {
+        Vis::Private if is_module_private => {
+            let module = item.and_then_ref(|it| it.syntax().containing_module())?;
+            SearchScope::from_module_and_module_spec(db, module)
+        }
+        // friends are declared in the same package
+        Vis::Restricted(_) => SearchScope::from_package(db, db.file_package_id(item.file_id)),
+        // test-only items of the dependencies are not compiled with the tests of the dependent package
+        _ if is_test_only => SearchScope::from_package(db, db.file_package_id(item.file_id)),
+        _ => return None,
+    };
+    let item = item.map(|it| it.into());
+    let name = item.value.name()?;
+    if name.as_string().starts_with("_") {
+        return None;
+    }
+    if has_usages(ctx, &item, scope) {
+        return None;
+    }
+    // private constants are allowed in the `abort_code` of the tests in the other modules
+    if let ast::NamedElement::Const(_) = &item.value {
+        let package_scope = SearchScope::from_package(db, db.file_package_id(item.file_id));
+        if has_attr_usages(ctx, &item, package_scope) {
+            return None;
+        }
+    }
+
+    let message = if is_test_only {
+        format!(
+            "Test-only {} `{}` is never used",
+            item_kind.to_lowercase(),
+            name.as_string()
+        )
+    } else {
+        format!("{item_kind} `{}` is never used", name.as_string())
+    };
+    acc.push(
+        Diagnostic::warning("unused-item", message, name.in_file(item.file_id).file_range())
+            .with_unused(true),
+    );
+    Some(())
+}
+
+/// References outside of the item itself, `spec fun_name {}` blocks are not counted.
+fn has_usages(
+    ctx: &DiagnosticsContext<'_>,
+    item: &InFile<ast::NamedElement>,
+    scope: SearchScope,
+) -> bool {
+    let item_range = item.value.syntax().text_range();
+    let mut found = false;
+    item_usages(&ctx.sema, item.clone())
+        .in_scope(scope)
+        .skip_files_without_name()
+        .search(&mut |file_id, reference| {
+            let is_inner_reference =
+                file_id == item.file_id && item_range.contains_range(reference.range);
+            let is_item_spec_ref = reference
+                .name
+                .as_name_ref()
+                .and_then(|it| it.syntax().parent())
+                .is_some_and(|it| it.kind() == ITEM_SPEC_REF);
+            found = !is_inner_reference && !is_item_spec_ref;
+            found
+        });
+    found
+}
+
+/// References inside of the attributes, like `#[expected_failure(abort_code = m::E)]`.
+fn has_attr_usages(
+    ctx: &DiagnosticsContext<'_>,
+    item: &InFile<ast::NamedElement>,
+    scope: SearchScope,
+) -> bool {
+    let mut found = false;
+    item_usages(&ctx.sema, item.clone())
+        .in_scope(scope)
+        .skip_files_without_name()
+        .search(&mut |_, reference| {
+            found = reference
+                .name
+                .as_name_ref()
+                .is_some_and(|it| it.syntax().ancestors().any(|it| it.kind() == ATTR_ITEM));
+            found
+        });
+    found
+}
+
+/// `friend` declaration is unused if the friend module doesn't call any of the friend functions.
+fn unused_friend(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    module: &InFile<ast::Module>,
+    friend: InFile<ast::Friend>,
+) -> Option<()> {
+    let friend_path = friend.and_then_ref(|it| it.path())?;
+    let friend_module = ctx.sema.resolve_to_element::<ast::Module>(friend_path.clone())?;
+    let friend_module_scope = SearchScope::from_file_range(friend_module.file_range());
+
+    let friend_funs = module
+        .value
+        .functions()
+        .into_iter()
+        .filter(|it| it.vis() == Vis::Restricted(VisLevel::Friend));
+    for friend_fun in friend_funs {
+        let friend_fun = friend_fun.in_file(module.file_id).map_into();
+        if item_usages(&ctx.sema, friend_fun)
+            .in_scope(friend_module_scope.clone())
+            .at_least_one()
+        {
+            return None;
+        }
+    }
+
+    let friend_range = friend.file_range();
+    acc.push(
+        Diagnostic::warning(
+            "unused-friend",
+            format!(
+                "Unused friend declaration: `{}` does not call any friend functions",
+                friend_path.value.syntax().text()
+            ),
+            friend_range,
+        )
+        .with_unused(true)
+        .with_local_fixes(remove_friend_fix(ctx, friend, friend_range)),
+    );
+    Some(())
+}
+
+fn remove_friend_fix(
+    ctx: &DiagnosticsContext<'_>,
+    friend: InFile<ast::Friend>,
+    diagnostic_range: FileRange,
+) -> Option<LocalAssists> {
+    let mut assists = ctx.local_assists_for_node(friend.as_ref())?;
+    assists.add_fix(
+        "remove-unused-friend",
+        "Remove unused friend declaration",
+        diagnostic_range.range,
+        |editor| {
+            let friend = friend.value;
+            let next_ws_sibling = friend.syntax().next_sibling_or_token();
+            editor.delete(friend.syntax());
+            if let Some(next_ws_sibling) = next_ws_sibling
+                && next_ws_sibling.kind() == WHITESPACE
+            {
+                editor.delete(next_ws_sibling);
+            }
+        },
+    );
+    Some(assists)
+}
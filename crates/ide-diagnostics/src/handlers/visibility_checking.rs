@@ -0,0 +1,168 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::DiagnosticsContext;
+use crate::diagnostic::Diagnostic;
+use base_db::SourceDatabase;
+use ide_db::assist_context::LocalAssists;
+use lang::nameres::scope::ScopeEntry;
+use lang::node_ext::ModuleLangExt;
+use syntax::SyntaxKind::*;
+use syntax::ast::HasVisibility;
+use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
+use syntax::ast::syntax_factory::SyntaxFactory;
+use syntax::ast::visibility::{Vis, VisLevel};
+use syntax::files::{FileRange, InFile};
+use syntax::syntax_editor::{Element, Position, SyntaxEditor};
+use syntax::{AstNode, ast};
+
+/// The reference resolves to the item, which is not visible at the reference location.
+pub(crate) fn private_item_access(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    reference: InFile<&ast::ReferenceElement>,
+    reference_range: FileRange,
+    scope_entry: &ScopeEntry,
+    vis: Vis,
+) -> Option<()> {
+    let item_name = ctx
+        .sema
+        .fq_name_for_scope_entry(scope_entry)
+        .map(|it| it.fq_identifier_text())
+        .unwrap_or_else(|| scope_entry.name.clone());
+    let message = match vis {
+        Vis::Restricted(VisLevel::Friend) => {
+            format!("`{item_name}` is visible only to the friends of its module")
+        }
+        Vis::Restricted(VisLevel::Package) => {
+            format!("`{item_name}` is `package` and cannot be used outside of its package")
+        }
+        _ => format!("`{item_name}` is private to its module"),
+    };
+
+    let fun = scope_entry.node_loc.to_ast::<ast::Fun>(ctx.sema.db);
+    let fixes = fun.and_then(|fun| fixes(ctx, fun, vis, reference, reference_range));
+    acc.push(Diagnostic::error("private-item-access", message, reference_range).with_local_fixes(fixes));
+    Some(())
+}
+
+/// `friend` declarations are only allowed for the modules of the same package.
+pub(crate) fn friend_from_other_package(
+    acc: &mut Vec<Diagnostic>,
+    ctx: &DiagnosticsContext<'_>,
+    friend: InFile<ast::Friend>,
+) -> Option<()> {
+    let db = ctx.sema.db;
+    let friend_path = friend.and_then_ref(|it| it.path())?;
+    let friend_module = ctx.sema.resolve_to_element::<ast::Module>(friend_path.clone())?;
+    if db.file_package_id(friend_module.file_id) == db.file_package_id(friend.file_id) {
+        return None;
+    }
+    acc.push(Diagnostic::error(
+        "friend-outside-package",
+        "Invalid `friend` declaration: cannot declare modules out of the current package as friends",
+        friend_path.file_range(),
+    ));
+    Some(())
+}
+
+fn fixes(
+    ctx: &DiagnosticsContext<'_>,
+    fun: InFile<ast::Fun>,
+    vis: Vis,
+    reference: InFile<&ast::ReferenceElement>,
+    reference_range: FileRange,
+) -> Option<LocalAssists> {
+    let db = ctx.sema.db;
+    let fun_package_id = db.file_package_id(fun.file_id);
+    if ctx.sema.is_library(fun_package_id) {
+        return None;
+    }
+    let fun_name = fun.value.name()?.as_string();
+    let fun_module = fun.value.syntax().containing_module()?;
+    let is_same_package = db.file_package_id(reference.file_id) == fun_package_id;
+    // friends should be declared in the same package and at the same address
+    let friend_module = reference
+        .value
+        .syntax()
+        .containing_module()
+        .filter(|_| is_same_package)
+        .filter(
+            |context_module| match (fun_module.address(), context_module.address()) {
+                (Some(fun_address), Some(context_address)) => {
+                    fun_address.equals_to(db, &context_address, false)
+                }
+                _ => false,
+            },
+        );
+
+    let mut assists = ctx.local_assists_for_node(fun.as_ref())?;
+    let fun = fun.value;
+    assists.add_fix(
+        "make-public",
+        format!("Make `{fun_name}` public"),
+        reference_range.range,
+        |editor| set_visibility(editor, &fun, "public"),
+    );
+    if let Some(friend_module) = friend_module
+        && let Some(friend_module_path) = module_path(&friend_module)
+    {
+        let label = match vis {
+            Vis::Restricted(VisLevel::Friend) => format!(
+                "Declare `{friend_module_path}` as a friend of `{}`",
+                module_path(&fun_module)?
+            ),
+            _ => format!("Make `{fun_name}` public(friend)"),
+        };
+        assists.add_fix("make-public-friend", label, reference_range.range, |editor| {
+            if !matches!(vis, Vis::Restricted(VisLevel::Friend)) {
+                set_visibility(editor, &fun, "public(friend)");
+            }
+            let make = SyntaxFactory::new();
+            let friend = make.friend(make.path_from_import_path(friend_module_path));
+            fun_module.add_friend_decl(editor, &friend);
+            editor.add_mappings(make.finish_with_mappings());
+        });
+    }
+    if is_same_package {
+        assists.add_fix(
+            "make-public-package",
+            format!("Make `{fun_name}` public(package)"),
+            reference_range.range,
+            |editor| set_visibility(editor, &fun, "public(package)"),
+        );
+    }
+    Some(assists)
+}
+
+fn set_visibility(editor: &mut SyntaxEditor, fun: &ast::Fun, visibility: &str) {
+    let make = SyntaxFactory::new();
+    let new_modifier = make.visibility_modifier(visibility);
+    match fun.visibility_modifier() {
+        Some(modifier) => editor.replace(modifier.syntax(), new_modifier.syntax()),
+        None => {
+            // after attributes, `public entry fun`
+            let anchor = fun
+                .syntax()
+                .children_with_tokens()
+                .find(|it| !matches!(it.kind(), ATTR | WHITESPACE | COMMENT));
+            if let Some(anchor) = anchor {
+                editor.insert_all(
+                    Position::before(anchor),
+                    vec![
+                        new_modifier.syntax().syntax_element(),
+                        make.whitespace(" ").syntax_element(),
+                    ],
+                );
+            }
+        }
+    }
+    editor.add_mappings(make.finish_with_mappings());
+}
+
+/// `0x1::m` for `module 0x1::m`.
+fn module_path(module: &ast::Module) -> Option<String> {
+    let address = module.self_or_parent_address_ref()?;
+    let name = module.name()?.as_string();
+    Some(format!("{}::{name}", address.syntax().text()))
+}
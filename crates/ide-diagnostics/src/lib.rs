@@ -100,7 +100,16 @@ pub fn semantic_diagnostics(
             handlers::find_unresolved_references(&mut acc, &ctx, reference_element.in_file(file_id));
         }
         if let Some(any_call_expr) = node.clone().cast::<ast::AnyCallExpr>() {
-            handlers::call_params::check_value_arguments(&mut acc, &ctx, any_call_expr.in_file(file_id));
+            handlers::call_params::check_value_arguments(
+                &mut acc,
+                &ctx,
+                any_call_expr.clone().in_file(file_id),
+            );
+            handlers::spec_checking::impure_fun_call_in_spec(
+                &mut acc,
+                &ctx,
+                any_call_expr.in_file(file_id),
+            );
         }
         if let Some(method_or_path) = node.clone().cast::<ast::MethodOrPath>() {
             handlers::missing_type_arguments::missing_type_arguments(
@@ -109,12 +118,13 @@ pub fn semantic_diagnostics(
                 method_or_path.in_file(file_id),
             );
         }
+        if let Some(struct_or_enum) = node.clone().cast::<ast::StructOrEnum>() {
+            handlers::attr_checking::event_struct_attr(&mut acc, &ctx, struct_or_enum.in_file(file_id));
+        }
         if let Some(module) = node.clone().cast::<ast::Module>() {
-            handlers::unused_import::find_unused_imports(
-                &mut acc,
-                &ctx,
-                module.in_file(file_id).map_into(),
-            );
+            let module = module.in_file(file_id);
+            handlers::unused_import::find_unused_imports(&mut acc, &ctx, module.clone().map_into());
+            handlers::unused_items::unused_items(&mut acc, &ctx, module);
         }
         if let Some(block_expr) = node.clone().cast::<ast::BlockExpr>() {
             handlers::unused_import::find_unused_imports(
@@ -128,6 +138,8 @@ pub fn semantic_diagnostics(
                 ast::CallExpr(it) => {
                     handlers::can_be_replaced_with_method_call(&mut acc, &ctx, it.clone().in_file(file_id));
                     handlers::spec_global_replace_with_index_expr::spec_global_replace_with_index_expr(&mut acc, &ctx, it.clone().in_file(file_id));
+                    handlers::spec_checking::old_outside_post_condition(&mut acc, &ctx, it.clone().in_file(file_id));
+                    handlers::ability_checking::spec_resource_type_ability_check(&mut acc, &ctx, it.clone().in_file(file_id));
                     handlers::replace_with_resource_index_expr::replace_with_resource_index_expr(&mut acc, &ctx, it.in_file(file_id));
                 },
                 ast::MethodCallExpr(it) => {
@@ -139,6 +151,9 @@ pub fn semantic_diagnostics(
                 ast::AbortExpr(it) => {
                     handlers::error_const_docs::error_const_in_abort(&mut acc, &ctx, it.in_file(file_id));
                 },
+                ast::MatchExpr(it) => {
+                    handlers::match_checking::match_exhaustiveness(&mut acc, &ctx, it.in_file(file_id));
+                },
                 ast::IdentPat(it) => {
                     handlers::unused_variables::check_unused_ident_pat(&mut acc, &ctx, it.in_file(file_id));
                 },
@@ -170,9 +185,24 @@ pub fn semantic_diagnostics(
                 ast::TupleStructPat(it) => {
                     handlers::missing_fields::missing_fields_in_tuple_struct_pat(&mut acc, &ctx, it.in_file(file_id));
                 },
+                ast::PathExpr(it) => {
+                    handlers::spec_checking::result_outside_ensures(&mut acc, &ctx, it.in_file(file_id));
+                },
+                ast::IncludeSchema(it) => {
+                    handlers::spec_checking::schema_fields_in_include(&mut acc, &ctx, it.in_file(file_id));
+                },
+                ast::ApplySchema(it) => {
+                    handlers::spec_checking::schema_fields_in_apply(&mut acc, &ctx, it.in_file(file_id));
+                },
+                ast::PragmaStmt(it) => {
+                    handlers::spec_checking::unknown_pragma(&mut acc, &ctx, it.in_file(file_id));
+                },
                 ast::CastExpr(it) => {
                     handlers::redundant_integer_cast(&mut acc, &ctx, it.in_file(file_id));
                 },
+                ast::Const(it) => {
+                    handlers::const_eval::const_eval_errors(&mut acc, &ctx, it.in_file(file_id));
+                },
                 ast::SpecFun(it) => {
                     handlers::check_syntax::spec_fun_requires_return_type(&mut acc, &ctx, it.in_file(file_id));
                 },
@@ -180,6 +210,15 @@ pub fn semantic_diagnostics(
                     let fun = it.in_file(file_id);
                     handlers::check_syntax::entry_fun_cannot_have_return_type(&mut acc, &ctx, fun.clone());
                     handlers::unused_acquires::unused_acquires(&mut acc, &ctx, fun.clone());
+                    handlers::attr_checking::view_fun_attr(&mut acc, &ctx, fun.clone());
+                    handlers::attr_checking::test_fun_signers(&mut acc, &ctx, fun.clone());
+                    handlers::control_flow_checking::control_flow(&mut acc, &ctx, fun.clone());
+                },
+                ast::Friend(it) => {
+                    handlers::visibility_checking::friend_from_other_package(&mut acc, &ctx, it.in_file(file_id));
+                },
+                ast::AttrItem(it) => {
+                    handlers::attr_checking::unknown_attribute(&mut acc, &ctx, it.in_file(file_id));
                 },
                 ast::VisibilityModifier(it) => {
                     let modifier = it.in_file(file_id);
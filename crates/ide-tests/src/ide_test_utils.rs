@@ -10,6 +10,7 @@ use syntax::files::FilePosition;
 use syntax::pretty_print::{SourceMark, apply_source_marks};
 use test_utils::{fixtures, get_and_replace_caret};
 
+pub mod assists;
 pub mod completion_utils;
 pub mod diagnostics;
 
@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::apply_fix;
+use crate::init_tracing_for_test;
+use expect_test::Expect;
+use ide_db::assist_config::AssistConfig;
+use ide_db::assists::{Assist, AssistResolveStrategy};
+use ide_diagnostics::config::DiagnosticsConfig;
+use stdx::itertools::Itertools;
+use syntax::files::FileRange;
+use syntax::{TextRange, TextSize};
+use test_utils::fixtures::test_state::{TestPackageFiles, TestState, named};
+use test_utils::{fixtures, get_and_replace_caret};
+use vfs::AbsPathBuf;
+
+pub fn check_assist(assist_id: &str, before: &str, after: Expect) {
+    check_assist_inner(assist_id, None, before, after);
+}
+
+/// Applies the assist with the label, for the assists which are offered once per target.
+pub fn check_assist_with_label(assist_id: &str, label: &str, before: &str, after: Expect) {
+    check_assist_inner(assist_id, Some(label), before, after);
+}
+
+fn check_assist_inner(assist_id: &str, label: Option<&str>, before: &str, after: Expect) {
+    init_tracing_for_test();
+
+    let source = stdx::trim_indent(before).trim().to_string();
+    let (source, offset) = get_and_replace_caret(&source, "/*caret*/");
+
+    let assist = assists_at_offset(source, TextRange::empty(offset))
+        .into_iter()
+        .find(|it| it.id.0 == assist_id && label.is_none_or(|label| it.label == label))
+        .unwrap_or_else(|| panic!("assist `{assist_id}` is not available"));
+
+    let mut actual_after = apply_fix(&assist, source);
+    actual_after.push_str("\n");
+    after.assert_eq(&actual_after);
+}
+
+pub fn check_assist_not_applicable(assist_id: &str, before: &str) {
+    init_tracing_for_test();
+
+    let source = stdx::trim_indent(before).trim().to_string();
+    let (source, offset) = get_and_replace_caret(&source, "/*caret*/");
+    let assists = assists_at_offset(source, TextRange::empty(offset));
+    assert!(
+        assists.iter().all(|it| it.id.0 != assist_id),
+        "assist `{assist_id}` should not be available"
+    );
+}
+
+pub fn check_assist_with_label_not_applicable(assist_id: &str, label: &str, before: &str) {
+    init_tracing_for_test();
+
+    let source = stdx::trim_indent(before).trim().to_string();
+    let (source, offset) = get_and_replace_caret(&source, "/*caret*/");
+    let assists = assists_at_offset(source, TextRange::empty(offset));
+    assert!(
+        assists.iter().all(|it| it.id.0 != assist_id || it.label != label),
+        "assist `{assist_id}` with label `{label}` should not be available"
+    );
+}
+
+/// Same as [`check_assist_with_label`], but for the multi-file fixture with `//- /main.move` sections.
+/// Prints all files of the package after the assist is applied.
+pub fn check_assist_in_files(assist_id: &str, label: &str, before: &str, after: Expect) {
+    let source = stdx::trim_indent(before);
+    check_assist_in_packages(assist_id, label, vec![named("TestPackage", source)], after);
+}
+
+/// Same as [`check_assist_in_files`], but for the multiple packages. File names should be unique
+/// across the packages. Changed manifests are printed after the source files.
+pub fn check_assist_in_packages(
+    assist_id: &str,
+    label: &str,
+    packages: Vec<TestPackageFiles>,
+    after: Expect,
+) {
+    init_tracing_for_test();
+
+    let test_state = fixtures::from_multiple_files_on_tmpfs(packages);
+    let assist = assists_at_caret(&test_state, AssistResolveStrategy::All)
+        .into_iter()
+        .find(|it| it.id.0 == assist_id && it.label == label)
+        .unwrap_or_else(|| panic!("assist `{assist_id}` with label `{label}` is not available"));
+    let source_change = assist.source_change.unwrap();
+
+    let move_files = test_state.all_move_files();
+    let mut res = String::new();
+    for (file_id, mut file_text) in move_files
+        .iter()
+        .cloned()
+        .sorted_by_key(|it| test_state.relpath(it.0))
+    {
+        if let Some(edit) = source_change.source_file_edits.get(&file_id) {
+            edit.apply(&mut file_text);
+        }
+        res.push_str("//- ");
+        res.push_str(&test_state.relpath(file_id));
+        res.push_str("\n");
+        res.push_str(&file_text.replace("/*caret*/", ""));
+    }
+    let ws_root = AbsPathBuf::assert(test_state.ws_root.clone());
+    let manifest_edits = source_change
+        .source_file_edits
+        .iter()
+        .filter(|(file_id, _)| move_files.iter().all(|(it, _)| it != *file_id))
+        .map(|(file_id, edit)| {
+            let path = test_state.file_path(*file_id).as_path().unwrap();
+            let relpath = path.strip_prefix(ws_root.as_path()).unwrap().as_str().to_string();
+            (relpath, *file_id, edit)
+        })
+        .sorted_by(|a, b| a.0.cmp(&b.0));
+    for (relpath, file_id, edit) in manifest_edits {
+        let mut file_text = test_state.analysis().file_text(file_id).unwrap().to_string();
+        edit.apply(&mut file_text);
+        res.push_str("//- ");
+        res.push_str(&relpath);
+        res.push_str("\n");
+        res.push_str(file_text.trim_end());
+        res.push_str("\n");
+    }
+    after.assert_eq(&res);
+}
+
+/// Checks the labels of all assists with the `assist_id` in the multi-package fixture.
+/// Assists are not resolved, like in the code action request of the editor.
+pub fn check_assist_labels_in_packages(
+    assist_id: &str,
+    packages: Vec<TestPackageFiles>,
+    expected_labels: &[&str],
+) {
+    init_tracing_for_test();
+
+    let test_state = fixtures::from_multiple_files_on_tmpfs(packages);
+    let labels = assists_at_caret(&test_state, AssistResolveStrategy::None)
+        .into_iter()
+        .filter(|it| it.id.0 == assist_id)
+        .map(|it| it.label.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(labels, expected_labels);
+}
+
+fn assists_at_caret(test_state: &TestState, resolve: AssistResolveStrategy) -> Vec<Assist> {
+    let (file_id, file_text) = test_state.file_with_caret("/*caret*/");
+    let offset = TextSize::of(&file_text[..file_text.find("/*caret*/").unwrap() + "/*caret*/".len()]);
+    test_state
+        .analysis()
+        .assists_with_fixes(
+            &AssistConfig { allowed: None },
+            &DiagnosticsConfig::test_sample(),
+            resolve,
+            FileRange {
+                file_id,
+                range: TextRange::empty(offset),
+            },
+        )
+        .unwrap()
+}
+
+fn assists_at_offset(source: &str, range: TextRange) -> Vec<Assist> {
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+    analysis
+        .assists_with_fixes(
+            &AssistConfig { allowed: None },
+            &DiagnosticsConfig::test_sample(),
+            AssistResolveStrategy::All,
+            FileRange { file_id, range },
+        )
+        .unwrap()
+}
@@ -26,11 +26,18 @@ pub fn check_diagnostics_with_config(config: DiagnosticsConfig, expect: Expect)
 }
 
 pub fn check_diagnostics_on_tmpfs(test_state: TestState, expect: Expect) {
+    check_diagnostics_on_tmpfs_with_config(test_state, DiagnosticsConfig::test_sample(), expect);
+}
+
+pub fn check_diagnostics_on_tmpfs_with_config(
+    test_state: TestState,
+    config: DiagnosticsConfig,
+    expect: Expect,
+) {
     // init_tracing_for_test();
 
     let (file_id, file_source) = test_state.file_with_caret("/*caret*/");
 
-    let config = DiagnosticsConfig::test_sample();
     let frange = test_state.analysis().full_file_range(file_id).unwrap();
     let diagnostics = test_state
         .analysis()
@@ -82,10 +89,24 @@ pub fn check_diagnostics_on_tmpfs_and_fix(test_state: TestState, before_fix: Exp
 }
 
 pub fn check_diagnostics_and_fix_with_id(fix_id: AssistId, before: Expect, after_fix: Expect) {
+    check_diagnostics_with_config_and_fix_with_id(
+        DiagnosticsConfig::test_sample(),
+        fix_id,
+        before,
+        after_fix,
+    );
+}
+
+pub fn check_diagnostics_with_config_and_fix_with_id(
+    config: DiagnosticsConfig,
+    fix_id: AssistId,
+    before: Expect,
+    after_fix: Expect,
+) {
     init_tracing_for_test();
 
     let source = clean_source(&before);
-    let diagnostics = check_diagnostics_inner(before, DiagnosticsConfig::test_sample());
+    let diagnostics = check_diagnostics_inner(before, config);
 
     let mut fixes = get_fixes_with_id(diagnostics, Some(fix_id));
     let fix = match fixes.len() {
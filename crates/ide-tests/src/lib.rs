@@ -14,20 +14,29 @@ mod test_syntax_highlighting;
 mod types;
 
 mod test_abilities_key;
+mod test_assists;
 mod test_code_lens;
+mod test_completion_attrs;
 mod test_completion_functions;
 mod test_completion_loops;
 mod test_completion_methods;
 mod test_completion_out_of_scope;
+mod test_completion_postfix;
 mod test_completion_relevance;
+mod test_completion_smart_constructors;
+mod test_completion_specs;
 mod test_db;
 mod test_diagnostics;
+mod test_document_links;
 mod test_error_const_docs;
 mod test_find_usages;
 mod test_goto_specification;
 mod test_inlay_hints;
 mod test_inlay_parameter_hints;
+mod test_library_cache;
 mod test_load_dependencies;
+mod test_memory_usage;
+mod test_moniker;
 mod test_named_addresses;
 mod test_organize_imports;
 mod test_rename;
@@ -36,6 +45,8 @@ mod test_resolve_types;
 mod test_signature_help_struct_lit_fields;
 mod test_signature_help_type_parameters;
 mod test_signature_help_value_parameters;
+mod test_static_index;
+mod test_status;
 mod test_view_syntax_tree;
 mod test_world_symbols;
 
@@ -0,0 +1,6 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod test_generate_spec;
+mod test_generate_unit_test;
+mod test_move_item_to_module;
@@ -0,0 +1,334 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::assists::{check_assist, check_assist_in_files, check_assist_not_applicable};
+use expect_test::expect;
+
+#[test]
+fn test_generate_spec_with_aborts_if_modifies_and_ensures() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            struct S has key { val: u8 }
+            const E_ZERO: u64 = 1;
+            public fun /*caret*/set_val(addr: address, val: u8): u8 acquires S {
+                assert!(val > 0, E_ZERO);
+                if (addr == @0x1) {
+                    abort 2
+                };
+                borrow_global_mut<S>(addr).val = val;
+                val
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                struct S has key { val: u8 }
+                const E_ZERO: u64 = 1;
+                public fun set_val(addr: address, val: u8): u8 acquires S {
+                    assert!(val > 0, E_ZERO);
+                    if (addr == @0x1) {
+                        abort 2
+                    };
+                    borrow_global_mut<S>(addr).val = val;
+                    val
+                }
+
+                spec set_val {
+                    aborts_if val <= 0 with E_ZERO;
+                    aborts_if addr == @0x1 with 2;
+                    modifies global<S>(addr);
+                    ensures result == result;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_with_nested_conditions() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun /*caret*/check(a: u64, b: bool) {
+                if (b) {
+                    assert!(a != 1, 1);
+                } else {
+                    assert!(!(a > 2 || a < 1), 2);
+                }
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun check(a: u64, b: bool) {
+                    if (b) {
+                        assert!(a != 1, 1);
+                    } else {
+                        assert!(!(a > 2 || a < 1), 2);
+                    }
+                }
+
+                spec check {
+                    aborts_if b && a == 1 with 1;
+                    aborts_if !b && (a > 2 || a < 1) with 2;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_modifies_for_move_to_with_inferred_type() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            struct S has key { val: u8 }
+            public fun /*caret*/publish(account: &signer) {
+                move_to(account, S { val: 1 });
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                use std::signer;
+
+                struct S has key { val: u8 }
+                public fun publish(account: &signer) {
+                    move_to(account, S { val: 1 });
+                }
+
+                spec publish {
+                    modifies global<S>(signer::address_of(account));
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_extends_existing_spec() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/div(a: u64, b: u64): u64 {
+                assert!(b != 0, 1);
+                a / b
+            }
+            spec div {
+                aborts_if b == 0 with 1;
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun div(a: u64, b: u64): u64 {
+                    assert!(b != 0, 1);
+                    a / b
+                }
+                spec div {
+                    aborts_if b == 0 with 1;
+                    ensures result == result;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_no_generate_spec_if_existing_spec_is_complete() {
+    check_assist_not_applicable(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/check(a: u64) {
+                assert!(a > 0, 1);
+            }
+            spec check {
+                aborts_if a <= 0 with 1;
+            }
+        }
+    "#,
+    )
+}
+
+#[test]
+fn test_generate_spec_modifies_for_move_to_with_existing_signer_import() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            use std::signer::{Self, address_of};
+            struct S has key { val: u8 }
+            public fun /*caret*/publish(account: &signer) {
+                move_to(account, S { val: 1 });
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                use std::signer::{Self, address_of};
+                struct S has key { val: u8 }
+                public fun publish(account: &signer) {
+                    move_to(account, S { val: 1 });
+                }
+
+                spec publish {
+                    modifies global<S>(signer::address_of(account));
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_skips_conditions_with_local_variables() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/withdraw(balance: u64, amount: u64) {
+                assert!(amount > 0, 1);
+                let remaining = balance - amount;
+                assert!(remaining >= 10, 2);
+                if (remaining > 100) {
+                    abort 3
+                };
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun withdraw(balance: u64, amount: u64) {
+                    assert!(amount > 0, 1);
+                    let remaining = balance - amount;
+                    assert!(remaining >= 10, 2);
+                    if (remaining > 100) {
+                        abort 3
+                    };
+                }
+
+                spec withdraw {
+                    aborts_if amount <= 0 with 1;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_skips_conditions_after_early_return() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/withdraw(balance: u64, amount: u64) {
+                assert!(amount > 0, 1);
+                if (balance == 0) return;
+                assert!(balance >= amount, 2);
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun withdraw(balance: u64, amount: u64) {
+                    assert!(amount > 0, 1);
+                    if (balance == 0) return;
+                    assert!(balance >= amount, 2);
+                }
+
+                spec withdraw {
+                    aborts_if amount <= 0 with 1;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_skips_conditions_after_parameter_reassignment() {
+    check_assist(
+        "generate-spec",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/deposit(balance: u64, amount: u64) {
+                balance = balance + amount;
+                assert!(balance < 100, 3);
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun deposit(balance: u64, amount: u64) {
+                    balance = balance + amount;
+                    assert!(balance < 100, 3);
+                }
+
+                spec deposit {}
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_spec_extends_spec_in_spec_file() {
+    check_assist_in_files(
+        "generate-spec",
+        "Add missing conditions to the spec of `withdraw`",
+        // language=Move
+        r#"
+        //- /main.move
+        module 0x1::m {
+            public fun /*caret*/withdraw(amount: u64): u64 {
+                assert!(amount > 0, 1);
+                amount
+            }
+        }
+        //- /main.spec.move
+        spec 0x1::m {
+            spec withdraw {
+                ensures result == amount;
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            //- /main.move
+            module 0x1::m {
+                public fun withdraw(amount: u64): u64 {
+                    assert!(amount > 0, 1);
+                    amount
+                }
+            }
+            //- /main.spec.move
+            spec 0x1::m {
+                spec withdraw {
+                    ensures result == amount;
+                    aborts_if amount <= 0 with 1;
+                    ensures result == result;
+                }
+            }
+        "#]],
+    )
+}
@@ -0,0 +1,288 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::assists::{check_assist, check_assist_not_applicable};
+use expect_test::expect;
+
+#[test]
+fn test_generate_unit_test_in_new_test_module() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::coin {
+            const E_ZERO: u64 = 1;
+            const E_TOO_MUCH: u64 = 2;
+            public fun /*caret*/mint(account: &signer, amount: u64) {
+                assert!(amount > 0, E_ZERO);
+                if (amount > 100) abort E_TOO_MUCH;
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::coin {
+                const E_ZERO: u64 = 1;
+                const E_TOO_MUCH: u64 = 2;
+                public fun mint(account: &signer, amount: u64) {
+                    assert!(amount > 0, E_ZERO);
+                    if (amount > 100) abort E_TOO_MUCH;
+                }
+            }
+
+            #[test_only]
+            module 0x1::coin_tests {
+                use 0x1::coin;
+
+                #[test(account = @0x123)]
+                fun test_mint(account: signer) {
+                    coin::mint(&account, 0);
+                }
+
+                #[test(account = @0x123)]
+                #[expected_failure(abort_code = coin::E_ZERO)]
+                fun test_mint_e_zero(account: signer) {
+                    coin::mint(&account, 0);
+                }
+
+                #[test(account = @0x123)]
+                #[expected_failure(abort_code = coin::E_TOO_MUCH)]
+                fun test_mint_e_too_much(account: signer) {
+                    coin::mint(&account, 0);
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_unit_test_in_existing_test_module() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/call(a: u8, b: bool, c: address, d: vector<u8>) {}
+        }
+
+        #[test_only]
+        module 0x1::m_tests {
+            use 0x1::m;
+
+            #[test]
+            fun test_call() {
+                m::call(1, true, @0x1, vector[]);
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun call(a: u8, b: bool, c: address, d: vector<u8>) {}
+            }
+
+            #[test_only]
+            module 0x1::m_tests {
+                use 0x1::m;
+
+                #[test]
+                fun test_call() {
+                    m::call(1, true, @0x1, vector[]);
+                }
+
+                #[test]
+                fun test_call_1() {
+                    m::call(0, false, @0x1, vector[]);
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_unit_test_adds_module_import() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun /*caret*/call() {}
+        }
+
+        #[test_only]
+        module 0x1::m_tests {
+            #[test]
+            fun test_other() {}
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun call() {}
+            }
+
+            #[test_only]
+            module 0x1::m_tests {
+                use 0x1::m;
+
+                #[test]
+                fun test_other() {}
+
+                #[test]
+                fun test_call() {
+                    m::call();
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_unit_test_for_entry_function_in_address_block() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        address 0x1 {
+        module m {
+            public entry fun /*caret*/transfer(from: &signer, to: signer, amount: u64) {}
+        }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            address 0x1 {
+            module m {
+                public entry fun transfer(from: &signer, to: signer, amount: u64) {}
+            }
+
+            #[test_only]
+            module m_tests {
+                use 0x1::m;
+
+                #[test(from = @0x123, to = @0x124)]
+                fun test_transfer(from: signer, to: signer) {
+                    m::transfer(&from, to, 0);
+                }
+            }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_no_generate_unit_test_for_private_function() {
+    check_assist_not_applicable(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun /*caret*/call() {}
+        }
+    "#,
+    )
+}
+
+#[test]
+fn test_no_generate_unit_test_inside_function_body() {
+    check_assist_not_applicable(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::m {
+            public fun call() {
+                /*caret*/
+            }
+        }
+    "#,
+    )
+}
+
+#[test]
+fn test_generate_unit_test_for_private_entry_function_in_same_module() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::m {
+            const E_ZERO: u64 = 1;
+            entry fun /*caret*/call(account: &mut signer, amount: u64) {
+                assert!(amount > 0, E_ZERO);
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                const E_ZERO: u64 = 1;
+                entry fun call(account: &mut signer, amount: u64) {
+                    assert!(amount > 0, E_ZERO);
+                }
+
+                #[test(account = @0x123)]
+                fun test_call(account: signer) {
+                    call(&mut account, 0);
+                }
+
+                #[test(account = @0x123)]
+                #[expected_failure(abort_code = E_ZERO)]
+                fun test_call_e_zero(account: signer) {
+                    call(&mut account, 0);
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_generate_unit_test_skips_error_consts_wrapped_into_calls() {
+    check_assist(
+        "generate-unit-test",
+        // language=Move
+        r#"
+        module 0x1::error {
+            public fun invalid_argument(r: u64): u64 { (0x1 << 16) + r }
+        }
+        module 0x1::coin {
+            use 0x1::error;
+            const E_ZERO: u64 = 1;
+            const E_TOO_MUCH: u64 = 2;
+            public fun /*caret*/mint(amount: u64) {
+                assert!(amount > 0, error::invalid_argument(E_ZERO));
+                if (amount > 100) abort E_TOO_MUCH;
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::error {
+                public fun invalid_argument(r: u64): u64 { (0x1 << 16) + r }
+            }
+            module 0x1::coin {
+                use 0x1::error;
+                const E_ZERO: u64 = 1;
+                const E_TOO_MUCH: u64 = 2;
+                public fun mint(amount: u64) {
+                    assert!(amount > 0, error::invalid_argument(E_ZERO));
+                    if (amount > 100) abort E_TOO_MUCH;
+                }
+            }
+
+            #[test_only]
+            module 0x1::coin_tests {
+                use 0x1::coin;
+
+                #[test]
+                fun test_mint() {
+                    coin::mint(0);
+                }
+
+                #[test]
+                #[expected_failure(abort_code = coin::E_TOO_MUCH)]
+                fun test_mint_e_too_much() {
+                    coin::mint(0);
+                }
+            }
+        "#]],
+    )
+}
@@ -0,0 +1,614 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::assists::{
+    check_assist_in_files, check_assist_in_packages, check_assist_labels_in_packages,
+    check_assist_not_applicable, check_assist_with_label, check_assist_with_label_not_applicable,
+};
+use expect_test::expect;
+use test_utils::fixtures::test_state::{named, named_with_deps};
+
+#[test]
+fn test_move_public_function_updates_imports_and_qualified_paths() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            public fun /*caret*/f(): u8 { 1 }
+        }
+        module 0x1::b {}
+        module 0x1::main {
+            use 0x1::a::f;
+            fun main() {
+                f();
+                0x1::a::f();
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {}
+            module 0x1::b {
+                public fun f(): u8 { 1 }
+            }
+            module 0x1::main {
+                use 0x1::b::f;
+                fun main() {
+                    f();
+                    0x1::b::f();
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_private_function_used_in_source_module() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            fun /*caret*/f(): u8 { 1 }
+            fun main() { f(); }
+        }
+        module 0x1::b {}
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {
+                use 0x1::b::f;
+
+                fun main() { f(); }
+            }
+            module 0x1::b {
+                package fun f(): u8 { 1 }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_function_imports_private_helper_of_source_module() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            fun helper(): u8 { 1 }
+            public fun /*caret*/f(): u8 { helper() }
+        }
+        module 0x1::b {}
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {
+                package fun helper(): u8 { 1 }
+            }
+            module 0x1::b {
+                use 0x1::a::helper;
+
+                public fun f(): u8 { helper() }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_function_together_with_its_spec() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            public fun /*caret*/f(): u8 { 1 }
+            spec f {
+                ensures result == 1;
+            }
+        }
+        module 0x1::b {
+            public fun g() {}
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {}
+            module 0x1::b {
+                public fun g() {}
+
+                public fun f(): u8 { 1 }
+
+                spec f {
+                    ensures result == 1;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_function_imported_in_group_use() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            public fun /*caret*/f(): u8 { 1 }
+            public fun g(): u8 { 2 }
+        }
+        module 0x1::b {}
+        module 0x1::main {
+            use 0x1::a::{f, g};
+            fun main() {
+                let _ = f() + g();
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {
+                public fun g(): u8 { 2 }
+            }
+            module 0x1::b {
+                public fun f(): u8 { 1 }
+            }
+            module 0x1::main {
+                use 0x1::a::g;
+                use 0x1::b::f;
+                fun main() {
+                    let _ = f() + g();
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_friend_function_adds_friend_declaration_to_target() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            friend 0x1::c;
+            public(friend) fun /*caret*/f(): u8 { 1 }
+        }
+        module 0x1::b {}
+        module 0x1::c {
+            use 0x1::a::f;
+            fun main(): u8 { f() }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {
+                friend 0x1::c;
+            }
+            module 0x1::b {
+                friend 0x1::c;
+
+                public(friend) fun f(): u8 { 1 }
+            }
+            module 0x1::c {
+                use 0x1::b::f;
+                fun main(): u8 { f() }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_function_adds_friend_declaration_for_called_friend_function() {
+    check_assist_with_label(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            friend 0x1::c;
+            public(friend) fun h(): u8 { 1 }
+        }
+        module 0x1::b {}
+        module 0x1::c {
+            use 0x1::a;
+            public fun /*caret*/f(): u8 { a::h() }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::a {
+                friend 0x1::c;
+                friend 0x1::b;
+                public(friend) fun h(): u8 { 1 }
+            }
+            module 0x1::b {
+                use 0x1::a;
+
+                public fun f(): u8 { a::h() }
+            }
+            module 0x1::c {
+                use 0x1::a;
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_no_move_if_target_module_depends_on_source_module() {
+    check_assist_with_label_not_applicable(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        module 0x1::a {
+            public fun /*caret*/f(): u8 { 1 }
+            public fun g(): u8 { f() }
+        }
+        module 0x1::b {
+            use 0x1::a;
+            fun main(): u8 { a::g() }
+        }
+    "#,
+    )
+}
+
+#[test]
+fn test_no_move_of_constant_used_in_source_module() {
+    check_assist_not_applicable(
+        "move-item-to-module",
+        // language=Move
+        r#"
+        module 0x1::a {
+            const /*caret*/MAX: u64 = 10;
+            fun main(): u64 { MAX }
+        }
+        module 0x1::b {}
+    "#,
+    )
+}
+
+#[test]
+fn test_no_move_of_struct_packed_in_source_module() {
+    check_assist_not_applicable(
+        "move-item-to-module",
+        // language=Move
+        r#"
+        module 0x1::a {
+            struct /*caret*/S { val: u8 }
+            fun make(): S { S { val: 1 } }
+        }
+        module 0x1::b {}
+    "#,
+    )
+}
+
+#[test]
+fn test_move_function_into_module_in_another_file() {
+    check_assist_in_files(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        // language=Move
+        r#"
+        //- /a.move
+        module 0x1::a {
+            public fun /*caret*/f(): u8 { 1 }
+        }
+        //- /b.move
+        module 0x1::b {
+            public fun g(): u8 { 2 }
+        }
+        //- /main.move
+        module 0x1::main {
+            use 0x1::a;
+            use 0x1::b;
+            fun main() {
+                let _ = a::f() + b::g();
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            //- /a.move
+            module 0x1::a {}
+            //- /b.move
+            module 0x1::b {
+                public fun g(): u8 { 2 }
+
+                public fun f(): u8 { 1 }
+            }
+            //- /main.move
+            module 0x1::main {
+                use 0x1::a;
+                use 0x1::b;
+                fun main() {
+                    let _ = b::f() + b::g();
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_function_updates_imports_in_dependent_package() {
+    check_assist_in_packages(
+        "move-item-to-module",
+        "Move `f` to `0x1::b`",
+        vec![
+            named(
+                "A",
+                // language=Move
+                r#"
+//- /a.move
+module 0x1::a {
+    public fun /*caret*/f(): u8 { 1 }
+}
+//- /b.move
+module 0x1::b {}
+"#,
+            ),
+            named_with_deps(
+                "Main",
+                // language=TOML
+                r#"
+[dependencies]
+A = { local = "../A" }
+"#,
+                // language=Move
+                r#"
+//- /main.move
+module 0x2::main {
+    use 0x1::a::f;
+    fun main(): u8 { f() }
+}
+"#,
+            ),
+        ],
+        // language=Move
+        expect![[r#"
+            //- /a.move
+            module 0x1::a {}
+            //- /b.move
+            module 0x1::b {
+                public fun f(): u8 { 1 }
+            }
+            //- /main.move
+            module 0x2::main {
+                use 0x1::b::f;
+                fun main(): u8 { f() }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_move_targets_include_modules_of_other_local_packages() {
+    check_assist_labels_in_packages(
+        "move-item-to-module",
+        vec![
+            named(
+                "A",
+                // language=Move
+                r#"
+//- /a.move
+module 0x1::a {}
+module 0x1::b {}
+"#,
+            ),
+            named_with_deps(
+                "Main",
+                // language=TOML
+                r#"
+[dependencies]
+A = { local = "../A" }
+"#,
+                // language=Move
+                r#"
+//- /main.move
+module 0x2::main {
+    public fun /*caret*/f(): u8 { 1 }
+}
+module 0x2::other {}
+"#,
+            ),
+        ],
+        &[
+            "Move `f` to `0x2::other`",
+            "Move `f` to `0x1::a`",
+            "Move `f` to `0x1::b`",
+        ],
+    )
+}
+
+#[test]
+fn test_no_move_into_package_depending_on_source_package() {
+    check_assist_labels_in_packages(
+        "move-item-to-module",
+        vec![
+            named(
+                "A",
+                // language=Move
+                r#"
+//- /a.move
+module 0x1::a {
+    public fun /*caret*/f(): u8 { 1 }
+}
+module 0x1::b {}
+"#,
+            ),
+            named_with_deps(
+                "Main",
+                // language=TOML
+                r#"
+[dependencies]
+A = { local = "../A" }
+"#,
+                // language=Move
+                r#"
+//- /main.move
+module 0x2::main {}
+"#,
+            ),
+        ],
+        &["Move `f` to `0x1::b`"],
+    )
+}
+
+#[test]
+fn test_no_move_into_package_if_item_uses_source_package() {
+    check_assist_labels_in_packages(
+        "move-item-to-module",
+        vec![
+            named(
+                "A",
+                // language=Move
+                r#"
+//- /a.move
+module 0x1::a {
+    public fun /*caret*/f(): u8 { g() }
+    fun g(): u8 { 1 }
+}
+"#,
+            ),
+            named(
+                "B",
+                // language=Move
+                r#"
+//- /b.move
+module 0x2::b {}
+"#,
+            ),
+        ],
+        &[],
+    )
+}
+
+#[test]
+fn test_move_function_into_another_package_adds_dependency() {
+    check_assist_in_packages(
+        "move-item-to-module",
+        "Move `f` to `0x2::b`",
+        vec![
+            named(
+                "A",
+                // language=Move
+                r#"
+//- /a.move
+module 0x1::a {
+    public(package) fun /*caret*/f(): u8 { 1 }
+}
+//- /main.move
+module 0x1::main {
+    use 0x1::a;
+    fun main(): u8 { a::f() }
+}
+"#,
+            ),
+            named(
+                "B",
+                // language=Move
+                r#"
+//- /b.move
+module 0x2::b {}
+"#,
+            ),
+        ],
+        // language=Move
+        expect![[r#"
+            //- /a.move
+            module 0x1::a {}
+            //- /b.move
+            module 0x2::b {
+                public fun f(): u8 { 1 }
+            }
+            //- /main.move
+            module 0x1::main {
+                use 0x1::a;
+                fun main(): u8 { 0x2::b::f() }
+            }
+            //- A/Move.toml
+            [package]
+            name = "A"
+            version = "0.1.0"
+
+            [dependencies]
+            B = { local = "../B" }
+        "#]],
+    )
+}
+
+#[test]
+fn test_no_move_if_target_module_depends_on_source_module_without_resolve() {
+    check_assist_labels_in_packages(
+        "move-item-to-module",
+        vec![named(
+            "A",
+            // language=Move
+            r#"
+//- /a.move
+module 0x1::a {
+    public fun /*caret*/f(): u8 { 1 }
+    public fun g(): u8 { f() }
+}
+//- /b.move
+module 0x1::b {
+    use 0x1::a;
+    fun main(): u8 { a::g() }
+}
+//- /c.move
+module 0x1::c {}
+"#,
+        )],
+        &["Move `f` to `0x1::c`"],
+    )
+}
+
+#[test]
+fn test_move_targets_are_limited() {
+    check_assist_labels_in_packages(
+        "move-item-to-module",
+        vec![named(
+            "A",
+            // language=Move
+            r#"
+//- /a.move
+module 0x1::a {
+    public fun /*caret*/f(): u8 { 0x1::m11::g() }
+}
+module 0x1::z {}
+//- /m.move
+module 0x1::m01 {}
+module 0x1::m02 {}
+module 0x1::m03 {}
+module 0x1::m04 {}
+module 0x1::m05 {}
+module 0x1::m06 {}
+module 0x1::m07 {}
+module 0x1::m08 {}
+module 0x1::m09 {}
+module 0x1::m10 {}
+module 0x1::m11 {
+    public fun g(): u8 { 1 }
+}
+"#,
+        )],
+        &[
+            "Move `f` to `0x1::z`",
+            "Move `f` to `0x1::m11`",
+            "Move `f` to `0x1::m01`",
+            "Move `f` to `0x1::m02`",
+            "Move `f` to `0x1::m03`",
+            "Move `f` to `0x1::m04`",
+            "Move `f` to `0x1::m05`",
+            "Move `f` to `0x1::m06`",
+            "Move `f` to `0x1::m07`",
+            "Move `f` to `0x1::m08`",
+        ],
+    )
+}
@@ -0,0 +1,164 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::completion_utils::{check_completions, do_single_completion};
+use expect_test::expect;
+
+#[test]
+fn test_attribute_names_for_struct() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[/*caret*/]
+            struct S {}
+        }
+    "#,
+        expect![[r#"
+            [
+                "test_only",
+                "verify_only",
+                "event",
+                "resource_group",
+                "resource_group_member",
+                "deprecated",
+                "fmt::skip",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_attribute_names_skip_existing() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[test, test_only]
+            #[te/*caret*/]
+            fun main() {}
+        }
+    "#,
+        expect![[r#"
+            []"#]],
+    )
+}
+
+#[test]
+fn test_attribute_with_keys_inserts_parens() {
+    do_single_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[test, expected_fai/*caret*/]
+            fun main() {}
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                #[test, expected_failure(/*caret*/)]
+                fun main() {}
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_expected_failure_keys() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[test]
+            #[expected_failure(abort_code = 1, /*caret*/)]
+            fun main() {}
+        }
+    "#,
+        expect![[r#"
+            [
+                "arithmetic_error",
+                "vector_error",
+                "out_of_gas",
+                "major_status",
+                "minor_status",
+                "location",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_test_attribute_keys_are_function_parameters() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[test(admin = @0x1, /*caret*/)]
+            fun test_main(admin: signer, user: signer, other: signer) {}
+        }
+    "#,
+        expect![[r#"
+            [
+                "user",
+                "other",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_test_attribute_key_inserts_value() {
+    do_single_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[test(adm/*caret*/)]
+            fun test_main(admin: signer) {}
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                #[test(admin = /*caret*/)]
+                fun test_main(admin: signer) {}
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_resource_group_member_key() {
+    do_single_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[resource_group_member(gr/*caret*/)]
+            struct S has key {}
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                #[resource_group_member(group = /*caret*/)]
+                struct S has key {}
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_resource_group_scope_values() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            #[resource_group(scope = /*caret*/)]
+            struct Group {}
+        }
+    "#,
+        expect![[r#"
+            [
+                "global",
+                "address",
+                "module_",
+            ]"#]],
+    )
+}
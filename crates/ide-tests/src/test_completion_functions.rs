@@ -345,6 +345,7 @@ fn test_function_in_path_position_with_auto_import() {
     let config = CompletionConfig {
         allow_snippets: AllowSnippets::new(true),
         enable_imports_on_the_fly: true,
+        ..CompletionConfig::default()
     };
     do_single_completion_with_config(
         config,
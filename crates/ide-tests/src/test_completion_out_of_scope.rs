@@ -10,6 +10,7 @@ fn check_out_of_scope_completions(source: &str, expected: Expect) {
         CompletionConfig {
             allow_snippets: AllowSnippets::new(true),
             enable_imports_on_the_fly: true,
+            ..CompletionConfig::default()
         },
         source,
         expected,
@@ -21,6 +22,7 @@ fn do_out_of_scope_completion(before: &str, after: Expect) {
         CompletionConfig {
             allow_snippets: AllowSnippets::new(true),
             enable_imports_on_the_fly: true,
+            ..CompletionConfig::default()
         },
         before,
         after,
@@ -0,0 +1,343 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::completion_utils::{
+    check_completions_with_config, do_single_completion_with_config,
+};
+use expect_test::{Expect, expect};
+use ide_completion::config::CompletionConfig;
+use ide_db::AllowSnippets;
+
+fn postfix_config() -> CompletionConfig {
+    CompletionConfig {
+        allow_snippets: AllowSnippets::new(true),
+        enable_postfix_completions: true,
+        ..CompletionConfig::default()
+    }
+}
+
+fn check_postfix_completions(source: &str, expected: Expect) {
+    check_completions_with_config(postfix_config(), source, expected)
+}
+
+fn do_postfix_completion(before: &str, after: Expect) {
+    do_single_completion_with_config(postfix_config(), before, after)
+}
+
+#[test]
+fn test_postfix_completions_for_bool() {
+    check_postfix_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(flag: bool) {
+                flag./*caret*/
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "if -> if (expr) {}",
+                "not -> !expr",
+                "assert -> assert!(expr, code)",
+                "ref -> &expr",
+                "refm -> &mut expr",
+                "let -> let name = expr;",
+                "vec -> vector[expr]",
+                "some -> option::some(expr)",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_postfix_completions_for_integer_reference() {
+    check_postfix_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(val: &u64) {
+                val./*caret*/
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "deref -> *expr",
+                "let -> let name = expr;",
+                "vec -> vector[expr]",
+                "some -> option::some(expr)",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_postfix_not() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(a: u8, b: u8) {
+                (a == b).no/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main(a: u8, b: u8) {
+                    !(a == b)
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_assert_strips_parens() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(a: u8, b: u8) {
+                (a == b).asse/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main(a: u8, b: u8) {
+                    assert!(a == b, /*caret*/)
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_abort() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            const E_NOT_FOUND: u64 = 1;
+            fun main() {
+                E_NOT_FOUND.abo/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                const E_NOT_FOUND: u64 = 1;
+                fun main() {
+                    abort E_NOT_FOUND
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_let() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun compute(): u8 { 1 }
+            fun main() {
+                compute().le/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun compute(): u8 { 1 }
+                fun main() {
+                    let /*caret*/ = compute();
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_refm() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            struct S { val: u8 }
+            fun main(s: S) {
+                s.refm/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                struct S { val: u8 }
+                fun main(s: S) {
+                    &mut s
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_vec() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(a: address) {
+                a.ve/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main(a: address) {
+                    vector[a]
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_match_fills_enum_variants() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            enum Color { Red, Rgb { r: u8 }, Custom(u64) }
+            fun main(color: Color) {
+                color.mat/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                enum Color { Red, Rgb { r: u8 }, Custom(u64) }
+                fun main(color: Color) {
+                    match (color) {
+                Color::Red => {/*caret*/},
+                Color::Rgb { .. } => {},
+                Color::Custom(..) => {},
+            }
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_some_for_option() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module std::option {
+            struct Option<Element> has copy, drop, store { vec: vector<Element> }
+        }
+        module 0x1::m {
+            use std::option::{Self, Option};
+            fun main(opt: Option<u8>) {
+                opt.som/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module std::option {
+                struct Option<Element> has copy, drop, store { vec: vector<Element> }
+            }
+            module 0x1::m {
+                use std::option::{Self, Option};
+                fun main(opt: Option<u8>) {
+                    option::is_some(&opt)
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_postfix_some_wraps_value() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(val: u8) {
+                val.som/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                use std::option;
+
+                fun main(val: u8) {
+                    option::some(val)
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_no_postfix_abort_for_non_u64_integer() {
+    check_postfix_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(val: u8) {
+                val./*caret*/
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "ref -> &expr",
+                "refm -> &mut expr",
+                "let -> let name = expr;",
+                "vec -> vector[expr]",
+                "some -> option::some(expr)",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_postfix_some_for_option_adds_module_import() {
+    do_postfix_completion(
+        // language=Move
+        r#"
+        module std::option {
+            struct Option<Element> has copy, drop, store { vec: vector<Element> }
+        }
+        module 0x1::m {
+            fun main(opt: std::option::Option<u8>) {
+                opt.non/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module std::option {
+                struct Option<Element> has copy, drop, store { vec: vector<Element> }
+            }
+            module 0x1::m {
+                use std::option;
+
+                fun main(opt: std::option::Option<u8>) {
+                    option::is_none(&opt)
+                }
+            }
+        "#]],
+    )
+}
@@ -0,0 +1,217 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::completion_utils::{check_completions, do_single_completion};
+use expect_test::expect;
+
+#[test]
+fn test_struct_literal_for_expected_type() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::m {
+    struct Point { x: u64, y: u64 }
+    fun main() {
+        let p: Point = Po/*caret*/;
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "Point { x, y }",
+                "Point",
+            ]"#]],
+    );
+}
+
+#[test]
+fn test_struct_literal_for_expected_type_in_call_argument() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::m {
+    struct Wrapper(u64, bool)
+    fun take(w: Wrapper) {}
+    fun main() {
+        take(Wr/*caret*/);
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "Wrapper(..)",
+                "Wrapper",
+            ]"#]],
+    );
+}
+
+#[test]
+fn test_enum_variant_constructors_for_expected_type() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::m {
+    enum Color { Red, Rgb(u8, u8, u8), Named { name: vector<u8> } }
+    fun main() {
+        let c: Color = Co/*caret*/;
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "Color::Red",
+                "Color::Rgb(..)",
+                "Color::Named { name }",
+                "Color",
+            ]"#]],
+    );
+}
+
+#[test]
+fn test_no_struct_literal_outside_of_declaring_module() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::point {
+    struct Point { x: u64, y: u64 }
+}
+module 0x1::m {
+    use 0x1::point::Point;
+    fun main() {
+        let p: Point = Po/*caret*/;
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "Point",
+            ]"#]],
+    );
+}
+
+#[test]
+fn test_functions_returning_expected_type_from_imported_module() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::coin {
+    struct Coin<phantom T> has store { value: u64 }
+    public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+    public fun zero_value<T>(c: &Coin<T>): u64 { c.value }
+    fun zero_private<T>(): Coin<T> { Coin { value: 0 } }
+}
+module 0x1::m {
+    use 0x1::coin::{Self, Coin};
+    struct AptosCoin {}
+    fun main() {
+        let zero_count: u8 = 0;
+        let c: Coin<AptosCoin> = zer/*caret*/;
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "coin::zero() -> Coin<AptosCoin>",
+                "zero_count -> u8",
+            ]"#]],
+    );
+}
+
+#[test]
+fn test_insert_function_returning_expected_type() {
+    do_single_completion(
+        // language=Move
+        r#"
+module 0x1::coin {
+    struct Coin<phantom T> has store { value: u64 }
+    public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+}
+module 0x1::m {
+    use 0x1::coin::{Self, Coin};
+    struct AptosCoin {}
+    fun main() {
+        let c: Coin<AptosCoin> = zer/*caret*/;
+    }
+}
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::coin {
+                struct Coin<phantom T> has store { value: u64 }
+                public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+            }
+            module 0x1::m {
+                use 0x1::coin::{Self, Coin};
+                struct AptosCoin {}
+                fun main() {
+                    let c: Coin<AptosCoin> = coin::zero()/*caret*/;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_insert_function_returning_expected_type_imports_module() {
+    do_single_completion(
+        // language=Move
+        r#"
+module 0x1::coin {
+    struct Coin<phantom T> has store { value: u64 }
+    public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+}
+module 0x1::m {
+    struct AptosCoin {}
+    fun main() {
+        let c: 0x1::coin::Coin<AptosCoin> = zer/*caret*/;
+    }
+}
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::coin {
+                struct Coin<phantom T> has store { value: u64 }
+                public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+            }
+            module 0x1::m {
+                use 0x1::coin;
+
+                struct AptosCoin {}
+                fun main() {
+                    let c: 0x1::coin::Coin<AptosCoin> = coin::zero()/*caret*/;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_functions_returning_expected_type_from_other_modules_in_scope() {
+    check_completions(
+        // language=Move
+        r#"
+module 0x1::coin {
+    struct Coin<phantom T> has store { value: u64 }
+    public fun zero<T>(): Coin<T> { Coin { value: 0 } }
+}
+module 0x1::faucet {
+    use 0x1::coin::{Self, Coin};
+    public fun zero_from_faucet<T>(): Coin<T> { coin::zero() }
+    public fun zero_amount(): u64 { 0 }
+}
+module 0x1::m {
+    use 0x1::coin::Coin;
+    use 0x1::faucet;
+    struct AptosCoin {}
+    fun main() {
+        let c: Coin<AptosCoin> = zer/*caret*/;
+    }
+}
+    "#,
+        expect![[r#"
+            [
+                "faucet::zero_from_faucet() -> Coin<AptosCoin>",
+                "coin::zero() -> Coin<AptosCoin>",
+            ]"#]],
+    );
+}
@@ -0,0 +1,221 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::completion_utils::{check_completions, do_single_completion};
+use expect_test::expect;
+
+#[test]
+fn test_old_in_ensures() {
+    do_single_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main(a: u8): u8 { a }
+            spec main {
+                ensures ol/*caret*/
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main(a: u8): u8 { a }
+                spec main {
+                    ensures old(/*caret*/)
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_pragma_names() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main() {}
+            spec main {
+                pragma /*caret*/;
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "verify",
+                "verify_duration_estimate",
+                "timeout",
+                "seed",
+                "opaque",
+                "intrinsic",
+                "aborts_if_is_strict",
+                "aborts_if_is_partial",
+                "emits_is_strict",
+                "emits_is_partial",
+                "requires_if_aborts",
+                "addition_overflow_unchecked",
+                "assume_no_abort_from_here",
+                "export_ensures",
+                "friend",
+                "disable_invariants_in_body",
+                "delegate_invariants_to_caller",
+                "bv",
+                "bv_ret",
+                "unroll",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_pragma_names_skip_existing() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main() {}
+            spec main {
+                pragma verify, ver/*caret*/;
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "verify_duration_estimate",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_non_bool_pragma_inserts_value() {
+    do_single_completion(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main() {}
+            spec main {
+                pragma timeo/*caret*/;
+            }
+        }
+    "#,
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main() {}
+                spec main {
+                    pragma timeout = /*caret*/;
+                }
+            }
+        "#]],
+    )
+}
+
+#[test]
+fn test_bool_pragma_values() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            fun main() {}
+            spec main {
+                pragma verify = /*caret*/;
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "true",
+                "false",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_schema_lit_fields_in_include() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            spec schema S {
+                addr: address;
+                val: u8;
+            }
+            fun main() {}
+            spec main {
+                include S { /*caret*/ };
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "addr -> address",
+                "val -> u8",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_schema_lit_fields_skip_existing() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            spec schema S {
+                addr: address;
+                val: u8;
+            }
+            fun main() {}
+            spec main {
+                include S { addr: @0x1, /*caret*/ };
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "val -> u8",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_apply_patterns() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            spec schema S {}
+            fun get_a() {}
+            public fun get_b() {}
+            #[test]
+            fun test_a() {}
+            spec module {
+                apply S to get_a, /*caret*/;
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "get_b",
+                "*",
+            ]"#]],
+    )
+}
+
+#[test]
+fn test_apply_schema_name() {
+    check_completions(
+        // language=Move
+        r#"
+        module 0x1::m {
+            spec schema MySchema {}
+            fun main() {}
+            spec module {
+                apply My/*caret*/ to *;
+            }
+        }
+    "#,
+        expect![[r#"
+            [
+                "MySchema",
+            ]"#]],
+    )
+}
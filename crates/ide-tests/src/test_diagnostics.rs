@@ -14,15 +14,21 @@ mod test_missing_fields;
 mod test_missing_value_arguments;
 
 mod test_ability_checking;
+mod test_attr_checking;
 mod test_check_syntax;
+mod test_const_eval;
+mod test_control_flow_checking;
 mod test_field_shorthand;
+mod test_match_checking;
 mod test_missing_type_arguments;
 mod test_needs_type_annotation;
+mod test_private_item_access;
 mod test_redundant_cast;
 mod test_replace_with_friend;
 mod test_replace_with_package;
 mod test_replace_with_resource_index_expr;
 mod test_simplify_turbofish;
+mod test_spec_checking;
 mod test_spec_global_replace_with_index_expr;
 mod test_type_checking;
 mod test_type_checking_fs;
@@ -31,6 +37,7 @@ mod test_unresolved_reference_auto_import;
 mod test_unused_acquires;
 mod test_unused_imports;
 mod test_unused_imports_fix;
+mod test_unused_items;
 mod test_unused_variables;
 
 #[test]
@@ -0,0 +1,108 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::check_diagnostics;
+use expect_test::expect;
+
+#[test]
+fn test_no_errors_for_known_attributes() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[event]
+            struct Deposit has drop, store { amount: u64 }
+            #[resource_group(scope = global)]
+            struct Group {}
+            #[view]
+            public fun balance(): u64 { 1 }
+            #[test(account = @0x1)]
+            #[expected_failure(abort_code = 1, location = Self)]
+            fun test_balance(account: signer) {}
+            #[lint::skip(needless_ref)]
+            fun main() {}
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unknown_attribute() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[tset]
+            //^^^^ warn: Unknown attribute `tset`
+            fun main() {}
+            #[test_only, my_attr(a = 1)]
+                       //^^^^^^^ warn: Unknown attribute `my_attr`
+            fun helper() {}
+        }
+    "#]]);
+}
+
+#[test]
+fn test_view_fun_without_return_type() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[view]
+            //^^^^ err: View functions must return a value
+            public fun get() {}
+        }
+    "#]]);
+}
+
+#[test]
+fn test_view_fun_modifies_global_storage() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct Counter has key { val: u64 }
+            #[view]
+            public fun bump(addr: address): u64 {
+                let counter = borrow_global_mut<Counter>(addr);
+                            //^^^^^^^^^^^^^^^^^^^^^^^^^^ err: View functions cannot modify global storage, `borrow_global_mut` is not allowed
+                counter.val
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_event_struct_without_drop_and_store() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[event]
+            //^^^^^ err: Event types must have `drop` and `store` abilities, missing `store`
+            struct Deposit has drop { amount: u64 }
+            #[event]
+            //^^^^^ err: Event types must have `drop` and `store` abilities, missing `drop`, `store`
+            enum Withdraw { V1 { amount: u64 } }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_test_parameters_not_matching_signers() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[test(admin = @0x1, usr = @0x2)]
+                               //^^^ err: Test function has no parameter named `usr`
+            fun test_main(admin: signer, user: signer) {}
+                                       //^^^^^^^^^^^^ err: Missing address assignment for the test parameter `user`
+        }
+    "#]]);
+}
+
+#[test]
+fn test_test_parameter_without_signers() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            #[test]
+            fun test_main(fx: signer) {}
+                        //^^^^^^^^^^ err: Missing address assignment for the test parameter `fx`
+        }
+    "#]]);
+}
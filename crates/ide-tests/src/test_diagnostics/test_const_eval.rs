@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::check_diagnostics;
+use expect_test::expect;
+
+#[test]
+fn test_no_errors_for_valid_const_expressions() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            const MAX: u64 = 1 << 20;
+            const FEE_BPS: u64 = 30 * 100;
+            const LIMIT: u64 = MAX_U64 - FEE_BPS;
+            const MIN: i8 = -128;
+            const BYTES: vector<u8> = x"00ff";
+            public fun main() {
+                MAX; FEE_BPS; LIMIT; MIN; BYTES;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_integer_overflow_in_const() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            const SUM: u8 = 255 + 1;
+                          //^^^^^^^ error: Integer overflow in constant expression: result does not fit into `u8`
+            const LITERAL: u8 = 256;
+                              //^^^ error: Integer overflow in constant expression: result does not fit into `u8`
+            const CAST: u8 = (MAX_U64 as u8);
+                            //^^^^^^^^^^^^^ error: Integer overflow in constant expression: result does not fit into `u8`
+            public fun main() {
+                SUM; LITERAL; CAST;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_division_by_zero_in_const() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            const ZERO: u64 = 0;
+            const DIV: u64 = 10 / ZERO;
+                           //^^^^^^^^^ error: Division by zero in constant expression
+            const REM: u64 = 10 % (5 - 5);
+                           //^^^^^^^^^^^^ error: Division by zero in constant expression
+            public fun main() {
+                DIV; REM;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_shift_out_of_range_in_const() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            const SHIFT: u8 = 1u8 << 8;
+                            //^^^^^^^^ error: Shift amount in constant expression is out of range for `u8`
+            public fun main() {
+                SHIFT;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_error_is_not_reported_for_const_referencing_invalid_const() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            const INVALID: u8 = 255 + 1;
+                              //^^^^^^^ error: Integer overflow in constant expression: result does not fit into `u8`
+            const DERIVED: u8 = INVALID + 1;
+            public fun main() {
+                DERIVED;
+            }
+        }
+    "#]]);
+}
@@ -0,0 +1,157 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::{check_diagnostics, check_diagnostics_and_fix_with_id};
+use expect_test::expect;
+use ide_db::assists::AssistId;
+
+#[test]
+fn test_unreachable_code_after_return() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(): u8 {
+                return 1;
+                let _a = 2;
+              //^^^^^^^^^^^ warn: Unreachable code
+                3
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unreachable_code_after_abort_in_branch() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(c: bool) {
+                if (c) {
+                    abort 1;
+                    c = false;
+                  //^^^^^^^^^^ warn: Unreachable code
+                };
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unreachable_code_after_loop_without_break() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main() {
+                loop {};
+                let _a = 1;
+              //^^^^^^^^^^^ warn: Unreachable code
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_unreachable_code_after_loop_with_break() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main() {
+                let i = 0;
+                loop {
+                    if (i > 10) break;
+                    i = i + 1;
+                };
+                let _a = i;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_unreachable_code_after_labeled_break_from_inner_loop() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main() {
+                'outer: loop {
+                    loop {
+                        break 'outer;
+                    };
+                };
+                let _a = 1;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_missing_return_value() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(c: bool): u8 {
+                if (c) return 1;
+            }
+          //^ err: Missing return value of type `u8`
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_missing_return_value_if_body_ends_with_abort() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(c: bool): u8 {
+                if (c) return 1;
+                abort 1;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_unused_label_for_continue() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main() {
+                let i = 0;
+                'outer: while (i < 10) {
+                    i = i + 1;
+                    loop {
+                        continue 'outer
+                    }
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_remove_unused_label() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("remove-unused-label"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main() {
+                    'a: loop {
+                  //^^ warn: Unused label `'a`
+                        break
+                    }
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main() {
+                    loop {
+                        break
+                    }
+                }
+            }
+        "#]],
+    );
+}
@@ -0,0 +1,209 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::{check_diagnostics, check_diagnostics_and_fix_with_id};
+use expect_test::expect;
+use ide_db::assists::AssistId;
+
+#[test]
+fn test_non_exhaustive_match_on_enum() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Color { Red, Green, Blue }
+            fun main(c: Color): u8 {
+                match (c) {
+                     //^ err: Non-exhaustive `match`: missing `Color::Green`, `Color::Blue`
+                    Color::Red => 1,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_non_exhaustive_match_with_many_missing_variants() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Level { L1, L2, L3, L4, L5 }
+            fun main(l: Level): u8 {
+                match (l) {
+                     //^ err: Non-exhaustive `match`: missing `Level::L2`, `Level::L3`, `Level::L4` and 1 more
+                    Level::L1 => 1,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_non_exhaustive_match_on_nested_variant() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Inner { A, B }
+            enum Outer { V(Inner), W }
+            fun main(o: Outer): u8 {
+                match (o) {
+                     //^ err: Non-exhaustive `match`: missing `Outer::V(Inner::B)`
+                    Outer::V(Inner::A) => 1,
+                    Outer::W => 2,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_non_exhaustive_match_on_bool() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(b: bool): u8 {
+                match (b) {
+                     //^ err: Non-exhaustive `match`: missing `false`
+                    true => 1,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_guarded_arm_does_not_cover_variant() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Color { Red, Green, Blue }
+            fun main(c: Color, flag: bool): u8 {
+                match (c) {
+                     //^ err: Non-exhaustive `match`: missing `Color::Red`
+                    Color::Red if (flag) => 1,
+                    Color::Green => 2,
+                    Color::Blue => 3,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_error_for_exhaustive_match() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Shape { Circle { r: u64 }, Rect(u64, u64), Empty }
+            fun main(s: Shape): u64 {
+                match (s) {
+                    Shape::Circle { r } => r,
+                    Shape::Rect(w, ..) => w,
+                    Shape::Empty => 0,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unreachable_match_arm() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Color { Red, Green, Blue }
+            fun main(c: Color): u8 {
+                match (c) {
+                    Color::Red => 1,
+                    Color::Green => 2,
+                    Color::Red => 3,
+                  //^^^^^^^^^^ warn: Unreachable match arm
+                    Color::Blue => 4,
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_redundant_wildcard_arm() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            enum Color { Red, Green, Blue }
+            fun main(c: Color): u8 {
+                match (c) {
+                    Color::Red => 1,
+                    Color::Green => 2,
+                    Color::Blue => 3,
+                    _ => 4,
+                  //^ warn: Redundant wildcard arm: all values are already matched
+                }
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_add_missing_match_arms_after_block_arm() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("add-missing-match-arms"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                enum Color { Red, Green, Blue }
+                fun main(c: Color) {
+                    match (c) {
+                         //^ err: Non-exhaustive `match`: missing `Color::Blue`
+                        Color::Red => {}
+                        Color::Green => {}
+                    }
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                enum Color { Red, Green, Blue }
+                fun main(c: Color) {
+                    match (c) {
+                        Color::Red => {}
+                        Color::Green => {}
+                        Color::Blue => abort 0,
+                    }
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_add_missing_match_arms_with_fields() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("add-missing-match-arms"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                enum Shape { Circle { r: u64 }, Rect(u64, u64), Empty }
+                fun area(s: &Shape): u64 {
+                    match (s) {
+                         //^ err: Non-exhaustive `match`: missing `Shape::Rect(..)`, `Shape::Empty`
+                        Shape::Circle { r } => *r
+                    }
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                enum Shape { Circle { r: u64 }, Rect(u64, u64), Empty }
+                fun area(s: &Shape): u64 {
+                    match (s) {
+                        Shape::Circle { r } => *r,
+                        Shape::Rect(..) => abort 0,
+                        Shape::Empty => abort 0,
+                    }
+                }
+            }
+        "#]],
+    );
+}
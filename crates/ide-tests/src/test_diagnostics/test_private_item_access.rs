@@ -0,0 +1,289 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::{
+    check_diagnostics_and_fix_with_id, check_diagnostics_no_fix, check_diagnostics_on_tmpfs,
+};
+use expect_test::expect;
+use ide_db::assists::AssistId;
+use test_utils::fixtures;
+use test_utils::fixtures::test_state::{named_with_deps, package};
+
+#[test]
+fn test_make_private_function_public() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("make-public"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                     //^^^^ err: `0x1::m::call` is private to its module
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_make_private_function_public_friend() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("make-public-friend"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                     //^^^^ err: `0x1::m::call` is private to its module
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                friend 0x1::main;
+
+                public(friend) fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_make_private_function_public_package() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("make-public-package"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m::call;
+                          //^^^^ err: `0x1::m::call` is private to its module
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public(package) fun call() {}
+            }
+            module 0x1::main {
+                use 0x1::m::call;
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_make_inline_function_public() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("make-public"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                inline fun call() {}
+            }
+            module 0x1::main {
+                fun main() {
+                    0x1::m::call();
+                          //^^^^ err: `0x1::m::call` is private to its module
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                public inline fun call() {}
+            }
+            module 0x1::main {
+                fun main() {
+                    0x1::m::call();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_add_friend_declaration_for_friend_function() {
+    check_diagnostics_and_fix_with_id(
+        AssistId::quick_fix("make-public-friend"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                use 0x1::string;
+                friend fun call() {}
+            }
+            module 0x1::string {}
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                     //^^^^ err: `0x1::m::call` is visible only to the friends of its module
+                }
+            }
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                use 0x1::string;
+                friend 0x1::main;
+
+                friend fun call() {}
+            }
+            module 0x1::string {}
+            module 0x1::main {
+                use 0x1::m;
+                fun main() {
+                    m::call();
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_no_friend_fix_for_module_at_another_address() {
+    check_diagnostics_no_fix(
+        AssistId::quick_fix("make-public-friend"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun call() {}
+            }
+            module 0x2::main {
+                fun main() {
+                    0x1::m::call();
+                          //^^^^ err: `0x1::m::call` is private to its module
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_package_function_from_another_package() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![
+        named_with_deps(
+            "main",
+            // language=TOML
+            r#"
+[dependencies]
+M = { local = "../m"}
+        "#,
+            // language=Move
+            r#"
+//- /main.move
+module 0x1::main {
+    fun main() {
+        0x1::m::call();/*caret*/
+    }
+}
+"#,
+        ),
+        package(
+            "m",
+            // language=TOML
+            r#"
+[package]
+name = "M"
+version = "0.1.0"
+            "#,
+            // language=Move
+            r#"
+//- /m.move
+module 0x1::m {
+    package fun call() {}
+}
+"#,
+        ),
+    ]);
+    // language=Move
+    check_diagnostics_on_tmpfs(
+        test_state,
+        expect![[r#"
+        module 0x1::main {
+            fun main() {
+                0x1::m::call();/*caret*/
+                      //^^^^ err: `0x1::m::call` is `package` and cannot be used outside of its package
+            }
+        }
+    "#]],
+    );
+}
+
+#[test]
+fn test_friend_declaration_for_module_from_another_package() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![
+        named_with_deps(
+            "main",
+            // language=TOML
+            r#"
+[dependencies]
+M = { local = "../m"}
+        "#,
+            // language=Move
+            r#"
+//- /main.move
+module 0x1::main {
+    friend 0x1::m;
+    friend 0x1::other;/*caret*/
+}
+module 0x1::other {}
+"#,
+        ),
+        package(
+            "m",
+            // language=TOML
+            r#"
+[package]
+name = "M"
+version = "0.1.0"
+            "#,
+            // language=Move
+            r#"
+//- /m.move
+module 0x1::m {}
+"#,
+        ),
+    ]);
+    // language=Move
+    check_diagnostics_on_tmpfs(
+        test_state,
+        expect![[r#"
+        module 0x1::main {
+            friend 0x1::m;
+                 //^^^^^^ err: Invalid `friend` declaration: cannot declare modules out of the current package as friends
+            friend 0x1::other;/*caret*/
+        }
+        module 0x1::other {}
+    "#]],
+    );
+}
@@ -0,0 +1,232 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::check_diagnostics;
+use expect_test::expect;
+
+#[test]
+fn test_old_in_requires() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(a: u8): u8 { a }
+            spec main {
+                requires old(a) == 1;
+                       //^^^ err: `old(..)` can only be used in post-conditions
+                aborts_if old(a) == 1;
+                        //^^^ err: `old(..)` can only be used in post-conditions
+                ensures old(a) == result;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_old_in_invariants() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct S has key { val: u8 }
+            spec module {
+                invariant forall a: address: old(exists<S>(a)) ==> exists<S>(a);
+                                           //^^^ err: `old(..)` can only be used in post-conditions
+                invariant update forall a: address: old(exists<S>(a)) ==> exists<S>(a);
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_error_for_old_in_let_post_and_inline_spec() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(a: u8): u8 {
+                spec {
+                    assert old(a) == 1;
+                };
+                a
+            }
+            spec main {
+                let post old_a = old(a);
+                ensures old_a == result;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_result_outside_ensures() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(): (u8, u8) { (1, 1) }
+            spec main {
+                requires result_1 == 1;
+                       //^^^^^^^^ err: `result_1` can only be used in `ensures` conditions
+                aborts_if result_2 == 1;
+                        //^^^^^^^^ err: `result_2` can only be used in `ensures` conditions
+                ensures result_1 == result_2;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_error_for_result_param() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main(result: u8): u8 { result }
+            spec main {
+                requires result == 1;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_global_without_key_ability() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct S has store { val: u8 }
+            struct R has key { val: u8 }
+            fun main() {}
+            spec main {
+                ensures global<S>(@0x1).val == 1;
+                             //^ err: Missing required ability `key`
+                ensures exists<S>(@0x1);
+                             //^ err: Missing required ability `key`
+                ensures exists<R>(@0x1);
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_impure_function_call_from_spec() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct S has key { val: u8 }
+            fun pure(a: &S): u8 { a.val }
+            fun modify(a: &mut S): bool { a.val = 1; true }
+            fun publish(s: &signer): bool { move_to(s, S { val: 1 }); true }
+            native fun main(s: S, account: signer);
+            spec main {
+                ensures pure(s) == 1;
+                ensures modify(s);
+                      //^^^^^^ err: Function `modify` has side effects and cannot be called from specifications
+                ensures publish(account);
+                      //^^^^^^^ err: Function `publish` has side effects and cannot be called from specifications
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_impure_function_call_through_other_functions_from_spec() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct S has key { val: u8 }
+            fun publish(s: &signer): bool { move_to(s, S { val: 1 }); true }
+            fun publish_twice(s: &signer): bool { publish(s) && publish(s) }
+            fun count(n: u8): u8 { if (n == 0) 0 else count(n - 1) }
+            native fun main(account: signer);
+            spec main {
+                ensures count(1) == 0;
+                ensures publish_twice(account);
+                      //^^^^^^^^^^^^^ err: Function `publish_twice` has side effects and cannot be called from specifications
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unknown_schema_field_in_include() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            spec schema S {
+                addr: address;
+            }
+            native fun main(addr: address);
+            spec main {
+                include S { addr, account: @0x1 };
+                                //^^^^^^^ err: Unknown field `account` for schema `S`
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unbound_schema_field_in_include() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            spec schema S {
+                addr: address;
+                val: u8;
+            }
+            native fun main(addr: address);
+            spec main {
+                include S;
+                      //^ err: Schema field `val` is not bound in the including context
+                include S { val: 1 };
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unbound_schema_field_in_apply() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            spec schema S {
+                addr: address;
+            }
+            public native fun get_a(addr: address);
+            public native fun get_b(account: address);
+            native fun set_c(val: u8);
+            spec module {
+                apply S to get_*;
+                    //^ err: Schema field `addr` is not a parameter of function `get_b`
+                apply S to public *;
+                    //^ err: Schema field `addr` is not a parameter of function `get_b`
+                apply S to * except get_b, set_c;
+                apply S { addr: @0x1 } to *;
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_unknown_pragma() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            fun main() {}
+            spec main {
+                pragma verify = false, opaque;
+                pragma verfy = false;
+                     //^^^^^ warn: Unknown pragma `verfy`
+            }
+        }
+    "#]]);
+}
+
+#[test]
+fn test_no_unknown_pragma_for_intrinsic() {
+    // language=Move
+    check_diagnostics(expect![[r#"
+        module 0x1::m {
+            struct Table {}
+            spec Table {
+                pragma intrinsic = map, map_new = new, custom_name = custom;
+            }
+        }
+    "#]]);
+}
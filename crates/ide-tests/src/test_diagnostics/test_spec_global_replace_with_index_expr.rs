@@ -58,7 +58,9 @@ fn test_no_trigger_if_global_function_has_invalid_type_param() {
             }
             spec main {
                 global<u8>(@0x1);
+                     //^^ err: Missing required ability `key`
                 global<Res>(@0x1);
+                     //^^^ err: Missing required ability `key`
             }
         }
     "#]]);
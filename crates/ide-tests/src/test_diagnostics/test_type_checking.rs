@@ -291,7 +291,7 @@ fn test_error_on_code_block_if_empty_block_and_return_type() {
     check_diagnostics(expect![[r#"
         module 0x1::M {
             fun call(): u8 {}
-                          //^ err: Incompatible type '()', expected 'u8'
+                          //^ err: Missing return value of type `u8`
         }
     "#]]);
 }
@@ -1586,6 +1586,7 @@ fn test_abort_expr_requires_integer() {
             fun main() {
                 abort 1;
                 abort 1u8;
+              //^^^^^^^^^^ warn: Unreachable code
                     //^^^ err: Incompatible type 'u8', expected any of ['u64', 'vector<u8>']
                 abort 1u64;
                 abort false;
@@ -2647,6 +2648,7 @@ fn test_abort_with_integer_type() {
             fun main() {
                 abort 1u64;
                 abort 1u8;
+              //^^^^^^^^^^ warn: Unreachable code
                     //^^^ err: Incompatible type 'u8', expected any of ['u64', 'vector<u8>']
                 abort 1u16;
                     //^^^^ err: Incompatible type 'u16', expected any of ['u64', 'vector<u8>']
@@ -2663,6 +2665,7 @@ fn test_abort_with_vector_u8() {
             fun main() {
                 abort b"1234";
                 abort vector[1, 2, 3, 4];
+              //^^^^^^^^^^^^^^^^^^^^^^^^^ warn: Unreachable code
                 abort vector[1u8, 2u8];
                 abort vector[1u128, 2u128];
                     //^^^^^^^^^^^^^^^^^^^^ err: Incompatible type 'vector<u128>', expected any of ['u64', 'vector<u8>']
@@ -2679,6 +2682,7 @@ fn test_abort_with_block_returning_u64() {
             fun main() {
                 abort { 1u64 };
                 abort { b"1234" };
+              //^^^^^^^^^^^^^^^^^^ warn: Unreachable code
                 abort { vector[1u8] };
                 abort { vector[1u64] };
                     //^^^^^^^^^^^^^^^^ err: Incompatible type 'vector<u64>', expected any of ['u64', 'vector<u8>']
@@ -2799,6 +2803,7 @@ fn test_match_with_integers_and_ranges() {
             fun main1(): bool {
                 let x = 0u8;
                 match (x) {
+                     //^ err: Non-exhaustive `match`: missing `_`
                     0 => true,
                     1 => false,
                     2..3 => false,
@@ -44,7 +44,7 @@ fn test_unresolved_private_function() {
         }
         module 0x1::main {
             use 0x1::m::call;
-                      //^^^^ err: Unresolved reference `call`: cannot resolve (note: item is private)
+                      //^^^^ err: `0x1::m::call` is private to its module
             fun main() {
                 call();
               //^^^^ err: Unresolved reference `call`: cannot resolve
@@ -64,7 +64,7 @@ fn test_unresolved_private_function_with_module() {
             use 0x1::m;
             fun main() {
                 m::call();
-                 //^^^^ err: Unresolved reference `call`: cannot resolve (note: item is private)
+                 //^^^^ err: `0x1::m::call` is private to its module
             }
         }
     "#]]);
@@ -81,7 +81,7 @@ fn test_unresolved_friend_function_with_module() {
             use 0x1::m;
             fun main() {
                 m::call();
-                 //^^^^ err: Unresolved reference `call`: cannot resolve (note: item is defined with `friend` visibility)
+                 //^^^^ err: `0x1::m::call` is visible only to the friends of its module
             }
         }
     "#]]);
@@ -362,6 +362,7 @@ fn test_unresolved_reference_for_schema_field_shorthand() {
             spec schema Schema {}
             spec module {
                 include Schema { addr };
+                               //^^^^ err: Unknown field `addr` for schema `Schema`
                                //^^^^ err: Unresolved reference `addr`: cannot resolve
             }
         }
@@ -0,0 +1,244 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ide_test_utils::diagnostics::{
+    check_diagnostics_on_tmpfs_with_config, check_diagnostics_with_config,
+    check_diagnostics_with_config_and_fix_with_id,
+};
+use expect_test::expect;
+use ide_db::assists::AssistId;
+use ide_diagnostics::config::DiagnosticsConfig;
+use test_utils::fixtures;
+use test_utils::fixtures::test_state::named;
+
+fn unused_items_config() -> DiagnosticsConfig {
+    DiagnosticsConfig {
+        unused_items: true,
+        ..DiagnosticsConfig::test_sample()
+    }
+}
+
+#[test]
+fn test_unused_private_functions() {
+    // language=Move
+    check_diagnostics_with_config(
+        unused_items_config(),
+        expect![[r#"
+            module 0x1::m {
+                fun used(): u8 { 1 }
+                fun unused() {}
+                  //^^^^^^ warn: Function `unused` is never used
+                fun recursive(n: u64): u64 { if (n == 0) 0 else recursive(n - 1) }
+                  //^^^^^^^^^ warn: Function `recursive` is never used
+                fun _ignored() {}
+                fun init_module(_account: &signer) {}
+                entry fun main() { used(); }
+                #[test]
+                fun test_main() {}
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_spec_usages() {
+    // language=Move
+    check_diagnostics_with_config(
+        unused_items_config(),
+        expect![[r#"
+            module 0x1::m {
+                const MAX: u64 = 10;
+                const UNUSED: u64 = 20;
+                    //^^^^^^ warn: Constant `UNUSED` is never used
+                fun helper(): u64 { 1 }
+                  //^^^^^^ warn: Function `helper` is never used
+                spec helper {
+                    ensures result <= MAX;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_unused_friend_and_package_functions() {
+    // language=Move
+    check_diagnostics_with_config(
+        unused_items_config(),
+        expect![[r#"
+            module 0x1::m {
+                friend 0x1::n;
+                public(friend) fun used_by_friend() {}
+                public(friend) fun unused_friend() {}
+                                 //^^^^^^^^^^^^^ warn: Function `unused_friend` is never used
+                package fun unused_package() {}
+                          //^^^^^^^^^^^^^^ warn: Function `unused_package` is never used
+                public fun unused_public() {}
+            }
+            module 0x1::n {
+                public fun call() { 0x1::m::used_by_friend() }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_unused_test_only_structs() {
+    // language=Move
+    check_diagnostics_with_config(
+        unused_items_config(),
+        expect![[r#"
+            module 0x1::m {
+                struct Unused {}
+                enum UnusedEnum { One }
+                #[test_only]
+                struct UnusedTestOnly {}
+                     //^^^^^^^^^^^^^^ warn: Test-only struct `UnusedTestOnly` is never used
+                #[test_only]
+                struct UsedOutside {}
+            }
+            #[test_only]
+            module 0x1::n {
+                public fun accept(_s: &0x1::m::UsedOutside) {}
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_package_function_used_in_another_file() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![named(
+        "main",
+        // language=Move
+        r#"
+//- /m.move
+module 0x1::m {
+    package fun used_in_other_file() {}/*caret*/
+    package fun unused() {}
+}
+//- /n.move
+module 0x1::n {
+    // unused() is mentioned only in the comment
+    public fun call() { 0x1::m::used_in_other_file() }
+}
+"#,
+    )]);
+    // language=Move
+    check_diagnostics_on_tmpfs_with_config(
+        test_state,
+        unused_items_config(),
+        expect![[r#"
+        module 0x1::m {
+            package fun used_in_other_file() {}/*caret*/
+            package fun unused() {}
+                      //^^^^^^ warn: Function `unused` is never used
+        }
+    "#]],
+    );
+}
+
+#[test]
+fn test_constant_used_in_abort_code_of_another_module() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![named(
+        "main",
+        // language=Move
+        r#"
+//- /m.move
+module 0x1::m {
+    const E_USED_IN_TEST: u64 = 1;/*caret*/
+    const E_UNUSED: u64 = 2;
+}
+//- /m_tests.move
+#[test_only]
+module 0x1::m_tests {
+    #[test]
+    #[expected_failure(abort_code = 0x1::m::E_USED_IN_TEST)]
+    fun test_abort() {
+        abort 1
+    }
+}
+"#,
+    )]);
+    // language=Move
+    check_diagnostics_on_tmpfs_with_config(
+        test_state,
+        unused_items_config(),
+        expect![[r#"
+        module 0x1::m {
+            const E_USED_IN_TEST: u64 = 1;/*caret*/
+            const E_UNUSED: u64 = 2;
+                //^^^^^^^^ warn: Constant `E_UNUSED` is never used
+        }
+    "#]],
+    );
+}
+
+#[test]
+fn test_unused_test_only_function() {
+    // language=Move
+    check_diagnostics_with_config(
+        unused_items_config(),
+        expect![[r#"
+            module 0x1::m {
+                #[test_only]
+                public fun setup(): u64 { 1 }
+                #[test_only]
+                public fun unused_setup() {}
+                         //^^^^^^^^^^^^ warn: Test-only function `unused_setup` is never used
+            }
+            #[test_only]
+            module 0x1::m_tests {
+                #[test]
+                fun test_m() {
+                    assert!(0x1::m::setup() == 1, 1);
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_no_unused_items_if_disabled() {
+    // language=Move
+    check_diagnostics_with_config(
+        DiagnosticsConfig::test_sample(),
+        expect![[r#"
+            module 0x1::m {
+                const UNUSED: u64 = 20;
+                fun unused() {}
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_remove_unused_friend() {
+    check_diagnostics_with_config_and_fix_with_id(
+        unused_items_config(),
+        AssistId::quick_fix("remove-unused-friend"),
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                friend 0x1::n;
+                friend 0x1::o;
+              //^^^^^^^^^^^^^^ warn: Unused friend declaration: `0x1::o` does not call any friend functions
+                public(friend) fun f() {}
+            }
+            module 0x1::n {
+                public fun call() { 0x1::m::f() }
+            }
+            module 0x1::o {}
+        "#]],
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                friend 0x1::n;
+                public(friend) fun f() {}
+            }
+            module 0x1::n {
+                public fun call() { 0x1::m::f() }
+            }
+            module 0x1::o {}
+        "#]],
+    );
+}
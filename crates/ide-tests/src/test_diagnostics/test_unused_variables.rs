@@ -235,7 +235,7 @@ fn test_no_unused_variable_for_test_parameter() {
     // language=Move
     check_diagnostics(expect![[r#"
         module 0x1::M {
-            #[test]
+            #[test(fx = @0x1)]
             fun test_main(fx: signer) {}
         }
     "#]]);
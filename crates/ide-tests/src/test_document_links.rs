@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use ide::document_links::{DocumentLink, DocumentLinkTarget, DocumentLinksConfig};
+use test_utils::fixtures;
+use test_utils::fixtures::test_state::package;
+
+const EXPLORER_URL: &str = "https://explorer.aptoslabs.com/account/{address}";
+
+fn links_config(address_explorer_url: Option<&str>) -> DocumentLinksConfig {
+    DocumentLinksConfig {
+        address_explorer_url: address_explorer_url.map(|it| it.to_string()),
+    }
+}
+
+fn link_texts(links: &[DocumentLink], file_text: &str) -> Vec<String> {
+    links.iter().map(|it| file_text[it.range].to_string()).collect()
+}
+
+#[test]
+fn test_url_and_item_links_in_doc_comments() {
+    // language=Move
+    let source = r#"
+module 0x1::m {
+    struct S {}
+    /// See https://aptos.dev/move. Returns [`S`], like [0x1::m::call] does,
+    /// but not [the docs](https://aptos.dev) or [unknown].
+    fun main(): S { call() }
+    fun call(): S { S {} }
+}
+"#;
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+    let links = analysis.document_links(&links_config(None), file_id).unwrap();
+
+    assert_eq!(
+        link_texts(&links, source),
+        vec!["https://aptos.dev/move", "S", "0x1::m::call", "https://aptos.dev"]
+    );
+    assert!(matches!(&links[0].target, DocumentLinkTarget::Url(url) if url == "https://aptos.dev/move"));
+    let DocumentLinkTarget::Item(nav_target) = &links[1].target else {
+        panic!("expected item link, got {:?}", links[1].target);
+    };
+    assert_eq!(nav_target.name, "S");
+    assert_eq!(links[1].tooltip.as_deref(), Some("Go to `S`"));
+    let DocumentLinkTarget::Item(nav_target) = &links[2].target else {
+        panic!("expected item link, got {:?}", links[2].target);
+    };
+    assert_eq!(nav_target.name, "call");
+}
+
+#[test]
+fn test_address_explorer_links() {
+    // language=Move
+    let source = r#"
+module 0x1::m {
+    fun main() {
+        let a = @0x0000000000000000000000000000000000000000000000000000000000000042;
+        let b = @std;
+        0x1::m::main();
+    }
+}
+"#;
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+
+    let links = analysis
+        .document_links(&links_config(Some(EXPLORER_URL)), file_id)
+        .unwrap();
+    let targets = links
+        .iter()
+        .map(|link| match &link.target {
+            DocumentLinkTarget::Url(url) => url.clone(),
+            target => panic!("expected url, got {target:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        vec![
+            "https://explorer.aptoslabs.com/account/0x42",
+            "https://explorer.aptoslabs.com/account/0x1",
+        ]
+    );
+    assert_eq!(links[0].tooltip.as_deref(), Some("Open 0x42 in the explorer"));
+    // addresses of the module paths are not links
+    assert_eq!(link_texts(&links, source)[1], "std");
+
+    let links = analysis.document_links(&links_config(None), file_id).unwrap();
+    assert!(links.is_empty());
+}
+
+#[test]
+fn test_local_dependency_links_in_manifest() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![
+        package(
+            "MyApp",
+            // language=TOML
+            r#"
+            # manifest of the app
+            [package]
+            name = "MyApp"
+            version = "0.1.0"
+
+            [dependencies]
+            MyDep = { local = "../MyDep" }
+
+            [dev-dependencies.MyDep]
+            # local = "../Commented"
+            local = "../MyDep"
+            "#,
+            r#"
+            //- main.move
+            module 0x1::main {}
+            "#,
+        ),
+        package(
+            "MyDep",
+            // language=TOML
+            r#"
+            [package]
+            name = "MyDep"
+            version = "0.1.0"
+            "#,
+            r#"
+            //- dep.move
+            module 0x1::dep {}
+            "#,
+        ),
+    ]);
+    let (file_id, file_text) = test_state.file_with_caret("# manifest of the app");
+    let links = test_state
+        .analysis()
+        .document_links(&links_config(Some(EXPLORER_URL)), file_id)
+        .unwrap();
+
+    assert_eq!(link_texts(&links, &file_text), vec!["../MyDep", "../MyDep"]);
+    let DocumentLinkTarget::File(target_path) = &links[0].target else {
+        panic!("expected file link, got {:?}", links[0].target);
+    };
+    let expected_path = test_state.ws_root.join("MyDep").join("Move.toml");
+    assert_eq!(target_path.as_path().unwrap().as_str(), expected_path.as_str());
+}
@@ -18,3 +18,82 @@ fn test_inlay_range_expr_hint() {
         }
     "#]]);
 }
+
+#[test]
+fn test_inlay_const_value_hints() {
+    // language=Move
+    check_inlay_hints(expect![[r#"
+        module 0x1::m {
+            const MAX: u64 = 1 << 20;
+                           //^^^^^^^ = 1048576
+            const FEE_BPS: u64 = 30 * 100;
+                               //^^^^^^^^ = 3000
+            const ONE: u8 = 1;
+            const NAME: vector<u8> = b"name";
+            const FLAGS: vector<bool> = vector[true, !false && ONE > 0];
+                                      //^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ = vector[true, true]
+        }
+    "#]]);
+}
+
+#[test]
+fn test_inlay_const_value_hints_for_const_chain() {
+    // every constant is evaluated only once, otherwise it takes 2^24 evaluations
+    // language=Move
+    check_inlay_hints(expect![[r#"
+        module 0x1::m {
+            const C0: u64 = 1;
+            const C1: u64 = C0 + C0;
+                          //^^^^^^^ = 2
+            const C2: u64 = C1 + C1;
+                          //^^^^^^^ = 4
+            const C3: u64 = C2 + C2;
+                          //^^^^^^^ = 8
+            const C4: u64 = C3 + C3;
+                          //^^^^^^^ = 16
+            const C5: u64 = C4 + C4;
+                          //^^^^^^^ = 32
+            const C6: u64 = C5 + C5;
+                          //^^^^^^^ = 64
+            const C7: u64 = C6 + C6;
+                          //^^^^^^^ = 128
+            const C8: u64 = C7 + C7;
+                          //^^^^^^^ = 256
+            const C9: u64 = C8 + C8;
+                          //^^^^^^^ = 512
+            const C10: u64 = C9 + C9;
+                           //^^^^^^^ = 1024
+            const C11: u64 = C10 + C10;
+                           //^^^^^^^^^ = 2048
+            const C12: u64 = C11 + C11;
+                           //^^^^^^^^^ = 4096
+            const C13: u64 = C12 + C12;
+                           //^^^^^^^^^ = 8192
+            const C14: u64 = C13 + C13;
+                           //^^^^^^^^^ = 16384
+            const C15: u64 = C14 + C14;
+                           //^^^^^^^^^ = 32768
+            const C16: u64 = C15 + C15;
+                           //^^^^^^^^^ = 65536
+            const C17: u64 = C16 + C16;
+                           //^^^^^^^^^ = 131072
+            const C18: u64 = C17 + C17;
+                           //^^^^^^^^^ = 262144
+            const C19: u64 = C18 + C18;
+                           //^^^^^^^^^ = 524288
+            const C20: u64 = C19 + C19;
+                           //^^^^^^^^^ = 1048576
+            const C21: u64 = C20 + C20;
+                           //^^^^^^^^^ = 2097152
+            const C22: u64 = C21 + C21;
+                           //^^^^^^^^^ = 4194304
+            const C23: u64 = C22 + C22;
+                           //^^^^^^^^^ = 8388608
+            const C24: u64 = C23 + C23;
+                           //^^^^^^^^^ = 16777216
+            // cycles are not evaluated
+            const X: u64 = Y + 1;
+            const Y: u64 = X * 2;
+        }
+    "#]]);
+}
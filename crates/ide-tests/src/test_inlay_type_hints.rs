@@ -15,6 +15,7 @@ const DISABLED_CONFIG: InlayHintsConfig = InlayHintsConfig {
     tuple_type_hints: false,
     parameter_hints: false,
     range_exclusive_hints: false,
+    const_value_hints: false,
     hide_closure_parameter_hints: false,
     fields_to_resolve: InlayFieldsToResolve::empty(),
 };
@@ -24,6 +25,7 @@ const TEST_CONFIG: InlayHintsConfig = InlayHintsConfig {
     tuple_type_hints: true,
     parameter_hints: true,
     range_exclusive_hints: true,
+    const_value_hints: true,
     ..DISABLED_CONFIG
 };
 
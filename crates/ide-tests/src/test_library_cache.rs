@@ -0,0 +1,79 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use base_db::SourceDatabase;
+use base_db::change::FileChanges;
+use base_db::package_root::{PackageId, PackageKind, PackageRoot};
+use ide_db::RootDatabase;
+use ide_db::library_cache::{LibraryCache, cached_symbol_index};
+use ide_db::symbol_index::{Query, world_symbols};
+use paths::Utf8Path;
+use test_utils::testdir::TestDir;
+use vfs::file_set::FileSet;
+use vfs::{AbsPathBuf, FileId, VfsPath};
+
+fn library_db(dir: &Utf8Path, source: &str) -> RootDatabase {
+    let manifest_file_id = FileId::from_raw(1);
+    let source_file_id = FileId::from_raw(2);
+    let mut file_set = FileSet::default();
+    file_set.insert(
+        manifest_file_id,
+        VfsPath::new_real_path(dir.join("lib/Move.toml").to_string()),
+    );
+    file_set.insert(
+        source_file_id,
+        VfsPath::new_real_path(dir.join("lib/sources/main.move").to_string()),
+    );
+
+    let mut changes = FileChanges::new();
+    changes.set_package_roots(vec![PackageRoot::new(
+        file_set,
+        PackageKind::Library,
+        Some(manifest_file_id),
+    )]);
+    changes.change_file(manifest_file_id, Some("[package]\nname = \"Lib\"\n".to_owned()));
+    changes.change_file(source_file_id, Some(source.to_owned()));
+
+    let mut db = RootDatabase::new();
+    db.apply_change(changes);
+    db.set_library_cache(Some(LibraryCache::new(
+        AbsPathBuf::assert(dir.join("cache")),
+        "0.0.0".to_owned(),
+    )));
+    db
+}
+
+fn library_package_id(db: &RootDatabase) -> PackageId {
+    db.all_package_ids().data(db)[0]
+}
+
+#[test]
+fn test_library_symbols_are_restored_from_disk_cache() {
+    let dir = TestDir::new();
+    let source = "module 0x1::main { fun main() {} }";
+
+    let db = library_db(dir.path(), source);
+    assert!(cached_symbol_index(&db, library_package_id(&db)).is_none());
+    let symbols = world_symbols(&db, Query::new("main", None));
+    assert_eq!(symbols.len(), 2);
+
+    // next session
+    let db = library_db(dir.path(), source);
+    assert!(cached_symbol_index(&db, library_package_id(&db)).is_some());
+    let restored_symbols = world_symbols(&db, Query::new("main", None));
+    assert_eq!(restored_symbols, symbols);
+    assert!(restored_symbols.iter().all(|it| it.syntax_loc(&db).is_some()));
+}
+
+#[test]
+fn test_library_disk_cache_is_invalidated_by_file_changes() {
+    let dir = TestDir::new();
+
+    let db = library_db(dir.path(), "module 0x1::main { fun main() {} }");
+    world_symbols(&db, Query::new("main", None));
+
+    let db = library_db(dir.path(), "module 0x1::main { fun main2() {} }");
+    assert!(cached_symbol_index(&db, library_package_id(&db)).is_none());
+    let symbols = world_symbols(&db, Query::new("main2", None));
+    assert_eq!(symbols.len(), 1);
+}
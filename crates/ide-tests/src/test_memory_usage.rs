@@ -0,0 +1,85 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use ide_db::assists::AssistResolveStrategy;
+use ide_db::symbol_index::Query;
+use ide_diagnostics::config::DiagnosticsConfig;
+use test_utils::fixtures;
+
+#[test]
+fn test_memory_usage_by_query_and_package() {
+    // language=Move
+    let source = r#"
+module 0x1::main {
+    struct S { val: u8 }
+    fun main(s: S): u8 {
+        let a = s.val;
+        a + 1
+    }
+}
+    "#;
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+    // infer types of `main`
+    let frange = analysis.full_file_range(file_id).unwrap();
+    analysis
+        .semantic_diagnostics(
+            &DiagnosticsConfig::test_sample(),
+            AssistResolveStrategy::None,
+            frange,
+        )
+        .unwrap();
+    // build the symbol index of the package
+    analysis.symbol_search(Query::new("main", None)).unwrap();
+
+    let memory_usage = analysis.memory_usage().unwrap();
+
+    let query_bytes = |name: &str| {
+        let query = memory_usage.queries.iter().find(|it| it.name == name).unwrap();
+        assert!(query.count > 0, "no {name}");
+        query.bytes
+    };
+    assert!(query_bytes("file texts") >= source.len());
+    assert!(query_bytes("syntax trees") > 0);
+    assert!(query_bytes("symbol indices") > 0);
+    assert!(query_bytes("inference results") > 0);
+
+    let main_package = memory_usage
+        .packages
+        .iter()
+        .find(|it| it.text_bytes == source.len())
+        .unwrap();
+    assert!(!main_package.is_library);
+    assert_eq!(main_package.files, 1);
+    assert!(main_package.parse_bytes > main_package.text_bytes);
+    assert!(main_package.symbol_index_bytes > 0);
+
+    let report = memory_usage.to_string();
+    assert!(report.contains("By query:"), "{report}");
+    assert!(report.contains("By package:"), "{report}");
+}
+
+#[test]
+fn test_memory_usage_does_not_compute_missing_values() {
+    // language=Move
+    let source = r#"
+module 0x1::main {
+    fun main(): u8 { 1 }
+}
+    "#;
+    let (analysis, _) = fixtures::from_single_file(source.to_string());
+
+    let memory_usage = analysis.memory_usage().unwrap();
+    for query in &memory_usage.queries {
+        if query.name != "file texts" {
+            assert_eq!((query.count, query.bytes), (0, 0), "{} are computed", query.name);
+        }
+    }
+    // the report itself must not memoize anything either
+    let memory_usage = analysis.memory_usage().unwrap();
+    assert!(
+        memory_usage
+            .packages
+            .iter()
+            .all(|it| it.parse_bytes == 0 && it.symbol_index_bytes == 0)
+    );
+}
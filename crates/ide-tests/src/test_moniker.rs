@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use ide::moniker::{ItemPath, MonikerKind, MonikerUniqueness};
+use syntax::files::FilePosition;
+use test_utils::fixtures;
+use test_utils::fixtures::test_state::package;
+
+#[test]
+fn test_moniker_of_dependency_item_and_back() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![
+        package(
+            "MyApp",
+            // language=TOML
+            r#"
+            [package]
+            name = "MyApp"
+            version = "0.1.0"
+
+            [dependencies]
+            MyDep = { local = "../MyDep" }
+
+            [addresses]
+            my_app = "0x42"
+            "#,
+            r#"
+            //- main.move
+            module my_app::main {
+                use dep_addr::dep;
+                fun main() {
+                    dep::/*caret*/call();
+                }
+            }
+            "#,
+        ),
+        package(
+            "MyDep",
+            // language=TOML
+            r#"
+            [package]
+            name = "MyDep"
+            version = "0.1.0"
+
+            [addresses]
+            dep_addr = "0x0000000000000000000000000000000000000000000000000000000000000043"
+            "#,
+            r#"
+            //- dep.move
+            module dep_addr::dep {
+                public fun call() {}
+            }
+            "#,
+        ),
+    ]);
+    let analysis = test_state.analysis();
+    let (file_id, file_text) = test_state.file_with_caret("/*caret*/");
+    let caret_offset = file_text.find("/*caret*/").unwrap() + "/*caret*/".len();
+    let position = FilePosition {
+        file_id,
+        offset: (caret_offset as u32).into(),
+    };
+
+    let monikers = analysis.moniker(position).unwrap().unwrap().info;
+    assert_eq!(monikers.len(), 1);
+    let moniker = &monikers[0];
+    assert_eq!(moniker.identifier(), "MyDep/0x43::dep::call");
+    assert_eq!(moniker.kind, MonikerKind::Export);
+    assert_eq!(moniker.uniqueness, MonikerUniqueness::Global);
+
+    let nav_targets = analysis
+        .resolve_moniker_identifier(&moniker.identifier())
+        .unwrap();
+    assert_eq!(nav_targets.len(), 1);
+    assert_eq!(test_state.relpath(nav_targets[0].file_id), "/dep.move");
+
+    assert!(
+        analysis
+            .resolve_moniker_identifier("MyApp/0x43::dep::call")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_moniker_identifier_parsing() {
+    let item_path = ItemPath::from_identifier("AptosFramework/0x1::coin::transfer").unwrap();
+    assert_eq!(item_path.package_name, "AptosFramework");
+    assert_eq!(item_path.address, "0x1");
+    assert_eq!(item_path.module_name, "coin");
+    assert_eq!(item_path.item_name.as_deref(), Some("transfer"));
+    assert_eq!(item_path.identifier(), "AptosFramework/0x1::coin::transfer");
+
+    let module_path = ItemPath::from_identifier("AptosFramework/0x1::coin").unwrap();
+    assert_eq!(module_path.item_name, None);
+
+    assert!(ItemPath::from_identifier("0x1::coin::transfer").is_none());
+    assert!(ItemPath::from_identifier("AptosFramework/0x1").is_none());
+    assert!(ItemPath::from_identifier("AptosFramework/0x1::coin::Coin::value").is_none());
+}
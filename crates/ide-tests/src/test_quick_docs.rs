@@ -7,6 +7,7 @@
 use crate::init_tracing_for_test;
 use expect_test::expect;
 use syntax::files::FilePosition;
+use test_utils::fixtures::test_state::package;
 use test_utils::{fixtures, get_first_marked_position};
 
 pub(crate) fn check_hover(source: &str, expect: expect_test::Expect) {
@@ -284,7 +285,33 @@ module 0x1::m {
             ```move
             0x1::m
 
-            const MY_CONST: u8
+            const MY_CONST: u8 = 1
+            ```
+            ---
+        "#]],
+    )
+}
+
+#[test]
+fn test_hover_for_const_with_evaluated_value() {
+    check_hover(
+        // language=Move
+        r#"
+module 0x1::m {
+    const FEE_BPS: u64 = 30 * 100;
+    const MAX_FEE: u64 = (FEE_BPS << 2) + (MAX_U8 as u64);
+    fun main() {
+        MAX_FEE;
+        //^
+    }
+}
+    "#,
+        // language=Markdown
+        expect![[r#"
+            ```move
+            0x1::m
+
+            const MAX_FEE: u64 = 12255
             ```
             ---
         "#]],
@@ -469,3 +496,100 @@ module 0x1::m {
         "#]],
     )
 }
+
+#[test]
+fn test_hover_code_blocks_in_docs_are_move_code() {
+    check_hover(
+        // language=Move
+        r#"
+module 0x1::m {
+    /// Returns the value, like in
+    /// ```
+    /// let v = value();
+    /// if (v > 0) {
+    ///     abort 1
+    /// }
+    /// ```
+    /// ```text
+    /// plain text
+    /// ```
+    public fun value(): u8 { 1 }
+    fun main() {
+        value();
+        //^
+    }
+}
+    "#,
+        // language=Markdown
+        expect![[r#"
+            ```move
+            0x1::m
+
+            public fun value(): u8
+            ```
+            ---
+            Returns the value, like in
+            ```move
+            let v = value();
+            if (v > 0) {
+                abort 1
+            }
+            ```
+            ```text
+            plain text
+            ```
+        "#]],
+    )
+}
+
+#[test]
+fn test_hover_intra_doc_links_and_item_location() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![package(
+        "MyApp",
+        // language=TOML
+        r#"
+        [package]
+        name = "MyApp"
+        version = "0.1.0"
+
+        [addresses]
+        my_app = "0x42"
+        "#,
+        // language=Move
+        r#"
+        //- main.move
+        module my_app::m {
+            struct Coin {}
+            /// Transfers the [`Coin`], see [`m::value`] and `value`,
+            /// but not `amount` or [`unknown`].
+            public fun transfer(amount: u64) {}
+            public fun value() {}
+            fun main() { /*caret*/transfer(1); }
+        }
+        "#,
+    )]);
+    let (file_id, file_text) = test_state.file_with_caret("/*caret*/");
+    let caret_offset = file_text.find("/*caret*/").unwrap() + "/*caret*/".len();
+    let position = FilePosition {
+        file_id,
+        offset: (caret_offset as u32).into(),
+    };
+
+    let hover_result = test_state.analysis().hover(position).unwrap().unwrap().info;
+    let ws_root_url = format!("file://{}", test_state.ws_root);
+    let doc_string = hover_result.doc_string.trim().replace(&ws_root_url, "file://$WS");
+
+    // language=Markdown
+    expect![[r#"
+        ```move
+        my_app::m
+
+        public fun transfer(amount: u64)
+        ```
+        ---
+        module `my_app::m` at `0x42` of package `MyApp`
+
+        Transfers the [`Coin`](file://$WS/MyApp/sources/main.move#L2,12), see [`m::value`](file://$WS/MyApp/sources/main.move#L6,16) and [`value`](file://$WS/MyApp/sources/main.move#L6,16),
+        but not `amount` or [`unknown`]."#]]
+    .assert_eq(&doc_string);
+}
@@ -5,8 +5,10 @@
 // Modifications have been made to the original code.
 
 use expect_test::{Expect, expect};
+use ide_db::source_change::FileSystemEdit;
 use ide_db::text_edit::TextEdit;
 use syntax::files::FilePosition;
+use test_utils::fixtures::test_state::named;
 use test_utils::{fixtures, get_marked_position_offset_with_data};
 
 fn check_rename(before: &str, rename_to: &str, after: Expect) {
@@ -40,7 +42,15 @@ fn check_rename(before: &str, rename_to: &str, after: Expect) {
             let mut result = analysis.file_text(file_id).unwrap().to_string();
             text_edit_builder.finish().apply(&mut result);
 
-            let mut actual_after = result.trim().to_string();
+            let mut actual_after = String::new();
+            let needs_confirmation = edit
+                .change_annotation()
+                .and_then(|id| source_change.annotations.get(&id))
+                .filter(|it| it.needs_confirmation);
+            if let Some(annotation) = needs_confirmation {
+                actual_after.push_str(&format!("// needs confirmation: {}\n", annotation.label));
+            }
+            actual_after.push_str(result.trim());
             actual_after.push_str("\n");
 
             after.assert_eq(&actual_after);
@@ -56,6 +66,27 @@ fn check_rename(before: &str, rename_to: &str, after: Expect) {
     }
 }
 
+fn check_module_file_renames(before: &str, rename_to: &str, expected_file_renames: Expect) {
+    let (offset, _) = get_marked_position_offset_with_data(before, "//^");
+    let (analysis, file_id) = fixtures::from_single_file(before.to_string());
+    let position = FilePosition { file_id, offset };
+
+    let source_change = analysis
+        .rename(position, rename_to)
+        .unwrap()
+        .unwrap_or_else(|err| panic!("Rename to '{rename_to}' failed unexpectedly: {err}"));
+    let file_renames = source_change
+        .file_system_edits
+        .iter()
+        .filter_map(|it| match it {
+            FileSystemEdit::MoveFile { dst, .. } => Some(dst.path.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    expected_file_renames.assert_debug_eq(&file_renames);
+}
+
 #[test]
 fn test_rename_function() {
     check_rename(
@@ -242,3 +273,334 @@ module 0x1::m {
         "#]],
     );
 }
+
+#[test]
+fn test_cannot_rename_function_to_existing_function_name() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    fun call() {}
+       //^
+    fun other() {}
+}
+    "#,
+        "other",
+        // language=Move
+        expect![[r#"
+            // error: `other` is already defined in this module
+        "#]],
+    );
+}
+
+#[test]
+fn test_cannot_rename_module_to_existing_module_name_at_same_address() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {}
+          //^
+module 0x1::other {}
+    "#,
+        "other",
+        // language=Move
+        expect![[r#"
+            // error: Module `other` is already defined at this address
+        "#]],
+    );
+}
+
+#[test]
+fn test_cannot_rename_struct_to_builtin_type() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    struct Coin {}
+         //^
+}
+    "#,
+        "u64",
+        // language=Move
+        expect![[r#"
+            // error: Invalid name `u64`: cannot rename to a builtin type
+        "#]],
+    );
+}
+
+#[test]
+fn test_cannot_rename_struct_field_to_existing_field_name() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    struct S { val: u8, other: u8 }
+              //^
+}
+    "#,
+        "other",
+        // language=Move
+        expect![[r#"
+            // error: Field `other` is already defined
+        "#]],
+    );
+}
+
+#[test]
+fn test_cannot_rename_param_to_existing_param_name() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    fun main(first: u8, second: u8) {}
+            //^
+}
+    "#,
+        "second",
+        // language=Move
+        expect![[r#"
+            // error: Parameter `second` is already defined
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_local_shadowed_by_later_binding_needs_confirmation() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    fun main() {
+        let amount = 1;
+           //^
+        let total = 2;
+        amount + total;
+    }
+}
+    "#,
+        "total",
+        // language=Move
+        expect![[r#"
+            // needs confirmation: This rename will change the program's meaning
+            module 0x1::m {
+                fun main() {
+                    let total = 1;
+                       //^
+                    let total = 2;
+                    total + total;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_local_capturing_outer_binding_needs_confirmation() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    fun main() {
+        let total = 1;
+        let amount = 2;
+           //^
+        total + amount;
+    }
+}
+    "#,
+        "total",
+        // language=Move
+        expect![[r#"
+            // needs confirmation: This rename will change the program's meaning
+            module 0x1::m {
+                fun main() {
+                    let total = 1;
+                    let total = 2;
+                       //^
+                    total + total;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_local_capturing_struct_lit_shorthand_needs_confirmation() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    struct S { total: u64 }
+    fun main() {
+        let total = 1;
+        let amount = 2;
+           //^
+        S { total };
+        amount;
+    }
+}
+    "#,
+        "total",
+        // language=Move
+        expect![[r#"
+            // needs confirmation: This rename will change the program's meaning
+            module 0x1::m {
+                struct S { total: u64 }
+                fun main() {
+                    let total = 1;
+                    let total = 2;
+                       //^
+                    S { total };
+                    total;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_local_in_inner_block_without_conflict() {
+    check_rename(
+        // language=Move
+        r#"
+module 0x1::m {
+    fun main() {
+        let total = 1;
+        {
+            let amount = 2;
+               //^
+            amount;
+        };
+        total;
+    }
+}
+    "#,
+        "total",
+        // language=Move
+        expect![[r#"
+            module 0x1::m {
+                fun main() {
+                    let total = 1;
+                    {
+                        let total = 2;
+                           //^
+                        total;
+                    };
+                    total;
+                }
+            }
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_module_renames_file_with_the_same_name() {
+    check_module_file_renames(
+        // language=Move
+        r#"
+module 0x1::main {
+          //^
+}
+    "#,
+        "coin",
+        expect![[r#"
+            [
+                "coin.move",
+            ]
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_module_keeps_file_with_different_name() {
+    check_module_file_renames(
+        // language=Move
+        r#"
+module 0x1::m {
+          //^
+}
+    "#,
+        "coin",
+        expect![[r#"
+            []
+        "#]],
+    );
+}
+
+#[test]
+fn test_rename_file_renames_module_with_the_same_name() {
+    let source = r#"
+module 0x1::main {
+    public fun call() {}
+}
+module 0x1::other {
+    use 0x1::main;
+    fun run() {
+        main::call();
+    }
+}
+"#;
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+
+    let source_change = analysis.will_rename_file(file_id, "coin").unwrap().unwrap();
+    assert!(source_change.file_system_edits.is_empty());
+
+    let mut result = analysis.file_text(file_id).unwrap().to_string();
+    source_change.get_source_edit(file_id).unwrap().apply(&mut result);
+
+    expect![[r#"
+        module 0x1::coin {
+            public fun call() {}
+        }
+        module 0x1::other {
+            use 0x1::coin;
+            fun run() {
+                coin::call();
+            }
+        }
+    "#]]
+    .assert_eq(&stdx::trim_indent(&result));
+}
+
+#[test]
+fn test_rename_file_moves_spec_file_of_the_module() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![named(
+        "TestPackage",
+        // language=Move
+        r#"
+//- /main.move
+module 0x1::main {
+    public fun call() {}
+}
+//- /main.spec.move
+spec 0x1::main {
+    spec call {}
+}
+"#,
+    )]);
+    let (file_id, _) = test_state.file_with_caret("module 0x1::main");
+
+    let source_change = test_state
+        .analysis()
+        .will_rename_file(file_id, "coin")
+        .unwrap()
+        .unwrap();
+    let file_renames = source_change
+        .file_system_edits
+        .iter()
+        .filter_map(|it| match it {
+            FileSystemEdit::MoveFile { src, dst } => Some((test_state.relpath(*src), dst.path.clone())),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    expect![[r#"
+        [
+            (
+                "/main.spec.move",
+                "coin.spec.move",
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&file_renames);
+}
@@ -0,0 +1,61 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use ide::static_index::TokenStaticData;
+use ide_db::SymbolKind;
+use test_utils::fixtures;
+
+#[test]
+fn test_static_index_definitions_references_and_paths() {
+    // language=Move
+    let source = r#"
+module std::main {
+    /// Adds one.
+    fun call(a: u8): u8 { a + 1 }
+    fun main() {
+        call(1);
+        call(2);
+    }
+}
+    "#;
+    let (analysis, file_id) = fixtures::from_single_file(source.to_string());
+    let static_index = analysis.static_index().unwrap();
+
+    let indexed_file = static_index
+        .files
+        .iter()
+        .find(|it| it.file_id == file_id)
+        .unwrap();
+    let token_text = |token: &TokenStaticData| &source[token.definition.range];
+    let find_token = |name: &str| {
+        static_index
+            .tokens
+            .iter()
+            .find(|it| it.definition.file_id == file_id && token_text(it) == name)
+            .unwrap()
+    };
+
+    let call = find_token("call");
+    assert_eq!(call.item_path.as_ref().unwrap().to_string(), "0x1::main::call");
+    assert!(call.hover.as_ref().unwrap().contains("Adds one."));
+    assert_eq!(call.references.len(), 3);
+    assert_eq!(call.references.iter().filter(|it| it.is_definition).count(), 1);
+    assert!(call.container.is_none());
+
+    let module = find_token("main");
+    assert_eq!(module.item_path.as_ref().unwrap().to_string(), "0x1::main");
+
+    let param = find_token("a");
+    assert!(param.item_path.is_none());
+    assert_eq!(param.references.len(), 2);
+    let (container_path, container_kind) = param.container.as_ref().unwrap();
+    assert_eq!(container_path.to_string(), "0x1::main::call");
+    assert_eq!(*container_kind, SymbolKind::Function);
+
+    let n_call_occurrences = indexed_file
+        .tokens
+        .iter()
+        .filter(|(range, _)| &source[*range] == "call")
+        .count();
+    assert_eq!(n_call_occurrences, 3);
+}
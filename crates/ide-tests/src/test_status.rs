@@ -0,0 +1,63 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use test_utils::fixtures;
+use test_utils::fixtures::test_state::package;
+
+#[test]
+fn test_status_of_file_and_named_addresses() {
+    let test_state = fixtures::from_multiple_files_on_tmpfs(vec![
+        package(
+            "MyApp",
+            // language=TOML
+            r#"
+            [package]
+            name = "MyApp"
+            version = "0.1.0"
+
+            [dependencies]
+            MyDep = { local = "../MyDep" }
+            MissingDep = { local = "../MissingDep" }
+
+            [addresses]
+            my_app = "0x42"
+            "#,
+            r#"
+            //- main.move
+            module my_app::main { /*caret*/ }
+            "#,
+        ),
+        package(
+            "MyDep",
+            // language=TOML
+            r#"
+            [package]
+            name = "MyDep"
+            version = "0.1.0"
+
+            [addresses]
+            std = "0x1"
+            my_app = "0x42"
+            "#,
+            r#""#,
+        ),
+    ]);
+    let (file_id, _) = test_state.file_with_caret("/*caret*/");
+    let status = test_state.analysis().status(Some(file_id)).unwrap();
+
+    let expected_lines = [
+        "owned by `MyApp` (local, ",
+        "MyApp` is the closest directory with the `Move.toml`",
+        "missing dependency: MissingDep",
+        "0x42 in `MyApp`",
+        "0x42 in `MyDep`",
+        "std = 0x1 (0x1 in `MyDep`)",
+        "aptos_token = 0x1 (default framework address)",
+    ];
+    for expected_line in expected_lines {
+        assert!(
+            status.contains(expected_line),
+            "`{expected_line}` not found in\n{status}"
+        );
+    }
+}
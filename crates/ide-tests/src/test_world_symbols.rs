@@ -6,6 +6,8 @@
 
 use expect_test::{Expect, expect};
 use ide_db::symbol_index::Query;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use test_utils::{SourceMark, apply_source_marks, fixtures};
 
 fn check_symbols(source: &str, query: Query, with_symbols: Expect) {
@@ -136,3 +138,54 @@ module 0x1::main {
         "#]],
     );
 }
+
+#[test]
+fn test_world_symbols_after_parallel_prime_caches() {
+    // language=Move
+    let source = r#"
+module 0x1::main {
+    fun main() {}
+}
+    "#;
+    let (analysis, _) = fixtures::from_single_file(source.to_string());
+
+    let last_progress = Mutex::new(None);
+    analysis
+        .parallel_prime_caches(2, |progress| {
+            *last_progress.lock().unwrap() = Some(progress);
+        })
+        .unwrap();
+    let last_progress = last_progress.into_inner().unwrap().unwrap();
+    assert!(last_progress.packages_total > 0);
+    assert_eq!(last_progress.packages_done, last_progress.packages_total);
+    assert!(last_progress.packages_currently_indexing.is_empty());
+
+    let symbols = analysis.symbol_search(Query::new("main", Some(128))).unwrap();
+    assert_eq!(symbols.len(), 2);
+}
+
+#[test]
+fn test_parallel_prime_caches_returns_after_cancellation() {
+    // language=Move
+    let source = r#"
+module 0x1::main {
+    fun main() {}
+}
+    "#;
+    let (mut test_package, _) = fixtures::test_package_from_single_file(source.to_string());
+
+    let analysis = test_package.analysis();
+    let started = Arc::new(AtomicBool::new(false));
+    let priming = std::thread::spawn({
+        let started = started.clone();
+        move || analysis.parallel_prime_caches(2, move |_| started.store(true, Ordering::SeqCst))
+    });
+    while !started.load(Ordering::SeqCst) && !priming.is_finished() {
+        std::thread::yield_now();
+    }
+
+    // waits for the priming snapshots to be dropped
+    test_package.request_cancellation();
+    // might've finished before the cancellation
+    let _ = priming.join().unwrap();
+}
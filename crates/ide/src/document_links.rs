@@ -0,0 +1,188 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::navigation_target::NavigationTarget;
+use base_db::SourceDatabase;
+use base_db::inputs::InternFileId;
+use base_db::source_db;
+use ide_db::RootDatabase;
+use ide_db::doc_links::{DocLink, doc_comment_links};
+use lang::nameres::address::Address;
+use lang::nameres::doc_links::resolve_doc_link;
+use syntax::files::InFile;
+use syntax::{AstNode, AstToken, TextRange, TextSize, ast};
+use toml::de::{DeTable, DeValue};
+use vfs::{FileId, VfsPath};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentLinksConfig {
+    /// Block explorer URL with the `{address}` placeholder, which is used for the address literals.
+    pub address_explorer_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DocumentLink {
+    pub range: TextRange,
+    pub target: DocumentLinkTarget,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum DocumentLinkTarget {
+    Url(String),
+    File(VfsPath),
+    Item(NavigationTarget),
+}
+
+// Feature: Document Links
+//
+// Makes clickable:
+// - `local = "../path"` dependencies in `Move.toml`, which link to the manifest of the dependency,
+// - URLs and intra-doc links like [`coin::transfer`] in the doc comments,
+// - `@0x1` and `@named` address literals, which open the block explorer if it's configured.
+pub(crate) fn document_links(
+    db: &RootDatabase,
+    config: &DocumentLinksConfig,
+    file_id: FileId,
+) -> Vec<DocumentLink> {
+    let package_root = db.package_root(db.file_package_id(file_id)).data(db);
+    if package_root.manifest_file_id == Some(file_id) {
+        let Some(manifest_path) = package_root.path_for_file(&file_id) else {
+            return vec![];
+        };
+        let manifest_text = db.file_text(file_id).text(db);
+        return manifest_links(manifest_path, &manifest_text);
+    }
+
+    let file = source_db::parse(db, file_id.intern(db)).tree();
+    let mut links = doc_comments_links(db, file_id, &file);
+    if let Some(explorer_url) = &config.address_explorer_url {
+        links.extend(address_links(db, explorer_url, &file));
+    }
+    links.sort_by_key(|it| it.range.start());
+    links
+}
+
+fn doc_comments_links(db: &RootDatabase, file_id: FileId, file: &ast::SourceFile) -> Vec<DocumentLink> {
+    let mut links = vec![];
+    let doc_comments = file
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token().and_then(ast::Comment::cast))
+        .filter(|it| it.is_doc() && it.is_outer());
+    for comment in doc_comments {
+        for (range, doc_link) in doc_comment_links(&comment) {
+            match doc_link {
+                DocLink::Url(url) => links.push(DocumentLink {
+                    range,
+                    target: DocumentLinkTarget::Url(url),
+                    tooltip: None,
+                }),
+                DocLink::Item(item_path) => {
+                    let Some(owner) = comment.syntax().parent() else {
+                        continue;
+                    };
+                    let Some(entry) = resolve_doc_link(db, InFile::new(file_id, owner), &item_path)
+                    else {
+                        continue;
+                    };
+                    let Some(nav_target) =
+                        NavigationTarget::from_syntax_loc(db, entry.name, entry.node_loc)
+                    else {
+                        continue;
+                    };
+                    links.push(DocumentLink {
+                        range,
+                        target: DocumentLinkTarget::Item(nav_target),
+                        tooltip: Some(format!("Go to `{item_path}`")),
+                    });
+                }
+            }
+        }
+    }
+    links
+}
+
+/// Links to the block explorer from the address literals, like `@0x1` and `@std`.
+fn address_links(db: &RootDatabase, explorer_url: &str, file: &ast::SourceFile) -> Vec<DocumentLink> {
+    let mut links = vec![];
+    for address_lit in file.syntax().descendants().filter_map(ast::AddressLit::cast) {
+        let (range, address) = if let Some(value_address) = address_lit.value_address() {
+            let text = value_address.int_number_token().text().to_string();
+            (value_address.syntax().text_range(), Address::value(&text))
+        } else if let Some(named_address) = address_lit.named_address() {
+            let name = named_address.ident_token().text().to_string();
+            (named_address.syntax().text_range(), Address::named(&name))
+        } else {
+            continue;
+        };
+        let Some(numeric_address) = address
+            .resolve_to_numeric_address(db)
+            .filter(|it| !it.is_underscore())
+        else {
+            continue;
+        };
+        let address_text = numeric_address.short();
+        links.push(DocumentLink {
+            range,
+            target: DocumentLinkTarget::Url(explorer_url.replace("{address}", &address_text)),
+            tooltip: Some(format!("Open {address_text} in the explorer")),
+        });
+    }
+    links
+}
+
+/// Links from the `local = "../path"` dependencies to their `Move.toml` files.
+fn manifest_links(manifest_path: &VfsPath, manifest_text: &str) -> Vec<DocumentLink> {
+    let Some(package_dir) = manifest_path.parent() else {
+        return vec![];
+    };
+    let Ok(manifest) = DeTable::parse(manifest_text) else {
+        return vec![];
+    };
+    let mut links = vec![];
+    for deps_key in ["dependencies", "dev-dependencies"] {
+        // covers both [dependencies] table with inner tables and [dependencies.AptosFramework]
+        let Some(DeValue::Table(deps_table)) = table_value(manifest.get_ref(), deps_key) else {
+            continue;
+        };
+        for (_, dep) in deps_table {
+            let DeValue::Table(dep_table) = dep.get_ref() else {
+                continue;
+            };
+            let Some((_, local)) = dep_table.iter().find(|(key, _)| key.get_ref() == "local") else {
+                continue;
+            };
+            let DeValue::String(local_path) = local.get_ref() else {
+                continue;
+            };
+            let Some(dep_manifest_path) =
+                package_dir.join(local_path).and_then(|it| it.join("Move.toml"))
+            else {
+                continue;
+            };
+            // without the quotes
+            let value_span = local.span();
+            if value_span.len() < 2 {
+                continue;
+            }
+            links.push(DocumentLink {
+                range: TextRange::new(
+                    TextSize::new(value_span.start as u32 + 1),
+                    TextSize::new(value_span.end as u32 - 1),
+                ),
+                target: DocumentLinkTarget::File(dep_manifest_path),
+                tooltip: None,
+            });
+        }
+    }
+    links.sort_by_key(|it| it.range.start());
+    links
+}
+
+fn table_value<'a, 'i>(table: &'a DeTable<'i>, key: &str) -> Option<&'a DeValue<'i>> {
+    table
+        .iter()
+        .find(|(it, _)| it.get_ref() == key)
+        .map(|(_, value)| value.get_ref())
+}
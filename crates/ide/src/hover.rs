@@ -4,24 +4,27 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
+mod doc_comments;
 mod named_element;
 mod spec_keywords;
 
 use crate::RangeInfo;
 use crate::hover::named_element::DocSignatureOwner;
+use base_db::{SourceDatabase, source_db};
 use ide_db::RootDatabase;
 use lang::Semantics;
+use lang::nameres::address::Address;
+use lang::nameres::fq_named_element::ItemFQNameOwner;
 use lang::node_ext::item_spec::ItemSpecExt;
 use lang::types::ty_db;
 use std::fmt::Write;
-use stdx::itertools::Itertools;
 use syntax::algo::find_node_at_offset;
 use syntax::ast::HoverDocsOwner;
 use syntax::ast::node_ext::syntax_element::SyntaxElementExt;
 use syntax::ast::node_ext::syntax_node::SyntaxNodeExt;
 use syntax::ast::token_at_offset_ext::TokenAtOffsetExt;
-use syntax::files::{FilePosition, InFileExt};
-use syntax::{AstNode, ast};
+use syntax::files::{FilePosition, InFile, InFileExt};
+use syntax::{AstNode, SyntaxNode, ast};
 use vfs::FileId;
 
 /// Contains the results when hovering over an item
@@ -53,7 +56,7 @@ pub(crate) fn hover(
     let name_like = find_node_at_offset::<ast::NameLike>(&file, offset)?;
     let name_range = name_like.syntax().text_range();
 
-    let hover_docs_owner = match name_like {
+    let (owner_file_id, hover_docs_owner) = match name_like {
         ast::NameLike::NameRef(name_ref) => {
             let ref_element = name_ref.syntax().ancestor_strict::<ast::ReferenceElement>()?;
             if let Some(result_hover) =
@@ -63,11 +66,11 @@ pub(crate) fn hover(
             }
             let doc_comments_owner =
                 sema.resolve_to_element::<ast::AnyHoverDocsOwner>(ref_element.in_file(file_id))?;
-            doc_comments_owner.value
+            (doc_comments_owner.file_id, doc_comments_owner.value)
         }
         ast::NameLike::Name(name) => {
             let doc_comments_owner = name.syntax().parent_of_type::<ast::AnyHoverDocsOwner>()?;
-            doc_comments_owner
+            (file_id, doc_comments_owner)
         }
     };
 
@@ -91,18 +94,53 @@ pub(crate) fn hover(
     // writeln!(doc_string).ok()?;
     // writeln!(doc_string).ok()?;
 
-    write!(doc_string, "{}", format_doc_comments(doc_comments)).ok()?;
+    let owner = hover_docs_owner.syntax().clone().in_file(owner_file_id);
+    if let Some(item_location) = item_location(db, &owner) {
+        writeln!(doc_string, "{item_location}").ok()?;
+        writeln!(doc_string).ok()?;
+    }
+
+    write!(
+        doc_string,
+        "{}",
+        doc_comments::format_doc_comments(db, owner, doc_comments)
+    )
+    .ok()?;
     writeln!(doc_string,).ok()?;
 
     Some(RangeInfo::new(name_range, HoverResult { doc_string }))
 }
 
-fn format_doc_comments(doc_comments: Vec<ast::Comment>) -> String {
-    doc_comments
-        .iter()
-        .filter_map(|it| it.comment_line())
-        .map(|it| it.trim())
-        .join("\n")
+/// Module and package of the item, with the value of the named address of the module.
+/// Returns `None` if there's nothing to add to the `0x1::m` header of the hover.
+fn item_location(db: &RootDatabase, item: &InFile<SyntaxNode>) -> Option<String> {
+    if ast::IdentPat::can_cast(item.value.kind()) {
+        return None;
+    }
+    let module = item.value.ancestors().find_map(ast::Module::cast)?;
+    let module_fq_name = module.in_file(item.file_id).fq_name(db)?;
+
+    let address = module_fq_name.address();
+    let named_address_value = match address {
+        Address::Named(_) => address
+            .resolve_to_numeric_address(db)
+            .filter(|it| !it.is_underscore()),
+        Address::Value(_) => None,
+    };
+    let package_name = source_db::metadata_for_package_id(db, db.file_package_id(item.file_id))
+        .and_then(|it| it.package_name);
+    if named_address_value.is_none() && package_name.is_none() {
+        return None;
+    }
+
+    let mut location = format!("module `{}`", module_fq_name.fq_identifier_text());
+    if let Some(address_value) = named_address_value {
+        write!(location, " at `{}`", address_value.short()).ok()?;
+    }
+    if let Some(package_name) = package_name {
+        write!(location, " of package `{package_name}`").ok()?;
+    }
+    Some(location)
 }
 
 fn docs_for_item_spec_fun_result(
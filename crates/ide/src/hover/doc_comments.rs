@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::navigation_target::NavigationTarget;
+use base_db::SourceDatabase;
+use ide_db::doc_links::{DocLink, code_span_paths, links_in_text};
+use ide_db::{RootDatabase, root_db};
+use lang::nameres::doc_links::resolve_doc_link;
+use lang::nameres::scope::ScopeEntry;
+use syntax::SyntaxKind::{IDENT_PAT, TYPE_PARAM};
+use syntax::files::InFile;
+use syntax::{SyntaxNode, TextSize, ast};
+
+/// Renders doc comments of the `owner` item as Markdown. Code blocks without the language
+/// are marked as Move code, and intra-doc links like [`coin::transfer`], as well as the code spans
+/// which resolve to the items, are turned into the `file://` links to the definitions.
+pub(super) fn format_doc_comments(
+    db: &RootDatabase,
+    owner: InFile<SyntaxNode>,
+    doc_comments: Vec<ast::Comment>,
+) -> String {
+    let mut lines = vec![];
+    let mut in_code_block = false;
+    for comment_line in doc_comments.iter().filter_map(|it| it.comment_line()) {
+        let line = comment_line.trim();
+        if let Some(info_string) = line.strip_prefix("```") {
+            if !in_code_block && info_string.trim().is_empty() {
+                lines.push("```move".to_string());
+            } else {
+                lines.push(line.to_string());
+            }
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            // keep the indentation of the code, without the space after `///`
+            let code_line = comment_line.strip_prefix(' ').unwrap_or(comment_line);
+            lines.push(code_line.trim_end().to_string());
+            continue;
+        }
+        lines.push(resolve_links_in_line(db, &owner, line));
+    }
+    lines.join("\n")
+}
+
+fn resolve_links_in_line(db: &RootDatabase, owner: &InFile<SyntaxNode>, line: &str) -> String {
+    // (offset, text) pairs to insert into the line
+    let mut insertions = vec![];
+    for (range, doc_link) in links_in_text(line) {
+        let DocLink::Item(item_path) = doc_link else {
+            continue;
+        };
+        let Some(url) =
+            resolve_doc_link(db, owner.clone(), &item_path).and_then(|entry| definition_url(db, entry))
+        else {
+            continue;
+        };
+        // `[path]` or `` [`path`] ``
+        let after_path = &line[usize::from(range.end())..];
+        let close_bracket_len = if after_path.starts_with("`]") { 2 } else { 1 };
+        insertions.push((range.end() + TextSize::new(close_bracket_len), format!("({url})")));
+    }
+    for (range, path) in code_span_paths(line) {
+        let Some(url) = resolve_doc_link(db, owner.clone(), &path)
+            .filter(|entry| !matches!(entry.node_loc.kind(), IDENT_PAT | TYPE_PARAM))
+            .and_then(|entry| definition_url(db, entry))
+        else {
+            continue;
+        };
+        // `path` -> [`path`](url)
+        insertions.push((range.start() - TextSize::new(1), "[".to_string()));
+        insertions.push((range.end() + TextSize::new(1), format!("]({url})")));
+    }
+    insertions.sort_by_key(|(offset, _)| *offset);
+
+    let mut resolved_line = line.to_string();
+    for (offset, text) in insertions.into_iter().rev() {
+        resolved_line.insert_str(offset.into(), &text);
+    }
+    resolved_line
+}
+
+/// `file:///path/to/file.move#L<line>,<column>` URL of the name of the definition.
+fn definition_url(db: &RootDatabase, entry: ScopeEntry) -> Option<String> {
+    let nav_target = NavigationTarget::from_syntax_loc(db, entry.name, entry.node_loc)?;
+    let file_id = nav_target.file_id;
+    let package_root = db.package_root(db.file_package_id(file_id)).data(db);
+    let file_path = package_root.path_for_file(&file_id)?.as_path()?;
+
+    let mut url = url::Url::from_file_path(file_path.as_str()).ok()?;
+    let line_col = root_db::line_index(db, file_id).line_col(nav_target.focus_or_full_range().start());
+    url.set_fragment(Some(&format!("L{},{}", line_col.line + 1, line_col.col + 1)));
+    Some(url.to_string())
+}
@@ -6,6 +6,7 @@
 
 use ide_db::RootDatabase;
 use lang::Semantics;
+use lang::const_eval::evaluate_const;
 use lang::nameres::scope::ScopeEntryExt;
 use std::fmt::Write;
 use stdx::format_to;
@@ -52,7 +53,7 @@ impl DocSignatureOwner for ast::NamedElement {
                 ast::AnyFun(it) => generate_any_fun(buf, it),
                 ast::Struct(it) => generate_struct(buf, it),
                 ast::Enum(it) => generate_enum(buf, it),
-                ast::Const(it) => generate_const(buf, it),
+                ast::Const(it) => generate_const(buf, sema, it),
                 ast::NamedField(it) => {
                     format_to!(buf, "field ");
                     generate_field(buf, it)
@@ -140,11 +141,18 @@ fn generate_vis(buf: &mut String, vis_owner: &impl HasVisibility) {
     }
 }
 
-fn generate_const(buf: &mut String, const_: ast::Const) -> Option<()> {
+fn generate_const(
+    buf: &mut String,
+    sema: &Semantics<'_, RootDatabase>,
+    const_: ast::Const,
+) -> Option<()> {
     generate_attrs(buf, &const_);
     let const_name = const_.name()?.as_string();
     format_to!(buf, "const {const_name}");
     generate_type_annotation(buf, const_.type_())?;
+    if let Ok(const_value) = evaluate_const(sema.db, sema.wrap_node_infile(const_)) {
+        format_to!(buf, " = {const_value}");
+    }
     Some(())
 }
 
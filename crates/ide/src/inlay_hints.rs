@@ -4,6 +4,7 @@
 // This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
 // Modifications have been made to the original code.
 
+mod const_value;
 mod ident_pat;
 mod param_name;
 mod range_exclusive;
@@ -65,6 +66,7 @@ fn hints(
             ast::IdentPat(it) => ident_pat::hints(hints, sema, config, it.in_file(file_id)),
             ast::AnyCallExpr(it) => param_name::hints(hints, sema, config, it.in_file(file_id)),
             ast::RangeExpr(it) => range_exclusive::hints(hints, sema, config, it),
+            ast::Const(it) => const_value::hints(hints, sema, config, it.in_file(file_id)),
             _ => Some(()),
         }
     };
@@ -106,6 +108,7 @@ pub struct InlayHintsConfig {
     pub tuple_type_hints: bool,
     pub parameter_hints: bool,
     pub range_exclusive_hints: bool,
+    pub const_value_hints: bool,
     pub hide_closure_parameter_hints: bool,
     pub fields_to_resolve: InlayFieldsToResolve,
 }
@@ -178,6 +181,7 @@ pub enum InlayKind {
     GenericParameter,
     Type,
     RangeExclusive,
+    ConstValue,
 }
 
 #[derive(Debug, Hash)]
@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::inlay_hints::{InlayHint, InlayHintLabel, InlayHintPosition, InlayHintsConfig, InlayKind};
+use ide_db::RootDatabase;
+use lang::Semantics;
+use lang::const_eval::evaluate_const;
+use syntax::files::InFile;
+use syntax::{AstNode, ast};
+
+pub(super) fn hints(
+    acc: &mut Vec<InlayHint>,
+    sema: &Semantics<'_, RootDatabase>,
+    config: &InlayHintsConfig,
+    const_: InFile<ast::Const>,
+) -> Option<()> {
+    if !config.const_value_hints {
+        return None;
+    }
+    let initializer_expr = const_.value.initializer()?.expr()?;
+    // value of the literal is already visible
+    if matches!(initializer_expr, ast::Expr::Literal(_)) {
+        return None;
+    }
+    let const_value = evaluate_const(sema.db, const_).ok()?;
+    acc.push(InlayHint {
+        range: initializer_expr.syntax().text_range(),
+        position: InlayHintPosition::After,
+        pad_left: true,
+        pad_right: false,
+        kind: InlayKind::ConstValue,
+        label: InlayHintLabel::from(format!("= {const_value}")),
+        text_edit: None,
+        resolve_parent: None,
+    });
+    Some(())
+}
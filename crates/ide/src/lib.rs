@@ -18,6 +18,7 @@ use syntax::{SourceFile, TextRange, TextSize};
 use vfs::{AbsPathBuf, FileId, Vfs};
 
 pub mod annotations;
+pub mod document_links;
 pub mod extend_selection;
 mod file_structure;
 mod goto_definition;
@@ -25,16 +26,20 @@ mod goto_specification;
 mod highlight_related;
 mod hover;
 pub mod inlay_hints;
+pub mod moniker;
 mod navigation_target;
 mod references;
 mod rename;
 pub mod runnables;
 mod signature_help;
+pub mod static_index;
+mod status;
 pub mod syntax_highlighting;
 mod type_info;
 mod view_syntax_tree;
 
 use crate::annotations::{Annotation, AnnotationConfig};
+use crate::document_links::{DocumentLink, DocumentLinksConfig};
 use crate::file_structure::StructureNode;
 use crate::hover::HoverResult;
 use crate::inlay_hints::{InlayHint, InlayHintsConfig};
@@ -42,12 +47,17 @@ pub use crate::navigation_target::NavigationTarget;
 use crate::references::ReferenceSearchResult;
 use crate::runnables::Runnable;
 pub use crate::signature_help::SignatureHelp;
+pub use crate::static_index::StaticIndex;
 pub use crate::syntax_highlighting::HlRange;
 use base_db::inputs::{InternFileId, PackageMetadata};
 use base_db::package_root::PackageId;
 use ide_completion::config::CompletionConfig;
 use ide_db::assist_config::AssistConfig;
 pub use ide_db::assists::{Assist, AssistKind, AssistResolveStrategy};
+use ide_db::memory_usage;
+pub use ide_db::memory_usage::MemoryUsage;
+use ide_db::prime_caches;
+pub use ide_db::prime_caches::ParallelPrimeCachesProgress;
 use ide_db::rename::RenameError;
 use ide_db::search::SearchScope;
 use ide_db::source_change::SourceChange;
@@ -106,6 +116,13 @@ impl AnalysisHost {
     pub fn request_cancellation(&mut self) {
         self.db.request_cancellation();
     }
+    pub fn update_lru_capacities(
+        &mut self,
+        parse_lru_cap: Option<usize>,
+        inference_lru_cap: Option<usize>,
+    ) {
+        self.db.update_lru_capacities(parse_lru_cap, inference_lru_cap);
+    }
     pub fn raw_database(&self) -> &RootDatabase {
         &self.db
     }
@@ -144,6 +161,11 @@ impl Analysis {
         self.with_db(|db| db.file_package_id(file_id))
     }
 
+    /// Transitive dependencies of the package, including the package itself.
+    pub fn dependencies(&self, package_id: PackageId) -> Cancellable<Vec<PackageId>> {
+        self.with_db(|db| hir_db::transitive_dep_package_ids(db, package_id))
+    }
+
     pub fn manifest_file_id(&self, package_id: PackageId) -> Cancellable<Option<FileId>> {
         self.with_db(|db| db.package_root(package_id).data(db).manifest_file_id)
     }
@@ -194,6 +216,29 @@ impl Analysis {
         self.with_db(|db| source_db::parse(db, file_id.intern(db)).tree())
     }
 
+    /// Warms up the caches of all packages, see [`prime_caches::parallel_prime_caches`].
+    pub fn parallel_prime_caches<F>(&self, num_worker_threads: usize, cb: F) -> Cancellable<()>
+    where
+        F: Fn(ParallelPrimeCachesProgress) + Sync + UnwindSafe,
+    {
+        self.with_db(move |db| prime_caches::parallel_prime_caches(db, num_worker_threads, &cb))
+    }
+
+    /// Debug info about the package roots and the owner of the `file_id`.
+    pub fn status(&self, file_id: Option<FileId>) -> Cancellable<String> {
+        self.with_db(|db| status::status(db, file_id))
+    }
+
+    /// Estimated memory usage of the caches, see [`memory_usage::memory_usage`].
+    pub fn memory_usage(&self) -> Cancellable<MemoryUsage> {
+        self.with_db(|db| memory_usage::memory_usage(db))
+    }
+
+    /// Resolves all identifiers of the local packages, see [`StaticIndex`].
+    pub fn static_index(&self) -> Cancellable<StaticIndex> {
+        self.with_db(|db| StaticIndex::compute(db))
+    }
+
     /// Gets the file's `LineIndex`: data structure to convert between absolute
     /// offsets and line/column representation.
     pub fn file_line_index(&self, file_id: FileId) -> Cancellable<Arc<LineIndex>> {
@@ -285,7 +330,10 @@ impl Analysis {
         self.with_db(|db| {
             let symbols = symbol_index::world_symbols(db, query)
                 .into_iter()
-                .filter_map(|s| NavigationTarget::from_syntax_loc(db, s.name, s.syntax_loc));
+                .filter_map(|s| {
+                    let syntax_loc = s.syntax_loc(db)?;
+                    NavigationTarget::from_syntax_loc(db, s.name, syntax_loc)
+                });
             match limit {
                 Some(limit) => symbols.take(limit).collect(),
                 None => symbols.collect(),
@@ -355,13 +403,18 @@ impl Analysis {
         self.with_db(|db| hover::hover(db, pos))
     }
 
-    // /// Returns moniker of symbol at position.
-    // pub fn moniker(
-    //     &self,
-    //     position: FilePosition,
-    // ) -> Cancellable<Option<RangeInfo<Vec<moniker::MonikerResult>>>> {
-    //     self.with_db(|db| moniker::moniker(db, position))
-    // }
+    /// Returns moniker of symbol at position.
+    pub fn moniker(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<moniker::MonikerResult>>>> {
+        self.with_db(|db| moniker::moniker(db, position))
+    }
+
+    /// Returns definitions of the item with the moniker identifier.
+    pub fn resolve_moniker_identifier(&self, identifier: &str) -> Cancellable<Vec<NavigationTarget>> {
+        self.with_db(|db| moniker::resolve_identifier(db, identifier))
+    }
 
     // /// Returns URL(s) for the documentation of the symbol under the cursor.
     // /// # Arguments
@@ -378,6 +431,15 @@ impl Analysis {
     //     })
     // }
 
+    /// Returns the clickable ranges of the file, see [`document_links::document_links`].
+    pub fn document_links(
+        &self,
+        config: &DocumentLinksConfig,
+        file_id: FileId,
+    ) -> Cancellable<Vec<DocumentLink>> {
+        self.with_db(|db| document_links::document_links(db, config, file_id))
+    }
+
     /// Computes parameter information at the given position.
     pub fn signature_help(&self, position: FilePosition) -> Cancellable<Option<SignatureHelp>> {
         self.with_db(|db| signature_help::signature_help(db, position))
@@ -540,10 +602,10 @@ impl Analysis {
             } else {
                 Vec::new()
             };
-            // let assists = ide_assists::assists(db, assist_config, resolve, frange);
+            let assists = ide_assists::assists(db, assist_config, resolve, frange);
 
-            let res = diagnostic_assists;
-            // res.extend(assists);
+            let mut res = diagnostic_assists;
+            res.extend(assists);
 
             res
         })
@@ -566,6 +628,15 @@ impl Analysis {
         self.with_db(|db| rename::prepare_rename(db, position))
     }
 
+    /// Returns the edit required to update the module named after the file, if the file is renamed.
+    pub fn will_rename_file(
+        &self,
+        file_id: FileId,
+        new_name_stem: &str,
+    ) -> Cancellable<Option<SourceChange>> {
+        self.with_db(|db| rename::will_rename_file(db, file_id, new_name_stem))
+    }
+
     pub fn organize_imports(&self, file_id: FileId) -> Cancellable<Option<Assist>> {
         self.with_db(|db| handlers::organize_imports::organize_imports_in_file(db, file_id))
     }
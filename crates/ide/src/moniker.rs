@@ -0,0 +1,200 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+//! Stable identifiers of the modules and module items, which could be used by other tools
+//! to link to the Move items, and back from the identifiers to the definitions.
+
+use crate::navigation_target::NavigationTarget;
+use crate::{RangeInfo, goto_definition};
+use base_db::SourceDatabase;
+use base_db::inputs::InternFileId;
+use base_db::source_db;
+use ide_db::RootDatabase;
+use ide_db::helpers::package_name;
+use ide_db::symbol_index::{self, Query};
+use lang::nameres::fq_named_element::{ItemFQName, ItemFQNameOwner};
+use std::fmt;
+use syntax::files::{FilePosition, FileRange, InFileExt};
+use syntax::{AstNode, algo, ast};
+
+pub const MONIKER_SCHEME: &str = "aptos-move";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonikerResult {
+    pub item_path: ItemPath,
+    pub kind: MonikerKind,
+    pub uniqueness: MonikerUniqueness,
+}
+
+impl MonikerResult {
+    pub fn identifier(&self) -> String {
+        self.item_path.identifier()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonikerKind {
+    /// Item is defined in the library package.
+    Import,
+    /// Item is defined in the local package.
+    Export,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonikerUniqueness {
+    /// Address is resolved to its value, so the identifier is unique for all Move packages.
+    Global,
+    /// Named address has no value, the identifier is unique only inside the package.
+    Package,
+}
+
+/// Fully qualified path of the module or the module item, with the named address
+/// resolved to its value, like `0x1::coin::transfer`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemPath {
+    /// Package which contains the definition.
+    pub package_name: String,
+    pub address: String,
+    /// Named address which could not be resolved to the value.
+    pub is_named_address: bool,
+    pub module_name: String,
+    pub item_name: Option<String>,
+}
+
+impl ItemPath {
+    /// `<package>/<address>::<module>[::<item>]`, like `AptosFramework/0x1::coin::transfer`.
+    pub fn identifier(&self) -> String {
+        format!("{}/{self}", self.package_name)
+    }
+
+    /// Parses the [`ItemPath::identifier`]. Whether the address is named is not a part of it,
+    /// and is set to `false`.
+    pub fn from_identifier(identifier: &str) -> Option<ItemPath> {
+        let (package_name, path) = identifier.split_once('/')?;
+        let mut segments = path.split("::");
+        let address = segments.next()?;
+        let module_name = segments.next()?;
+        let item_name = segments.next();
+        if segments.next().is_some() || [package_name, address, module_name].contains(&"") {
+            return None;
+        }
+        Some(ItemPath {
+            package_name: package_name.to_string(),
+            address: address.to_string(),
+            is_named_address: false,
+            module_name: module_name.to_string(),
+            item_name: item_name.map(|it| it.to_string()),
+        })
+    }
+
+    fn is_same_item(&self, other: &ItemPath) -> bool {
+        self.package_name == other.package_name
+            && self.address == other.address
+            && self.module_name == other.module_name
+            && self.item_name == other.item_name
+    }
+}
+
+impl fmt::Display for ItemPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.address, self.module_name)?;
+        if let Some(item_name) = &self.item_name {
+            write!(f, "::{item_name}")?;
+        }
+        Ok(())
+    }
+}
+
+// Feature: Moniker
+//
+// Returns the stable identifiers of the module or the module item at the cursor,
+// built from the package name and the fully qualified path of the item.
+pub(crate) fn moniker(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<RangeInfo<Vec<MonikerResult>>> {
+    let RangeInfo { range, info: nav_targets } = goto_definition::goto_definition_multi(db, position)?;
+    let monikers = nav_targets
+        .iter()
+        .filter_map(|nav_target| moniker_for_nav_target(db, nav_target))
+        .collect::<Vec<_>>();
+    if monikers.is_empty() {
+        return None;
+    }
+    Some(RangeInfo::new(range, monikers))
+}
+
+/// Definitions of the item with the [`ItemPath::identifier`].
+pub(crate) fn resolve_identifier(db: &RootDatabase, identifier: &str) -> Vec<NavigationTarget> {
+    let Some(item_path) = ItemPath::from_identifier(identifier) else {
+        return vec![];
+    };
+    let name = item_path.item_name.as_ref().unwrap_or(&item_path.module_name);
+    symbol_index::world_symbols(db, Query::new(name, None))
+        .into_iter()
+        .filter(|symbol| symbol.name == *name)
+        .filter_map(|symbol| {
+            let syntax_loc = symbol.syntax_loc(db)?;
+            NavigationTarget::from_syntax_loc(db, symbol.name, syntax_loc)
+        })
+        .filter(|nav_target| {
+            let name_range = FileRange {
+                file_id: nav_target.file_id,
+                range: nav_target.focus_or_full_range(),
+            };
+            item_path_for_name(db, name_range).is_some_and(|it| it.is_same_item(&item_path))
+        })
+        .collect()
+}
+
+fn moniker_for_nav_target(db: &RootDatabase, nav_target: &NavigationTarget) -> Option<MonikerResult> {
+    let name_range = FileRange {
+        file_id: nav_target.file_id,
+        range: nav_target.focus_or_full_range(),
+    };
+    let item_path = item_path_for_name(db, name_range)?;
+    let package_root = db.package_root(db.file_package_id(name_range.file_id)).data(db);
+    let kind = if package_root.is_library() {
+        MonikerKind::Import
+    } else {
+        MonikerKind::Export
+    };
+    let uniqueness = if item_path.is_named_address {
+        MonikerUniqueness::Package
+    } else {
+        MonikerUniqueness::Global
+    };
+    Some(MonikerResult { item_path, kind, uniqueness })
+}
+
+/// Path of the module or the module item which is named by the identifier at `name_range`.
+pub(crate) fn item_path_for_name(db: &RootDatabase, name_range: FileRange) -> Option<ItemPath> {
+    let file = source_db::parse(db, name_range.file_id.intern(db)).tree();
+    let named_element =
+        algo::find_node_at_offset::<ast::NamedElement>(file.syntax(), name_range.range.start())?;
+    if named_element.name()?.ident_token().text_range() != name_range.range {
+        return None;
+    }
+    let fq_name = named_element.in_file(name_range.file_id).fq_name(db)?;
+
+    let address = fq_name.address();
+    let numeric_address = address
+        .resolve_to_numeric_address(db)
+        .filter(|it| !it.is_underscore());
+    let (module_name, item_name) = match &fq_name {
+        ItemFQName::Module { name, .. } => (name.clone(), None),
+        ItemFQName::Item { module_fq_name, name } => (module_fq_name.name(), Some(name.clone())),
+    };
+    Some(ItemPath {
+        package_name: package_name(db, db.file_package_id(name_range.file_id)),
+        is_named_address: numeric_address.is_none(),
+        address: numeric_address
+            .map(|it| it.short())
+            .unwrap_or_else(|| address.identifier_text()),
+        module_name,
+        item_name,
+    })
+}
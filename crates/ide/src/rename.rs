@@ -7,11 +7,12 @@
 use crate::RangeInfo;
 use ide_db::defs::{Definition, NameClass, NameRefClass};
 use ide_db::rename::{IdentifierKind, RenameError, bail, format_err};
-use ide_db::source_change::SourceChange;
+use ide_db::source_change::{FileSystemEdit, SourceChange};
 use ide_db::{RootDatabase, rename};
 use lang::Semantics;
-use syntax::files::{FilePosition, InFile};
+use syntax::files::{FilePosition, InFile, InFileExt};
 use syntax::{AstNode, SyntaxNode, ast};
+use vfs::FileId;
 
 type RenameResult<T> = Result<T, RenameError>;
 
@@ -48,6 +49,30 @@ pub(crate) fn rename(
     Ok(change)
 }
 
+/// Renames the module named after the file, when the file itself is renamed from `old.move` to `new.move`.
+pub(crate) fn will_rename_file(
+    db: &RootDatabase,
+    file_id: FileId,
+    new_name_stem: &str,
+) -> Option<SourceChange> {
+    let sema = Semantics::new(db, file_id);
+    let old_name_stem = rename::move_file_stem(db, file_id)?;
+    if IdentifierKind::classify(new_name_stem).ok()? != IdentifierKind::Ident {
+        return None;
+    }
+    let module = sema
+        .parse(file_id)
+        .all_modules()
+        .find(|it| it.name().is_some_and(|name| name.as_string() == old_name_stem))?;
+    let mut change =
+        rename::rename_named_element(&sema, module.in_file(file_id).map_into(), new_name_stem).ok()?;
+    // the file is already being renamed by the client, the `.spec.move` companion is still moved
+    change
+        .file_system_edits
+        .retain(|it| !matches!(it, FileSystemEdit::MoveFile { src, .. } if *src == file_id));
+    Some(change)
+}
+
 fn find_definition(
     sema: &Semantics<'_, RootDatabase>,
     syntax: &SyntaxNode,
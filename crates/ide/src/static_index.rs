@@ -0,0 +1,194 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+//! Definitions, references, hover docs and fully qualified names of all identifiers
+//! of the local packages, computed ahead of time for the code intelligence exports (SCIP).
+
+use crate::moniker::{ItemPath, item_path_for_name};
+use crate::navigation_target::NavigationTarget;
+use crate::{RangeInfo, goto_definition, hover};
+use base_db::SourceDatabase;
+use base_db::inputs::InternFileId;
+use base_db::source_db;
+use ide_db::{RootDatabase, SymbolKind, ast_kind_to_symbol_kind};
+use lang::hir_db;
+use lang::nameres::scope::VecExt;
+use std::collections::HashMap;
+use syntax::files::{FilePosition, FileRange};
+use syntax::{AstNode, SyntaxKind::*, TextRange, algo, ast};
+use vfs::FileId;
+
+/// Index of the identifiers of all `.move` files of the local packages.
+#[derive(Debug)]
+pub struct StaticIndex {
+    pub files: Vec<StaticIndexedFile>,
+    /// Resolved definitions, indexed by the [`TokenId`].
+    pub tokens: Vec<TokenStaticData>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenId(pub usize);
+
+#[derive(Debug)]
+pub struct StaticIndexedFile {
+    pub file_id: FileId,
+    /// Identifiers of the file which are resolved to a single definition.
+    pub tokens: Vec<(TextRange, TokenId)>,
+}
+
+/// Definition together with all its references from the indexed files.
+#[derive(Debug)]
+pub struct TokenStaticData {
+    pub name: String,
+    pub kind: Option<SymbolKind>,
+    pub definition: FileRange,
+    pub hover: Option<String>,
+    /// `None` for the definitions which are not visible outside of the module,
+    /// like local variables, parameters or fields.
+    pub item_path: Option<ItemPath>,
+    /// Path and kind of the closest enclosing item for the definitions without the `item_path`.
+    pub container: Option<(ItemPath, SymbolKind)>,
+    pub references: Vec<ReferenceData>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReferenceData {
+    pub range: FileRange,
+    pub is_definition: bool,
+}
+
+impl StaticIndex {
+    pub(crate) fn compute(db: &RootDatabase) -> StaticIndex {
+        let _p = tracing::info_span!("StaticIndex::compute").entered();
+
+        let mut index = StaticIndexBuilder {
+            db,
+            files: vec![],
+            tokens: vec![],
+            definitions: HashMap::new(),
+        };
+        let builtins_file_id = db.builtins_file_id().map(|it| it.data(db));
+        for package_id in db.all_package_ids().data(db) {
+            if db.package_root(package_id).data(db).is_library() {
+                continue;
+            }
+            for file_id in hir_db::source_file_ids_in_package(db, package_id) {
+                if Some(file_id) != builtins_file_id {
+                    index.add_file(file_id);
+                }
+            }
+        }
+        StaticIndex {
+            files: index.files,
+            tokens: index.tokens,
+        }
+    }
+}
+
+struct StaticIndexBuilder<'db> {
+    db: &'db RootDatabase,
+    files: Vec<StaticIndexedFile>,
+    tokens: Vec<TokenStaticData>,
+    definitions: HashMap<FileRange, TokenId>,
+}
+
+impl StaticIndexBuilder<'_> {
+    fn add_file(&mut self, file_id: FileId) {
+        let db = self.db;
+        let file = source_file(db, file_id);
+        let mut indexed_file = StaticIndexedFile { file_id, tokens: vec![] };
+
+        let ident_tokens = file
+            .syntax()
+            .descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| matches!(it.kind(), IDENT | QUOTE_IDENT));
+        for token in ident_tokens {
+            let range = token.text_range();
+            let position = FilePosition {
+                file_id,
+                offset: range.start(),
+            };
+            let Some(RangeInfo { info: nav_targets, .. }) =
+                goto_definition::goto_definition_multi(db, position)
+            else {
+                continue;
+            };
+            // unresolved or ambiguous
+            let Some(nav_target) = nav_targets.single_or_none() else {
+                continue;
+            };
+            let token_id = self.token_for_definition(nav_target);
+
+            let range = FileRange { file_id, range };
+            let token = &mut self.tokens[token_id.0];
+            token.references.push(ReferenceData {
+                range,
+                is_definition: token.definition == range,
+            });
+            indexed_file.tokens.push((range.range, token_id));
+        }
+        self.files.push(indexed_file);
+    }
+
+    fn token_for_definition(&mut self, nav_target: NavigationTarget) -> TokenId {
+        let definition = FileRange {
+            file_id: nav_target.file_id,
+            range: nav_target.focus_or_full_range(),
+        };
+        if let Some(token_id) = self.definitions.get(&definition) {
+            return *token_id;
+        }
+        let hover = hover::hover(
+            self.db,
+            FilePosition {
+                file_id: definition.file_id,
+                offset: definition.range.start(),
+            },
+        )
+        .map(|it| it.info.doc_string);
+        let item_path = item_path_for_name(self.db, definition);
+        let container = match item_path {
+            Some(_) => None,
+            None => container_item(self.db, definition),
+        };
+        let token_id = TokenId(self.tokens.len());
+        self.tokens.push(TokenStaticData {
+            name: nav_target.name,
+            kind: nav_target.kind,
+            definition,
+            hover,
+            item_path,
+            container,
+            references: vec![],
+        });
+        self.definitions.insert(definition, token_id);
+        token_id
+    }
+}
+
+fn container_item(db: &RootDatabase, name_range: FileRange) -> Option<(ItemPath, SymbolKind)> {
+    let file = source_file(db, name_range.file_id);
+    let named_element =
+        algo::find_node_at_offset::<ast::NamedElement>(file.syntax(), name_range.range.start())?;
+    named_element
+        .syntax()
+        .ancestors()
+        .skip(1)
+        .filter_map(ast::NamedElement::cast)
+        .find_map(|item| {
+            let item_name_range = FileRange {
+                file_id: name_range.file_id,
+                range: item.name()?.ident_token().text_range(),
+            };
+            let item_path = item_path_for_name(db, item_name_range)?;
+            Some((item_path, ast_kind_to_symbol_kind(&item)))
+        })
+}
+
+fn source_file(db: &RootDatabase, file_id: FileId) -> ast::SourceFile {
+    source_db::parse(db, file_id.intern(db)).tree()
+}
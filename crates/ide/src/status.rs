@@ -0,0 +1,144 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This file contains code originally from rust-analyzer, licensed under Apache License 2.0.
+// Modifications have been made to the original code.
+
+use base_db::SourceDatabase;
+use base_db::package_root::{PackageId, PackageKind, PackageRoot};
+use base_db::source_db;
+use ide_db::RootDatabase;
+use ide_db::helpers::package_name;
+use lang::hir_db;
+use std::collections::BTreeMap;
+use stdx::format_to;
+use vfs::FileId;
+
+// Feature: Status
+//
+// Shows the package roots of the analysis with their dependencies and named addresses,
+// and which package owns the current file.
+//
+// | Editor  | Action Name |
+// |---------|-------------|
+// | VS Code | **move-on-aptos: Status** |
+pub(crate) fn status(db: &RootDatabase, file_id: Option<FileId>) -> String {
+    let mut buf = String::new();
+
+    if let Some(file_id) = file_id {
+        format_to!(buf, "{}\n", file_status(db, file_id));
+    }
+
+    let package_ids = db.all_package_ids().data(db);
+    format_to!(buf, "Package roots ({}):\n", package_ids.len());
+    for package_id in package_ids.iter().copied() {
+        let package_root = db.package_root(package_id).data(db);
+        format_to!(
+            buf,
+            "  {}: {} files",
+            package_description(db, package_id, &package_root),
+            package_root.file_set.len()
+        );
+        if let Some(metadata) = source_db::metadata_for_package_id(db, package_id) {
+            if !metadata.resolve_deps {
+                buf.push_str(", dependencies are not resolved");
+            }
+            let n_deps = hir_db::transitive_dep_package_ids(db, package_id).len() - 1;
+            format_to!(buf, ", {n_deps} transitive dependencies");
+        }
+        buf.push('\n');
+        for missing_dep in hir_db::missing_dependencies(db, package_id) {
+            format_to!(buf, "    missing dependency: {missing_dep}\n");
+        }
+    }
+
+    buf.push_str("\nNamed addresses:\n");
+    buf.push_str(&named_addresses_status(db));
+
+    buf
+}
+
+/// Package which owns the file, and the reason why it's selected.
+fn file_status(db: &RootDatabase, file_id: FileId) -> String {
+    if db.builtins_file_id().is_some_and(|it| it.data(db) == file_id) {
+        return "File: builtins file, not a part of any package\n".to_string();
+    }
+    let package_id = db.file_package_id(file_id);
+    let package_root = db.package_root(package_id).data(db);
+
+    let mut buf = String::new();
+    match package_root.path_for_file(&file_id) {
+        Some(file_path) => format_to!(buf, "File: {file_path}\n"),
+        None => format_to!(buf, "File: {file_id:?}\n"),
+    }
+    format_to!(
+        buf,
+        "  owned by {}\n",
+        package_description(db, package_id, &package_root)
+    );
+
+    let package_dir = package_root
+        .manifest_file_id
+        .and_then(|it| package_root.path_for_file(&it))
+        .and_then(|it| it.parent());
+    let reason = match package_dir {
+        Some(package_dir) => format!(
+            "`{package_dir}` is the closest directory with the `Move.toml` which contains the file"
+        ),
+        None => "file is outside of all package directories".to_string(),
+    };
+    format_to!(buf, "  reason: {reason}\n");
+
+    let metadata = source_db::metadata_for_package_id(db, package_id);
+    if metadata.is_some_and(|it| !it.resolve_deps) {
+        buf.push_str("  dependencies of the package are not resolved (`resolve_deps = false`)\n");
+    }
+    buf
+}
+
+fn package_description(db: &RootDatabase, package_id: PackageId, package_root: &PackageRoot) -> String {
+    let kind = match package_root.kind {
+        PackageKind::Local => "local",
+        PackageKind::Library => "library",
+    };
+    let manifest_path = package_root
+        .manifest_file_id
+        .and_then(|it| package_root.path_for_file(&it));
+    match manifest_path {
+        Some(manifest_path) => format!("`{}` ({kind}, {manifest_path})", package_name(db, package_id)),
+        None => format!("`{}` ({kind}, no manifest)", package_name(db, package_id)),
+    }
+}
+
+/// Named addresses of all packages together with the packages which declare them.
+fn named_addresses_status(db: &RootDatabase) -> String {
+    let mut declarations = BTreeMap::<String, Vec<(String, String)>>::new();
+    for package_id in db.all_package_ids().data(db) {
+        let Some(metadata) = source_db::metadata_for_package_id(db, package_id) else {
+            continue;
+        };
+        for (address_name, address_value) in metadata.named_addresses {
+            declarations
+                .entry(address_name)
+                .or_default()
+                .push((address_value, package_name(db, package_id)));
+        }
+    }
+
+    let mut buf = String::new();
+    let named_addresses = hir_db::named_addresses(db)
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    for (address_name, address_value) in named_addresses {
+        let sources = match declarations.get(&address_name) {
+            Some(declarations) => declarations
+                .iter()
+                .map(|(value, package_name)| format!("{value} in `{package_name}`"))
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => "default framework address".to_string(),
+        };
+        format_to!(buf, "  {address_name} = {address_value} ({sources})\n");
+    }
+    buf
+}
@@ -0,0 +1,522 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compile-time evaluation of the constant initializers, like `const MAX: u64 = 1 << 20;`.
+
+use crate::loc::{SyntaxLoc, SyntaxLocFileExt, SyntaxLocInput};
+use crate::nameres;
+use crate::nameres::address::{Address, NumericAddress};
+use crate::types::ty::Ty;
+use crate::types::ty::integer::IntegerKind;
+use crate::types::ty::range_like::TySequence;
+use crate::types::ty_db;
+use base_db::SourceDatabase;
+use num_bigint::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use syntax::ast::{ArithOp, BinaryOp, CmpOp, IsString, LogicOp, Ordering};
+use syntax::files::{InFile, InFileExt};
+use syntax::{AstNode, AstToken, TextRange, ast};
+use vfs::FileId;
+
+/// Limits the chain of constants referencing other constants.
+const MAX_CONST_REF_DEPTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstValue {
+    Integer {
+        value: BigInt,
+        kind: IntegerKind,
+    },
+    Bool(bool),
+    Address(NumericAddress),
+    /// `vector<u8>` from the byte string `b"..."` or the hex string `x"..."`.
+    Bytes(Vec<u8>),
+    Vector(Vec<ConstValue>),
+}
+
+impl ConstValue {
+    fn integer(&self) -> Option<(&BigInt, IntegerKind)> {
+        match self {
+            ConstValue::Integer { value, kind } => Some((value, *kind)),
+            _ => None,
+        }
+    }
+
+    /// Integers are compared by value, as the kind of the untyped literals is not always known.
+    fn is_equal_to(&self, other: &ConstValue) -> bool {
+        match (self, other) {
+            (ConstValue::Integer { value, .. }, ConstValue::Integer { value: other_value, .. }) => {
+                value == other_value
+            }
+            (ConstValue::Vector(items), ConstValue::Vector(other_items)) => {
+                items.len() == other_items.len()
+                    && items
+                        .iter()
+                        .zip(other_items)
+                        .all(|(it, other)| it.is_equal_to(other))
+            }
+            _ => self == other,
+        }
+    }
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstValue::Integer { value, .. } => write!(f, "{value}"),
+            ConstValue::Bool(value) => write!(f, "{value}"),
+            ConstValue::Address(address) => write!(f, "@{}", address.short()),
+            ConstValue::Bytes(bytes) => {
+                let is_printable = bytes
+                    .iter()
+                    .all(|it| (it.is_ascii_graphic() && !matches!(it, b'"' | b'\\')) || *it == b' ');
+                if is_printable {
+                    write!(f, "b\"{}\"", String::from_utf8_lossy(bytes))
+                } else {
+                    write!(f, "x\"")?;
+                    for byte in bytes {
+                        write!(f, "{byte:02x}")?;
+                    }
+                    write!(f, "\"")
+                }
+            }
+            ConstValue::Vector(items) => {
+                write!(f, "vector[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstEvalError {
+    /// Expression is not supported by the evaluator, is invalid, or refers to a constant
+    /// which cannot be evaluated.
+    NotEvaluated,
+    /// Result of the expression at `range` does not fit into the integer type.
+    Overflow {
+        range: TextRange,
+        kind: IntegerKind,
+    },
+    /// Shift amount at `range` is not less than the number of bits in the integer type.
+    ShiftOutOfRange {
+        range: TextRange,
+        kind: IntegerKind,
+    },
+    DivisionByZero {
+        range: TextRange,
+    },
+}
+
+type ConstEvalResult = Result<ConstValue, ConstEvalError>;
+
+/// Evaluates the initializer of the constant. Ranges of the errors are in the file of the constant,
+/// errors of the referenced constants are not propagated, and result in [`ConstEvalError::NotEvaluated`].
+pub fn evaluate_const(db: &dyn SourceDatabase, const_: InFile<ast::Const>) -> ConstEvalResult {
+    evaluate_const_tracked(db, SyntaxLocInput::new(db, const_.loc())).clone()
+}
+
+#[salsa_macros::tracked(returns(ref))]
+fn evaluate_const_tracked<'db>(
+    db: &'db dyn SourceDatabase,
+    const_loc: SyntaxLocInput<'db>,
+) -> ConstEvalResult {
+    let const_ = const_loc
+        .to_ast::<ast::Const>(db)
+        .ok_or(ConstEvalError::NotEvaluated)?;
+    let const_refs = RefCell::new(HashMap::new());
+    evaluate_const_with_depth(db, const_, &const_refs, 0)
+}
+
+/// Values of the constants referenced during the evaluation, so that each of them is evaluated
+/// only once. `None` for the constants which are being evaluated, or which cannot be evaluated.
+type ConstRefValues = RefCell<HashMap<SyntaxLoc, Option<ConstValue>>>;
+
+fn evaluate_const_with_depth(
+    db: &dyn SourceDatabase,
+    const_: InFile<ast::Const>,
+    const_refs: &ConstRefValues,
+    depth: usize,
+) -> ConstEvalResult {
+    // breaks the cycles
+    const_refs.borrow_mut().insert(const_.loc(), None);
+
+    let (file_id, const_) = const_.unpack();
+    let initializer_expr = const_
+        .initializer()
+        .and_then(|it| it.expr())
+        .ok_or(ConstEvalError::NotEvaluated)?;
+    let const_ty = const_
+        .type_()
+        .map(|it| ty_db::lower_type(db, it.in_file(file_id), false));
+    let ctx = ConstEvalCtx {
+        db,
+        file_id,
+        const_refs,
+        depth,
+    };
+    ctx.eval_expr(&initializer_expr, const_ty.as_ref())
+}
+
+struct ConstEvalCtx<'db> {
+    db: &'db dyn SourceDatabase,
+    file_id: FileId,
+    const_refs: &'db ConstRefValues,
+    depth: usize,
+}
+
+impl ConstEvalCtx<'_> {
+    fn eval_expr(&self, expr: &ast::Expr, expected_ty: Option<&Ty>) -> ConstEvalResult {
+        match expr {
+            ast::Expr::Literal(literal) => self.eval_literal(literal, expected_ty),
+            ast::Expr::ParenExpr(paren_expr) => {
+                let inner_expr = paren_expr.expr().ok_or(ConstEvalError::NotEvaluated)?;
+                self.eval_expr(&inner_expr, expected_ty)
+            }
+            ast::Expr::AnnotatedExpr(annotated_expr) => {
+                let inner_expr = annotated_expr.expr().ok_or(ConstEvalError::NotEvaluated)?;
+                let annotated_ty = annotated_expr
+                    .type_()
+                    .map(|it| ty_db::lower_type(self.db, it.in_file(self.file_id), false));
+                self.eval_expr(&inner_expr, annotated_ty.as_ref().or(expected_ty))
+            }
+            ast::Expr::BangExpr(bang_expr) => {
+                let inner_expr = bang_expr.expr().ok_or(ConstEvalError::NotEvaluated)?;
+                Ok(ConstValue::Bool(!self.eval_bool(&inner_expr)?))
+            }
+            ast::Expr::MinusExpr(minus_expr) => self.eval_negation(minus_expr, expected_ty),
+            ast::Expr::BinExpr(bin_expr) => self.eval_bin_expr(bin_expr, expected_ty),
+            ast::Expr::CastExpr(cast_expr) => self.eval_cast(cast_expr),
+            ast::Expr::VectorLitExpr(vector_lit_expr) => {
+                self.eval_vector_lit(vector_lit_expr, expected_ty)
+            }
+            ast::Expr::PathExpr(path_expr) => self.eval_const_ref(path_expr),
+            _ => Err(ConstEvalError::NotEvaluated),
+        }
+    }
+
+    fn eval_bool(&self, expr: &ast::Expr) -> Result<bool, ConstEvalError> {
+        match self.eval_expr(expr, Some(&Ty::Bool))? {
+            ConstValue::Bool(value) => Ok(value),
+            _ => Err(ConstEvalError::NotEvaluated),
+        }
+    }
+
+    fn eval_integer(
+        &self,
+        expr: &ast::Expr,
+        expected_kind: Option<IntegerKind>,
+    ) -> Result<(BigInt, IntegerKind), ConstEvalError> {
+        let expected_ty = expected_kind.map(Ty::Integer);
+        let value = self.eval_expr(expr, expected_ty.as_ref())?;
+        value
+            .integer()
+            .map(|(value, kind)| (value.clone(), kind))
+            .ok_or(ConstEvalError::NotEvaluated)
+    }
+
+    fn eval_literal(&self, literal: &ast::Literal, expected_ty: Option<&Ty>) -> ConstEvalResult {
+        match literal.kind() {
+            ast::LiteralKind::Bool(value) => Ok(ConstValue::Bool(value)),
+            ast::LiteralKind::IntNumber(int_number) => {
+                let (value, kind) = parse_int_number(int_number, expected_ty)?;
+                integer_in_range(value, kind, literal.syntax().text_range())
+            }
+            ast::LiteralKind::ByteString(byte_string) => {
+                let bytes = parse_byte_string(byte_string.text_without_quotes())
+                    .ok_or(ConstEvalError::NotEvaluated)?;
+                Ok(ConstValue::Bytes(bytes))
+            }
+            ast::LiteralKind::HexString(hex_string) => {
+                let bytes = parse_hex_string(hex_string.text_without_quotes())
+                    .ok_or(ConstEvalError::NotEvaluated)?;
+                Ok(ConstValue::Bytes(bytes))
+            }
+            ast::LiteralKind::Address(address_lit) => {
+                let address = if let Some(value_address) = address_lit.value_address() {
+                    Address::value(value_address.int_number_token().text())
+                } else {
+                    let named_address =
+                        address_lit.named_address().ok_or(ConstEvalError::NotEvaluated)?;
+                    Address::named(named_address.ident_token().text())
+                };
+                address
+                    .resolve_to_numeric_address(self.db)
+                    .filter(|it| !it.is_underscore())
+                    .map(ConstValue::Address)
+                    .ok_or(ConstEvalError::NotEvaluated)
+            }
+            ast::LiteralKind::Invalid => Err(ConstEvalError::NotEvaluated),
+        }
+    }
+
+    fn eval_negation(&self, minus_expr: &ast::MinusExpr, expected_ty: Option<&Ty>) -> ConstEvalResult {
+        let inner_expr = minus_expr.expr().ok_or(ConstEvalError::NotEvaluated)?;
+        let (value, kind) = match &inner_expr {
+            // `-128i8` is in range, while `128i8` is not
+            ast::Expr::Literal(literal) => match literal.kind() {
+                ast::LiteralKind::IntNumber(int_number) => parse_int_number(int_number, expected_ty)?,
+                _ => return Err(ConstEvalError::NotEvaluated),
+            },
+            _ => self.eval_integer(&inner_expr, expected_integer_kind(expected_ty))?,
+        };
+        if !kind.is_signed() && !kind.is_default() {
+            // unsigned integers cannot be negated, which is a type error
+            return Err(ConstEvalError::NotEvaluated);
+        }
+        integer_in_range(-value, kind, minus_expr.syntax().text_range())
+    }
+
+    fn eval_bin_expr(&self, bin_expr: &ast::BinExpr, expected_ty: Option<&Ty>) -> ConstEvalResult {
+        let (lhs, (_, op), rhs) = bin_expr.unpack().ok_or(ConstEvalError::NotEvaluated)?;
+        let rhs = rhs.ok_or(ConstEvalError::NotEvaluated)?;
+        let range = bin_expr.syntax().text_range();
+        match op {
+            BinaryOp::LogicOp(LogicOp::And) => {
+                Ok(ConstValue::Bool(self.eval_bool(&lhs)? && self.eval_bool(&rhs)?))
+            }
+            BinaryOp::LogicOp(LogicOp::Or) => {
+                Ok(ConstValue::Bool(self.eval_bool(&lhs)? || self.eval_bool(&rhs)?))
+            }
+            BinaryOp::CmpOp(CmpOp::Eq { negated }) => {
+                let lhs_value = self.eval_expr(&lhs, None)?;
+                let rhs_value = self.eval_expr(&rhs, None)?;
+                Ok(ConstValue::Bool(lhs_value.is_equal_to(&rhs_value) != negated))
+            }
+            BinaryOp::CmpOp(CmpOp::Ord { ordering, strict }) => {
+                let (lhs_value, _) = self.eval_integer(&lhs, None)?;
+                let (rhs_value, _) = self.eval_integer(&rhs, None)?;
+                let result = match (ordering, strict) {
+                    (Ordering::Less, true) => lhs_value < rhs_value,
+                    (Ordering::Less, false) => lhs_value <= rhs_value,
+                    (Ordering::Greater, true) => lhs_value > rhs_value,
+                    (Ordering::Greater, false) => lhs_value >= rhs_value,
+                };
+                Ok(ConstValue::Bool(result))
+            }
+            BinaryOp::ArithOp(arith_op @ (ArithOp::Shl | ArithOp::Shr)) => {
+                let (value, kind) = self.eval_integer(&lhs, expected_integer_kind(expected_ty))?;
+                let (shift, _) = self.eval_integer(&rhs, Some(IntegerKind::U8))?;
+                let shift = shift.to_u32().ok_or(ConstEvalError::NotEvaluated)?;
+                if integer_bits(kind).is_some_and(|bits| shift >= bits) {
+                    return Err(ConstEvalError::ShiftOutOfRange { range, kind });
+                }
+                let result = match arith_op {
+                    // bits shifted out of the integer are discarded
+                    ArithOp::Shl => truncate_to_bits(value << shift, kind),
+                    _ => value >> shift,
+                };
+                Ok(ConstValue::Integer { value: result, kind })
+            }
+            BinaryOp::ArithOp(arith_op) => {
+                let expected_kind = expected_integer_kind(expected_ty);
+                let (lhs_value, lhs_kind) = self.eval_integer(&lhs, expected_kind)?;
+                let (rhs_value, rhs_kind) = self.eval_integer(&rhs, expected_kind)?;
+                let kind = match (lhs_kind, rhs_kind) {
+                    (IntegerKind::Integer, kind) | (kind, IntegerKind::Integer) => kind,
+                    (lhs_kind, rhs_kind) if lhs_kind == rhs_kind => lhs_kind,
+                    // mismatched types are reported by the type checker
+                    _ => return Err(ConstEvalError::NotEvaluated),
+                };
+                // untyped operand gets the type of the other one
+                check_integer_range(&lhs_value, kind, lhs.syntax().text_range())?;
+                check_integer_range(&rhs_value, kind, rhs.syntax().text_range())?;
+
+                if matches!(arith_op, ArithOp::Div | ArithOp::Rem) && rhs_value.is_zero() {
+                    return Err(ConstEvalError::DivisionByZero { range });
+                }
+                let result = match arith_op {
+                    ArithOp::Add => lhs_value + rhs_value,
+                    ArithOp::Sub => lhs_value - rhs_value,
+                    ArithOp::Mul => lhs_value * rhs_value,
+                    // both truncate towards zero, as in Move
+                    ArithOp::Div => lhs_value / rhs_value,
+                    ArithOp::Rem => lhs_value % rhs_value,
+                    ArithOp::BitAnd => lhs_value & rhs_value,
+                    ArithOp::BitOr => lhs_value | rhs_value,
+                    ArithOp::BitXor => lhs_value ^ rhs_value,
+                    ArithOp::Shl | ArithOp::Shr => unreachable!(),
+                };
+                integer_in_range(result, kind, range)
+            }
+            BinaryOp::LogicOp(LogicOp::Implies | LogicOp::IfAndOnlyIf) | BinaryOp::Assignment { .. } => {
+                Err(ConstEvalError::NotEvaluated)
+            }
+        }
+    }
+
+    fn eval_cast(&self, cast_expr: &ast::CastExpr) -> ConstEvalResult {
+        let cast_type = cast_expr.type_().ok_or(ConstEvalError::NotEvaluated)?;
+        let cast_kind = ty_db::lower_type(self.db, cast_type.in_file(self.file_id), false)
+            .into_ty_integer()
+            .ok_or(ConstEvalError::NotEvaluated)?;
+        let (value, _) = self.eval_integer(&cast_expr.expr(), None)?;
+        integer_in_range(value, cast_kind, cast_expr.syntax().text_range())
+    }
+
+    fn eval_vector_lit(
+        &self,
+        vector_lit_expr: &ast::VectorLitExpr,
+        expected_ty: Option<&Ty>,
+    ) -> ConstEvalResult {
+        let explicit_item_ty = vector_lit_expr
+            .type_arg()
+            .and_then(|it| it.type_())
+            .map(|it| ty_db::lower_type(self.db, it.in_file(self.file_id), false));
+        let item_ty = explicit_item_ty.or_else(|| match expected_ty {
+            Some(Ty::Seq(TySequence::Vector(item_ty))) => Some(item_ty.as_ref().clone()),
+            _ => None,
+        });
+        let items = vector_lit_expr
+            .arg_exprs()
+            .map(|arg_expr| self.eval_expr(&arg_expr, item_ty.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ConstValue::Vector(items))
+    }
+
+    fn eval_const_ref(&self, path_expr: &ast::PathExpr) -> ConstEvalResult {
+        if self.depth >= MAX_CONST_REF_DEPTH {
+            return Err(ConstEvalError::NotEvaluated);
+        }
+        let const_ =
+            nameres::resolve_no_inf_cast::<ast::Const>(self.db, path_expr.path().in_file(self.file_id))
+                .ok_or(ConstEvalError::NotEvaluated)?;
+        let const_loc = const_.loc();
+        if let Some(value) = self.const_refs.borrow().get(&const_loc) {
+            return value.clone().ok_or(ConstEvalError::NotEvaluated);
+        }
+        // errors are reported for the referenced constant itself
+        let value = evaluate_const_with_depth(self.db, const_, self.const_refs, self.depth + 1).ok();
+        self.const_refs.borrow_mut().insert(const_loc, value.clone());
+        value.ok_or(ConstEvalError::NotEvaluated)
+    }
+}
+
+fn expected_integer_kind(expected_ty: Option<&Ty>) -> Option<IntegerKind> {
+    expected_ty.and_then(|it| it.clone().into_ty_integer())
+}
+
+/// Value and the kind of the integer literal, the kind is taken from the suffix, or from the expected type.
+fn parse_int_number(
+    int_number: ast::IntNumber,
+    expected_ty: Option<&Ty>,
+) -> Result<(BigInt, IntegerKind), ConstEvalError> {
+    let suffix_kind = IntegerKind::from_suffixed_literal(int_number.clone());
+    let text = int_number.text().replace('_', "").to_lowercase();
+    let digits = match suffix_kind {
+        Some(kind) => text.strip_suffix(&kind.to_string()).unwrap_or(&text),
+        None => &text,
+    };
+    let value = match digits.strip_prefix("0x") {
+        Some(hex_digits) => BigInt::parse_bytes(hex_digits.as_bytes(), 16),
+        None => BigInt::parse_bytes(digits.as_bytes(), 10),
+    }
+    .ok_or(ConstEvalError::NotEvaluated)?;
+    let kind = suffix_kind
+        .or_else(|| expected_integer_kind(expected_ty))
+        .unwrap_or(IntegerKind::Integer);
+    Ok((value, kind))
+}
+
+fn integer_in_range(value: BigInt, kind: IntegerKind, range: TextRange) -> ConstEvalResult {
+    check_integer_range(&value, kind, range)?;
+    Ok(ConstValue::Integer { value, kind })
+}
+
+fn check_integer_range(
+    value: &BigInt,
+    kind: IntegerKind,
+    range: TextRange,
+) -> Result<(), ConstEvalError> {
+    if let Some((min, max)) = integer_bounds(kind)
+        && (*value < min || *value > max)
+    {
+        return Err(ConstEvalError::Overflow { range, kind });
+    }
+    Ok(())
+}
+
+fn integer_bits(kind: IntegerKind) -> Option<u32> {
+    let bits = match kind {
+        IntegerKind::Integer => return None,
+        IntegerKind::U8 | IntegerKind::I8 => 8,
+        IntegerKind::U16 | IntegerKind::I16 => 16,
+        IntegerKind::U32 | IntegerKind::I32 => 32,
+        IntegerKind::U64 | IntegerKind::I64 => 64,
+        IntegerKind::U128 | IntegerKind::I128 => 128,
+        IntegerKind::U256 | IntegerKind::I256 => 256,
+    };
+    Some(bits)
+}
+
+fn integer_bounds(kind: IntegerKind) -> Option<(BigInt, BigInt)> {
+    let bits = integer_bits(kind)?;
+    if kind.is_signed() {
+        let half = BigInt::one() << (bits - 1);
+        Some((-half.clone(), half - 1))
+    } else {
+        Some((BigInt::zero(), (BigInt::one() << bits) - 1))
+    }
+}
+
+/// Keeps the lowest bits of the value, as the two's complement for the signed integers.
+fn truncate_to_bits(value: BigInt, kind: IntegerKind) -> BigInt {
+    let Some(bits) = integer_bits(kind) else {
+        return value;
+    };
+    let modulus = BigInt::one() << bits;
+    let truncated = ((value % &modulus) + &modulus) % &modulus;
+    if kind.is_signed() && truncated >= (BigInt::one() << (bits - 1)) {
+        truncated - modulus
+    } else {
+        truncated
+    }
+}
+
+/// Contents of the `b"..."` literal, with the `\n`, `\r`, `\t`, `\\`, `\0`, `\"` and `\xHH` escapes.
+fn parse_byte_string(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let byte = match chars.next()? {
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            '\\' => b'\\',
+            '0' => b'\0',
+            '"' => b'"',
+            'x' => {
+                let hex_digits = [chars.next()?, chars.next()?].iter().collect::<String>();
+                u8::from_str_radix(&hex_digits, 16).ok()?
+            }
+            _ => return None,
+        };
+        bytes.push(byte);
+    }
+    Some(bytes)
+}
+
+/// Contents of the `x"..."` literal.
+fn parse_hex_string(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Control flow graph of the function body.
+//!
+//! There's a node for every evaluated expression, created after the nodes of its subexpressions,
+//! and a node for the entry of every statement and tail expression of the block. Diverging
+//! expressions (`return`, `abort`, `break`, `continue`) are followed by a node without predecessors,
+//! so the code after them is unreachable from the entry.
+
+mod builder;
+
+use std::collections::{HashMap, HashSet};
+use syntax::{SyntaxNode, ast};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CfgNodeId(u32);
+
+impl CfgNodeId {
+    fn idx(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CfgNodeKind {
+    /// Start of the function body.
+    Entry,
+    /// End of the function, reached by `return` or by the completion of the body.
+    Exit,
+    /// Expression is evaluated.
+    Expr(ast::Expr),
+    /// Entry of the block statement or the tail expression, join point of the branches,
+    /// or the point right after the diverging expression.
+    Dummy,
+}
+
+#[derive(Debug, Clone)]
+pub struct CfgNode {
+    pub kind: CfgNodeKind,
+    pub predecessors: Vec<CfgNodeId>,
+    pub successors: Vec<CfgNodeId>,
+}
+
+#[derive(Debug)]
+pub struct ControlFlowGraph {
+    nodes: Vec<CfgNode>,
+    entry: CfgNodeId,
+    exit: CfgNodeId,
+    /// Completion of the body block, right before the exit.
+    body_end: CfgNodeId,
+    expr_nodes: HashMap<ast::Expr, CfgNodeId>,
+    block_element_entries: HashMap<SyntaxNode, CfgNodeId>,
+    label_decls: Vec<ast::LabelDecl>,
+    used_label_decls: HashSet<ast::LabelDecl>,
+    reachable: Vec<bool>,
+}
+
+impl ControlFlowGraph {
+    pub fn for_fun(fun: &ast::Fun) -> Option<ControlFlowGraph> {
+        let body = fun.body()?;
+        Some(ControlFlowGraph::new(&body))
+    }
+
+    pub fn new(body: &ast::BlockExpr) -> ControlFlowGraph {
+        let mut graph = builder::build(body);
+        graph.reachable = graph.compute_reachable();
+        graph
+    }
+
+    pub fn entry(&self) -> CfgNodeId {
+        self.entry
+    }
+
+    pub fn exit(&self) -> CfgNodeId {
+        self.exit
+    }
+
+    pub fn node(&self, id: CfgNodeId) -> &CfgNode {
+        &self.nodes[id.idx()]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (CfgNodeId, &CfgNode)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(idx, node)| (CfgNodeId(idx as u32), node))
+    }
+
+    pub fn expr_node(&self, expr: &ast::Expr) -> Option<CfgNodeId> {
+        self.expr_nodes.get(expr).copied()
+    }
+
+    /// Entry node of the statement or the tail expression of the block.
+    pub fn block_element_entry(&self, element: &SyntaxNode) -> Option<CfgNodeId> {
+        self.block_element_entries.get(element).copied()
+    }
+
+    pub fn is_reachable(&self, id: CfgNodeId) -> bool {
+        self.reachable[id.idx()]
+    }
+
+    /// `None` for the nodes outside the graph, like the statements of the lambda bodies.
+    pub fn is_block_element_reachable(&self, element: &SyntaxNode) -> Option<bool> {
+        self.block_element_entry(element).map(|it| self.is_reachable(it))
+    }
+
+    /// Whether the body can complete without `return`, `abort` or an infinite loop.
+    pub fn can_complete_normally(&self) -> bool {
+        self.is_reachable(self.body_end)
+    }
+
+    /// Loop labels not referred to by any `break` or `continue`.
+    pub fn unused_labels(&self) -> Vec<ast::LabelDecl> {
+        self.label_decls
+            .iter()
+            .filter(|it| !self.used_label_decls.contains(it))
+            .cloned()
+            .collect()
+    }
+
+    fn compute_reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.nodes.len()];
+        let mut stack = vec![self.entry];
+        while let Some(id) = stack.pop() {
+            if reachable[id.idx()] {
+                continue;
+            }
+            reachable[id.idx()] = true;
+            stack.extend(self.node(id).successors.iter().copied());
+        }
+        reachable
+    }
+}
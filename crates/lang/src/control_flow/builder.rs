@@ -0,0 +1,290 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::control_flow::{CfgNode, CfgNodeId, CfgNodeKind, ControlFlowGraph};
+use std::collections::{HashMap, HashSet};
+use syntax::ast::HasStmts;
+use syntax::ast::operators::{BinaryOp, LogicOp};
+use syntax::{AstNode, SyntaxNode, ast};
+
+pub(super) fn build(body: &ast::BlockExpr) -> ControlFlowGraph {
+    let mut builder = CfgBuilder {
+        nodes: vec![],
+        exit: CfgNodeId(0),
+        expr_nodes: HashMap::new(),
+        block_element_entries: HashMap::new(),
+        label_decls: vec![],
+        used_label_decls: HashSet::new(),
+        loop_scopes: vec![],
+    };
+    let entry = builder.add_node(CfgNodeKind::Entry, &[]);
+    builder.exit = builder.add_node(CfgNodeKind::Exit, &[]);
+    let body_end = builder.block_expr(body, entry);
+    builder.add_edge(body_end, builder.exit);
+
+    ControlFlowGraph {
+        nodes: builder.nodes,
+        entry,
+        exit: builder.exit,
+        body_end,
+        expr_nodes: builder.expr_nodes,
+        block_element_entries: builder.block_element_entries,
+        label_decls: builder.label_decls,
+        used_label_decls: builder.used_label_decls,
+        reachable: vec![],
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LoopScope {
+    label_decl: Option<ast::LabelDecl>,
+    continue_target: CfgNodeId,
+    break_target: CfgNodeId,
+}
+
+struct CfgBuilder {
+    nodes: Vec<CfgNode>,
+    exit: CfgNodeId,
+    expr_nodes: HashMap<ast::Expr, CfgNodeId>,
+    block_element_entries: HashMap<SyntaxNode, CfgNodeId>,
+    label_decls: Vec<ast::LabelDecl>,
+    used_label_decls: HashSet<ast::LabelDecl>,
+    loop_scopes: Vec<LoopScope>,
+}
+
+impl CfgBuilder {
+    /// Returns the node where the control goes after the `expr` is evaluated.
+    fn expr(&mut self, expr: &ast::Expr, pred: CfgNodeId) -> CfgNodeId {
+        match expr {
+            ast::Expr::BlockExpr(block_expr) => self.block_expr(block_expr, pred),
+            ast::Expr::IfExpr(if_expr) => {
+                let condition_exit = self.opt_expr(if_expr.condition().and_then(|it| it.expr()), pred);
+                let then_exit = self.opt_block_or_inline_expr(if_expr.then_branch(), condition_exit);
+                let else_exit = self.opt_block_or_inline_expr(if_expr.else_branch(), condition_exit);
+                self.add_expr_node(expr, &[then_exit, else_exit])
+            }
+            ast::Expr::LoopExpr(loop_expr) => {
+                let loop_head = self.add_dummy_node(&[pred]);
+                // only reachable with `break`
+                let loop_exit = self.add_dummy_node(&[]);
+                self.with_loop_scope(loop_expr.label_decl(), loop_head, loop_exit, |this| {
+                    let body_exit = this.opt_block_or_inline_expr(loop_expr.loop_body_expr(), loop_head);
+                    this.add_edge(body_exit, loop_head);
+                });
+                self.add_expr_node(expr, &[loop_exit])
+            }
+            ast::Expr::WhileExpr(while_expr) => {
+                let loop_head = self.add_dummy_node(&[pred]);
+                let condition_exit =
+                    self.opt_expr(while_expr.condition().and_then(|it| it.expr()), loop_head);
+                let loop_exit = self.add_dummy_node(&[condition_exit]);
+                self.with_loop_scope(while_expr.label_decl(), loop_head, loop_exit, |this| {
+                    let body_exit =
+                        this.opt_block_or_inline_expr(while_expr.loop_body_expr(), condition_exit);
+                    this.add_edge(body_exit, loop_head);
+                });
+                self.add_expr_node(expr, &[loop_exit])
+            }
+            ast::Expr::ForExpr(for_expr) => {
+                let iterable_exit =
+                    self.opt_expr(for_expr.for_condition().and_then(|it| it.expr()), pred);
+                let loop_head = self.add_dummy_node(&[iterable_exit]);
+                let loop_exit = self.add_dummy_node(&[loop_head]);
+                self.with_loop_scope(for_expr.label_decl(), loop_head, loop_exit, |this| {
+                    let body_exit = this.opt_block_or_inline_expr(for_expr.loop_body_expr(), loop_head);
+                    this.add_edge(body_exit, loop_head);
+                });
+                self.add_expr_node(expr, &[loop_exit])
+            }
+            ast::Expr::MatchExpr(match_expr) => {
+                let scrutinee_exit = self.opt_expr(match_expr.expr(), pred);
+                let mut arm_exits = vec![];
+                for arm in match_expr.arms() {
+                    let guard_exit =
+                        self.opt_expr(arm.match_guard().and_then(|it| it.expr()), scrutinee_exit);
+                    arm_exits.push(self.opt_expr(arm.expr(), guard_exit));
+                }
+                if arm_exits.is_empty() {
+                    arm_exits.push(scrutinee_exit);
+                }
+                self.add_expr_node(expr, &arm_exits)
+            }
+            ast::Expr::BinExpr(bin_expr)
+                if matches!(
+                    bin_expr.op_kind(),
+                    Some(BinaryOp::LogicOp(LogicOp::And | LogicOp::Or))
+                ) =>
+            {
+                // short-circuiting, rhs might not be evaluated
+                let lhs_exit = self.opt_expr(bin_expr.lhs(), pred);
+                let rhs_exit = self.opt_expr(bin_expr.rhs(), lhs_exit);
+                self.add_expr_node(expr, &[lhs_exit, rhs_exit])
+            }
+            ast::Expr::ReturnExpr(return_expr) => {
+                let value_exit = self.opt_expr(return_expr.expr(), pred);
+                let return_node = self.add_expr_node(expr, &[value_exit]);
+                self.add_edge(return_node, self.exit);
+                self.add_dummy_node(&[])
+            }
+            ast::Expr::AbortExpr(abort_expr) => {
+                let code_exit = self.opt_expr(abort_expr.expr(), pred);
+                self.add_expr_node(expr, &[code_exit]);
+                self.add_dummy_node(&[])
+            }
+            ast::Expr::BreakExpr(break_expr) => {
+                let break_node = self.add_expr_node(expr, &[pred]);
+                if let Some(loop_scope) = self.find_loop_scope(break_expr.label()) {
+                    self.add_edge(break_node, loop_scope.break_target);
+                }
+                self.add_dummy_node(&[])
+            }
+            ast::Expr::ContinueExpr(continue_expr) => {
+                let continue_node = self.add_expr_node(expr, &[pred]);
+                if let Some(loop_scope) = self.find_loop_scope(continue_expr.label()) {
+                    self.add_edge(continue_node, loop_scope.continue_target);
+                }
+                self.add_dummy_node(&[])
+            }
+            // not evaluated in place
+            ast::Expr::LambdaExpr(_) | ast::Expr::SpecBlockExpr(_) => self.add_expr_node(expr, &[pred]),
+            _ => {
+                let mut pred = pred;
+                for child_expr in child_exprs(expr.syntax()) {
+                    pred = self.expr(&child_expr, pred);
+                }
+                self.add_expr_node(expr, &[pred])
+            }
+        }
+    }
+
+    fn opt_expr(&mut self, expr: Option<ast::Expr>, pred: CfgNodeId) -> CfgNodeId {
+        match expr {
+            Some(expr) => self.expr(&expr, pred),
+            None => pred,
+        }
+    }
+
+    fn block_expr(&mut self, block_expr: &ast::BlockExpr, pred: CfgNodeId) -> CfgNodeId {
+        let mut pred = pred;
+        for stmt in block_expr.stmts() {
+            let stmt_entry = self.add_block_element_entry(stmt.syntax(), pred);
+            pred = self.stmt(&stmt, stmt_entry);
+        }
+        if let Some(tail_expr) = block_expr.tail_expr() {
+            let tail_entry = self.add_block_element_entry(tail_expr.syntax(), pred);
+            pred = self.expr(&tail_expr, tail_entry);
+        }
+        self.add_expr_node(&block_expr.clone().into(), &[pred])
+    }
+
+    fn opt_block_or_inline_expr(
+        &mut self,
+        block_or_inline_expr: Option<ast::BlockOrInlineExpr>,
+        pred: CfgNodeId,
+    ) -> CfgNodeId {
+        match block_or_inline_expr {
+            Some(ast::BlockOrInlineExpr::BlockExpr(block_expr)) => self.block_expr(&block_expr, pred),
+            Some(ast::BlockOrInlineExpr::InlineExpr(inline_expr)) => {
+                self.opt_expr(inline_expr.expr(), pred)
+            }
+            None => pred,
+        }
+    }
+
+    fn stmt(&mut self, stmt: &ast::Stmt, pred: CfgNodeId) -> CfgNodeId {
+        match stmt {
+            ast::Stmt::LetStmt(let_stmt) => self.opt_expr(let_stmt.initializer(), pred),
+            ast::Stmt::ExprStmt(expr_stmt) => self.opt_expr(expr_stmt.expr(), pred),
+            // specification statements are not executed
+            _ => pred,
+        }
+    }
+
+    fn with_loop_scope(
+        &mut self,
+        label_decl: Option<ast::LabelDecl>,
+        continue_target: CfgNodeId,
+        break_target: CfgNodeId,
+        f: impl FnOnce(&mut Self),
+    ) {
+        if let Some(label_decl) = &label_decl {
+            self.label_decls.push(label_decl.clone());
+        }
+        self.loop_scopes.push(LoopScope {
+            label_decl,
+            continue_target,
+            break_target,
+        });
+        f(self);
+        self.loop_scopes.pop();
+    }
+
+    /// Innermost loop for the unlabeled `break` and `continue`, or the loop with the matching label.
+    fn find_loop_scope(&mut self, label: Option<ast::Label>) -> Option<LoopScope> {
+        let Some(label) = label else {
+            return self.loop_scopes.last().cloned();
+        };
+        let label_name = label.name_as_string();
+        let loop_scope = self
+            .loop_scopes
+            .iter()
+            .rev()
+            .find(|it| {
+                it.label_decl
+                    .as_ref()
+                    .is_some_and(|label_decl| label_decl.name_as_string() == label_name)
+            })
+            .cloned()?;
+        if let Some(label_decl) = &loop_scope.label_decl {
+            self.used_label_decls.insert(label_decl.clone());
+        }
+        Some(loop_scope)
+    }
+
+    fn add_expr_node(&mut self, expr: &ast::Expr, preds: &[CfgNodeId]) -> CfgNodeId {
+        let id = self.add_node(CfgNodeKind::Expr(expr.clone()), preds);
+        self.expr_nodes.insert(expr.clone(), id);
+        id
+    }
+
+    fn add_block_element_entry(&mut self, element: &SyntaxNode, pred: CfgNodeId) -> CfgNodeId {
+        let id = self.add_dummy_node(&[pred]);
+        self.block_element_entries.insert(element.clone(), id);
+        id
+    }
+
+    fn add_dummy_node(&mut self, preds: &[CfgNodeId]) -> CfgNodeId {
+        self.add_node(CfgNodeKind::Dummy, preds)
+    }
+
+    fn add_node(&mut self, kind: CfgNodeKind, preds: &[CfgNodeId]) -> CfgNodeId {
+        let id = CfgNodeId(self.nodes.len() as u32);
+        self.nodes.push(CfgNode {
+            kind,
+            predecessors: vec![],
+            successors: vec![],
+        });
+        for pred in preds {
+            self.add_edge(*pred, id);
+        }
+        id
+    }
+
+    fn add_edge(&mut self, from: CfgNodeId, to: CfgNodeId) {
+        self.nodes[from.idx()].successors.push(to);
+        self.nodes[to.idx()].predecessors.push(from);
+    }
+}
+
+/// Subexpressions in the order of evaluation, looking through the non-expression nodes
+/// like argument lists and struct literal fields.
+fn child_exprs(node: &SyntaxNode) -> Vec<ast::Expr> {
+    let mut exprs = vec![];
+    for child in node.children() {
+        match ast::Expr::cast(child.clone()) {
+            Some(expr) => exprs.push(expr),
+            None => exprs.extend(child_exprs(&child)),
+        }
+    }
+    exprs
+}
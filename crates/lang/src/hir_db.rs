@@ -79,7 +79,15 @@ pub(crate) fn inference(db: &dyn SourceDatabase, owner_loc: SyntaxLoc, msl: bool
     inference_tracked(db, SyntaxLocInput::new(db, owner_loc), msl)
 }
 
-#[salsa_macros::tracked(returns(ref))]
+pub const DEFAULT_INFERENCE_LRU_CAP: usize = 1024;
+
+/// Sets the number of the inference results which are kept in memory after the revision change,
+/// least recently used ones are evicted.
+pub fn set_inference_lru_capacity(db: &mut dyn SourceDatabase, capacity: usize) {
+    inference_tracked::set_lru_capacity(db, capacity);
+}
+
+#[salsa_macros::tracked(returns(ref), lru = 1024)]
 fn inference_tracked<'db>(
     db: &'db dyn SourceDatabase,
     ctx_owner_loc: SyntaxLocInput<'db>,
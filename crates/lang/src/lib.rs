@@ -8,6 +8,8 @@ pub mod nameres;
 pub(crate) mod semantics;
 
 pub mod builtins_file;
+pub mod const_eval;
+pub mod control_flow;
 pub mod hir_db;
 pub mod item_scope;
 pub mod item_specs;
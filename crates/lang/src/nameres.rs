@@ -22,6 +22,7 @@ use syntax::{AstNode, ast};
 pub mod address;
 pub mod binding;
 mod blocks;
+pub mod doc_links;
 pub mod fq_named_element;
 pub mod is_visible;
 pub mod labels;
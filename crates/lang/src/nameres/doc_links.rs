@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolution of the intra-doc links, like [`vector::push_back`], in the doc comments.
+
+use crate::hir_db;
+use crate::nameres::address::Address;
+use crate::nameres::name_resolution::{
+    WalkScopesCtx, get_entries_from_walking_scopes, get_modules_as_entries,
+};
+use crate::nameres::namespaces::ALL_NS;
+use crate::nameres::scope::{NamedItemsInFileExt, ScopeEntry, ScopeEntryExt};
+use base_db::SourceDatabase;
+use syntax::files::{InFile, InFileExt};
+use syntax::{SyntaxKind, SyntaxNode, ast};
+
+/// Resolves `link` path from the doc comment of the `owner` item. Supports `name`, `module::name`,
+/// `address::module` and `address::module::name` paths, names are looked up the same way
+/// as if the path was written in the body of the item.
+pub fn resolve_doc_link(
+    db: &dyn SourceDatabase,
+    owner: InFile<SyntaxNode>,
+    link: &str,
+) -> Option<ScopeEntry> {
+    let segments = link.split("::").collect::<Vec<_>>();
+    match segments.as_slice() {
+        [name] => entry_in_scope(db, &owner, name),
+        [qualifier, name] => {
+            let qualifier_entry = entry_in_scope(db, &owner, qualifier)
+                .or_else(|| enclosing_module_entry(&owner, qualifier));
+            match qualifier_entry {
+                Some(qualifier_entry) => member_entry(db, &qualifier_entry, name),
+                // `address::module`
+                None => module_entry(db, &owner, qualifier, name),
+            }
+        }
+        [address, module_name, name] => {
+            let module = module_entry(db, &owner, address, module_name)?;
+            member_entry(db, &module, name)
+        }
+        _ => None,
+    }
+}
+
+fn entry_in_scope(
+    db: &dyn SourceDatabase,
+    owner: &InFile<SyntaxNode>,
+    name: &str,
+) -> Option<ScopeEntry> {
+    let walk_ctx = WalkScopesCtx {
+        allowed_ns: ALL_NS,
+        start_at: owner.clone(),
+        expected_name: Some(name.to_string()),
+    };
+    get_entries_from_walking_scopes(db, walk_ctx).into_iter().next()
+}
+
+/// Docs often refer to the items of the current module with the module name, like `coin::value`.
+fn enclosing_module_entry(owner: &InFile<SyntaxNode>, module_name: &str) -> Option<ScopeEntry> {
+    let module = owner.value.ancestors().find_map(ast::Module::cast)?;
+    if module.name()?.as_string() != module_name {
+        return None;
+    }
+    module.in_file(owner.file_id).to_entry()
+}
+
+fn module_entry(
+    db: &dyn SourceDatabase,
+    owner: &InFile<SyntaxNode>,
+    address: &str,
+    module_name: &str,
+) -> Option<ScopeEntry> {
+    let address = if address.starts_with(|it: char| it.is_ascii_digit()) {
+        Address::value(address)
+    } else {
+        Address::named(address)
+    };
+    let package_id = db.file_package_id(owner.file_id);
+    get_modules_as_entries(db, package_id, address)
+        .into_iter()
+        .find(|it| it.name == module_name)
+}
+
+fn member_entry(db: &dyn SourceDatabase, container: &ScopeEntry, name: &str) -> Option<ScopeEntry> {
+    let members = match container.node_loc.kind() {
+        SyntaxKind::MODULE => {
+            let module_loc = container.node_loc.clone();
+            let mut entries = hir_db::module_importable_entries(db, module_loc.clone());
+            entries.extend(hir_db::module_importable_entries_from_related(db, module_loc));
+            entries
+        }
+        SyntaxKind::ENUM => {
+            let enum_ = container.cast_into::<ast::Enum>(db)?;
+            enum_.value.variants().to_entries(enum_.file_id)
+        }
+        _ => return None,
+    };
+    members.into_iter().find(|it| it.name == name)
+}
@@ -52,11 +52,14 @@ impl<'a, 'db> TypeAstWalker<'a, 'db> {
         match ctx_owner {
             ast::InferenceCtxOwner::Fun(fun) => {
                 if let Some(fun_block_expr) = fun.body() {
-                    self.infer_block_expr(
-                        &fun_block_expr,
-                        Expected::ExpectType(self.expected_return_ty.clone()),
-                        true,
-                    );
+                    // missing return value is reported by the control flow analysis,
+                    // as the body might end with `return` or `abort`
+                    let expected_return = if fun_block_expr.tail_expr().is_some() {
+                        Expected::ExpectType(self.expected_return_ty.clone())
+                    } else {
+                        Expected::NoValue
+                    };
+                    self.infer_block_expr(&fun_block_expr, expected_return, true);
                 }
             }
             ast::InferenceCtxOwner::SpecFun(spec_fun) => {
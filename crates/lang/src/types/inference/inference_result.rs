@@ -12,8 +12,10 @@ use crate::types::inference::combine_types::TypeError;
 use crate::types::ty::Ty;
 use crate::types::ty::ty_callable::TyCallable;
 use crate::types::ty::ty_var::TyInfer;
+use base_db::memory_counters::TrackedSize;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::mem::size_of;
 use syntax::{AstNode, TextRange, ast};
 use vfs::FileId;
 
@@ -30,11 +32,15 @@ pub struct InferenceResult {
     resolved_method_calls: HashMap<SyntaxLoc, Option<ScopeEntry>>,
     resolved_fields: HashMap<SyntaxLoc, Option<ScopeEntry>>,
     resolved_ident_pats: HashMap<SyntaxLoc, Option<ScopeEntry>>,
+
+    /// Counted in the memory usage of the database while the result is memoized.
+    memory_size: Option<TrackedSize<FileId>>,
 }
 
 impl InferenceResult {
     pub fn from_ctx(mut ctx: InferenceCtx) -> Self {
         Self::unify_remaining_int_vars_into_integer(&mut ctx);
+        let db = ctx.db;
 
         let type_errors = ctx
             .type_errors
@@ -71,7 +77,7 @@ impl InferenceResult {
         let resolved_fields = keys_into_syntax_loc(ctx.resolved_fields, file_id);
         let resolved_ident_pats = keys_into_syntax_loc(ctx.resolved_ident_pats, file_id);
 
-        InferenceResult {
+        let mut inference_result = InferenceResult {
             file_id: ctx.file_id,
             type_errors,
             pat_types,
@@ -81,7 +87,35 @@ impl InferenceResult {
             resolved_method_calls,
             resolved_fields,
             resolved_ident_pats,
+            memory_size: None,
+        };
+        let bytes = inference_result.estimate_memory_size();
+        inference_result.memory_size =
+            Some(db.memory_counters().inference_results.track(file_id, bytes));
+
+        inference_result
+    }
+
+    /// Shallow estimate, heap allocations of the types and scope entries are not counted.
+    fn estimate_memory_size(&self) -> usize {
+        fn map_size<V>(map: &HashMap<SyntaxLoc, V>) -> usize {
+            map.capacity() * (size_of::<SyntaxLoc>() + size_of::<V>())
         }
+        let resolved_paths_size = self
+            .resolved_paths
+            .values()
+            .map(|entries| entries.capacity() * size_of::<ScopeEntryWithVis>())
+            .sum::<usize>();
+        size_of::<Self>()
+            + self.type_errors.capacity() * size_of::<TypeError>()
+            + map_size(&self.pat_types)
+            + map_size(&self.expr_types)
+            + map_size(&self.call_expr_types)
+            + map_size(&self.resolved_paths)
+            + resolved_paths_size
+            + map_size(&self.resolved_method_calls)
+            + map_size(&self.resolved_fields)
+            + map_size(&self.resolved_ident_pats)
     }
 
     fn unify_remaining_int_vars_into_integer(ctx: &mut InferenceCtx) {
@@ -112,6 +112,10 @@ impl AptosPackage {
         &self.transitive_dep_roots
     }
 
+    pub fn kind(&self) -> PackageKind {
+        self.kind
+    }
+
     pub fn manifest_path(&self) -> &AbsPath {
         self.manifest_path.as_path()
     }
@@ -14,6 +14,7 @@
 
 mod constructors;
 mod exprs;
+mod items;
 mod paths;
 mod uses;
 
@@ -175,6 +175,12 @@ impl SyntaxFactory {
 
         new_pat_field
     }
+
+    pub fn match_arm(&self, pat_text: &str, expr_text: &str) -> ast::MatchArm {
+        let match_expr =
+            expr_from_text::<ast::MatchExpr>(&format!("match (x) {{ {pat_text} => {expr_text}, }}"));
+        match_expr.arms().pop().unwrap().clone_for_update()
+    }
 }
 
 pub(super) fn expr_from_text<E: Into<ast::Expr> + AstNode>(text: &str) -> E {
@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ast::syntax_factory::{SyntaxFactory, ast_from_text, module_item_from_text};
+use crate::{AstNode, ast};
+
+impl SyntaxFactory {
+    pub fn fun_from_text(&self, fun_text: &str) -> ast::Fun {
+        module_item_from_text::<ast::Fun>(fun_text).clone_for_update()
+    }
+
+    pub fn module_from_text(&self, module_text: &str) -> ast::Module {
+        ast_from_text::<ast::Module>(module_text).clone_for_update()
+    }
+
+    pub fn item_from_text(&self, item_text: &str) -> ast::Item {
+        module_item_from_text::<ast::Item>(item_text).clone_for_update()
+    }
+
+    pub fn friend(&self, module_path: ast::Path) -> ast::Friend {
+        module_item_from_text::<ast::Friend>(&format!("friend {module_path};")).clone_for_update()
+    }
+
+    pub fn visibility_modifier(&self, visibility: &str) -> ast::VisibilityModifier {
+        module_item_from_text::<ast::VisibilityModifier>(&format!("{visibility} fun main() {{}}"))
+            .clone_for_update()
+    }
+
+    pub fn item_spec_from_text(&self, item_spec_text: &str) -> ast::ItemSpec {
+        module_item_from_text::<ast::ItemSpec>(item_spec_text).clone_for_update()
+    }
+
+    pub fn spec_stmt_from_text(&self, stmt_text: &str) -> ast::Stmt {
+        ast_from_text::<ast::Stmt>(&format!("module 0x1::m {{ spec main {{ {stmt_text} }} }}"))
+            .clone_for_update()
+    }
+}
@@ -36,6 +36,10 @@ impl SyntaxFactory {
         module_item_from_text::<ast::UseSpeck>(&buf).clone_for_update()
     }
 
+    pub fn use_alias(&self, name: &str) -> ast::UseAlias {
+        module_item_from_text::<ast::UseAlias>(&format!("use 0x1::m as {name};")).clone_for_update()
+    }
+
     pub fn use_speck_with_group(
         &self,
         module_path: ast::Path,
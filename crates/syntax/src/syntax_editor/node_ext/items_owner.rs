@@ -1,7 +1,8 @@
+use crate::ast::HasItems;
 use crate::ast::edit::IndentLevel;
 use crate::ast::syntax_factory::SyntaxFactory;
-use crate::syntax_editor::{Element, SyntaxEditor};
-use crate::{AstNode, ast};
+use crate::syntax_editor::{Element, Position, SyntaxEditor};
+use crate::{AstNode, ast, match_ast};
 
 impl ast::AnyHasItems {
     pub fn add_use_stmt(&self, editor: &mut SyntaxEditor, use_stmt: &ast::UseStmt) -> Option<()> {
@@ -20,4 +21,41 @@ impl ast::AnyHasItems {
 
         Some(())
     }
+
+    /// Adds an item after the last item of the owner, separated with the blank line.
+    pub fn add_item(&self, editor: &mut SyntaxEditor, item: &impl AstNode) -> Option<()> {
+        let make = SyntaxFactory::new();
+        let indent = IndentLevel::from_node(self.syntax()) + 1;
+        match self.items().last() {
+            Some(last_item) => {
+                editor.insert_all(
+                    Position::after(last_item.syntax()),
+                    vec![
+                        make.whitespace(&format!("\n\n{indent}")).syntax_element(),
+                        item.syntax().syntax_element(),
+                    ],
+                );
+            }
+            None => {
+                let l_curly = match_ast! {
+                    match (self.syntax) {
+                        ast::Module(it) => it.l_curly_token(),
+                        ast::Script(it) => it.l_curly_token(),
+                        ast::ModuleSpec(it) => it.l_curly_token(),
+                        _ => None,
+                    }
+                }?;
+                let outer_indent = IndentLevel::from_node(self.syntax());
+                editor.insert_all(
+                    Position::after(l_curly),
+                    vec![
+                        make.whitespace(&format!("\n{indent}")).syntax_element(),
+                        item.syntax().syntax_element(),
+                        make.whitespace(&format!("\n{outer_indent}")).syntax_element(),
+                    ],
+                );
+            }
+        }
+        Some(())
+    }
 }
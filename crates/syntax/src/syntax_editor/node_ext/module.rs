@@ -1,5 +1,6 @@
 use crate::SyntaxKind::WHITESPACE;
 use crate::ast::UseStmtsOwner;
+use crate::ast::edit::IndentLevel;
 use crate::ast::node_ext::syntax_element::SyntaxElementExt;
 use crate::ast::syntax_factory::SyntaxFactory;
 use crate::syntax_editor::{Element, Position, SyntaxEditor};
@@ -33,3 +34,26 @@ impl ast::AnyHasItems {
         anchor.map(|it| (Position::after(it), !has_extra_newline_at_the_end))
     }
 }
+
+impl ast::Module {
+    /// Adds the `friend` declaration after the existing ones, or after the `use` statements otherwise.
+    pub fn add_friend_decl(&self, editor: &mut SyntaxEditor, friend: &ast::Friend) -> Option<()> {
+        let (anchor, needs_newline_at_the_end) = match self.friend_decls().last() {
+            Some(last_friend) => (Position::after(last_friend.syntax()), false),
+            None => ast::AnyHasItems::from(self.clone()).pos_after_last_use_stmt()?,
+        };
+
+        let make = SyntaxFactory::new();
+        let indent = IndentLevel::from_node(self.syntax()) + 1;
+        let mut elements_to_add = vec![
+            make.whitespace(&format!("\n{indent}")).syntax_element(),
+            friend.syntax().syntax_element(),
+        ];
+        if needs_newline_at_the_end {
+            elements_to_add.push(make.newline().into());
+        }
+        editor.insert_all(anchor, elements_to_add);
+
+        Some(())
+    }
+}
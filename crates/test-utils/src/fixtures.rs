@@ -21,6 +21,12 @@ use vfs::{FileId, VfsPath};
 const BUILTINS_FILE_ID: FileId = FileId::from_raw(0);
 
 pub fn from_single_file(text: impl Into<String>) -> (Analysis, FileId) {
+    let (test_package, file_id) = test_package_from_single_file(text);
+    (test_package.analysis(), file_id)
+}
+
+/// Same as [`from_single_file`], but keeps the [`TestPackage`] to change the database afterwards.
+pub fn test_package_from_single_file(text: impl Into<String>) -> (TestPackage, FileId) {
     let text = text.into();
     let mut test_package = TestPackage::new();
 
@@ -35,7 +41,7 @@ pub fn from_single_file(text: impl Into<String>) -> (Analysis, FileId) {
 
     test_package.apply_changes(changes);
 
-    (test_package.analysis(), file_id)
+    (test_package, file_id)
 }
 
 pub struct TestPackage {
@@ -80,6 +86,11 @@ impl TestPackage {
         self.analysis().file_text(file_id).unwrap().to_string()
     }
 
+    /// Cancels the running queries, blocks until all [`Analysis`] snapshots are dropped.
+    pub fn request_cancellation(&mut self) {
+        self.analysis_host.request_cancellation();
+    }
+
     pub(crate) fn apply_changes(&mut self, changes: FileChanges) {
         for (file_id, _) in &changes.files_changed {
             self.files.insert(file_id.to_owned());
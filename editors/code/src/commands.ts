@@ -76,6 +76,33 @@ export function analyzerStatus(ctx: CtxInit): Cmd {
     };
 }
 
+export function memoryUsage(ctx: CtxInit): Cmd {
+    const tdcp = new (class implements vscode.TextDocumentContentProvider {
+        readonly uri = vscode.Uri.parse("aptos-lsp-memory://memory");
+        readonly eventEmitter = new vscode.EventEmitter<vscode.Uri>();
+
+        provideTextDocumentContent(_uri: vscode.Uri): vscode.ProviderResult<string> {
+            if (!vscode.window.activeTextEditor) return "";
+
+            return ctx.client.sendRequest(lsp_ext.memoryUsage);
+        }
+
+        get onDidChange(): vscode.Event<vscode.Uri> {
+            return this.eventEmitter.event;
+        }
+    })();
+
+    ctx.pushExtCleanup(
+        vscode.workspace.registerTextDocumentContentProvider("aptos-lsp-memory", tdcp),
+    );
+
+    return async () => {
+        tdcp.eventEmitter.fire(tdcp.uri);
+        const document = await vscode.workspace.openTextDocument(tdcp.uri);
+        return vscode.window.showTextDocument(document, vscode.ViewColumn.Beside, true);
+    };
+}
+
 export function toggleLSPLogs(ctx: Ctx): Cmd {
     return async () => {
         const config = vscode.workspace.getConfiguration("move-on-aptos");
@@ -177,6 +204,39 @@ export function runTest(ctx: CtxInit): Cmd {
     };
 }
 
+let proverOutputChannel: vscode.OutputChannel | undefined;
+
+export function runProver(ctx: CtxInit): Cmd {
+    return async (runnable: lsp_ext.Runnable) => {
+        const client = ctx.client;
+        const result = await vscode.window.withProgress(
+            {
+                location: vscode.ProgressLocation.Notification,
+                title: `Running Move Prover: ${runnable.label}`,
+            },
+            () => client.sendRequest(lsp_ext.runProver, runnable.args),
+        );
+
+        if (!proverOutputChannel) {
+            proverOutputChannel = vscode.window.createOutputChannel("Move Prover");
+            ctx.pushExtCleanup(proverOutputChannel);
+        }
+        proverOutputChannel.replace(result.output);
+
+        if (result.success) {
+            void vscode.window.showInformationMessage(`Move Prover: ${runnable.label} succeeded`);
+            return;
+        }
+        const message = result.nErrors > 0
+            ? `Move Prover: ${result.nErrors} verification error(s), see Problems view`
+            : "Move Prover failed";
+        const choice = await vscode.window.showErrorMessage(message, "Show Output");
+        if (choice === "Show Output") {
+            proverOutputChannel.show(true);
+        }
+    };
+}
+
 export function debugTest(_ctx: CtxInit): Cmd {
     return async (runnable: lsp_ext.Runnable) => {
         const args = runnable.args;
@@ -230,6 +230,24 @@ export class Ctx {
                     }
                 }),
             );
+            // `Move.toml` files are not in the document selector of the client,
+            // so the `local` dependency links are requested separately
+            const client = this._client;
+            this.pushClientCleanup(
+                vscode.languages.registerDocumentLinkProvider(
+                    { scheme: "file", pattern: "**/Move.toml" },
+                    {
+                        async provideDocumentLinks(document, token) {
+                            const links = await client.sendRequest(
+                                lc.DocumentLinkRequest.type,
+                                { textDocument: { uri: document.uri.toString() } },
+                                token,
+                            );
+                            return client.protocol2CodeConverter.asDocumentLinks(links, token);
+                        },
+                    },
+                ),
+            );
         }
         return this._client;
     }
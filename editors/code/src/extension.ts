@@ -208,7 +208,7 @@ function createCommands(): Record<string, CommandFactory> {
         },
 
         analyzerStatus: { enabled: commands.analyzerStatus },
-        // memoryUsage: { enabled: commands.memoryUsage },
+        memoryUsage: { enabled: commands.memoryUsage },
         // reloadWorkspace: { enabled: commands.reloadWorkspace },
         // rebuildProcMacros: { enabled: commands.rebuildProcMacros },
         // matchingBrace: { enabled: commands.matchingBrace },
@@ -245,6 +245,7 @@ function createCommands(): Record<string, CommandFactory> {
         runTest: { enabled: commands.runTest },
         debugTest: { enabled: commands.debugTest },
         debugTransaction: { enabled: commands.debugTransaction },
+        runProver: { enabled: commands.runProver },
         showReferences: { enabled: commands.showReferences },
         // triggerParameterHints: { enabled: commands.triggerParameterHints },
         // rename: { enabled: commands.rename },
@@ -15,6 +15,13 @@ export const analyzerStatus = new lc.RequestType<AnalyzerStatusParams, string, v
 );
 export type AnalyzerStatusParams = { textDocument?: lc.TextDocumentIdentifier };
 
+export const memoryUsage = new lc.RequestType0<string, void>("aptos-language-server/memoryUsage");
+
+export const symbolId = new lc.RequestType<SymbolIdParams, lc.Location[], void>(
+    "aptos-language-server/symbolId",
+);
+export type SymbolIdParams = { identifier: string };
+
 export const openServerLogs = new lc.NotificationType0("aptos-language-server/openServerLogs");
 
 export const serverStatus = new lc.NotificationType<ServerStatusParams>(
@@ -63,6 +70,16 @@ export type AptosRunnableArgs = {
     depRoots?: string[];
 };
 
+export const runProver = new lc.RequestType<AptosRunnableArgs, RunProverResult, void>(
+    "aptos-language-server/runProver",
+);
+
+export type RunProverResult = {
+    success: boolean;
+    nErrors: number;
+    output: string;
+};
+
 export const organizeImports = new lc.RequestType<OrganizeImportsParams, lc.TextEdit[], void>(
     "experimental/organizeImports",
 );